@@ -0,0 +1,83 @@
+// 构建脚本：注入构建信息，供系统监控接口展示；将 migrations 目录下的迁移嵌入程序
+
+use std::fmt::Write;
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    let git_commit = command_output("git", &["rev-parse", "--short", "HEAD"]);
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version = command_output(&rustc, &["--version"]);
+    let build_timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+
+    println!("cargo:rustc-env=BUILD_GIT_COMMIT={}", git_commit);
+    println!("cargo:rustc-env=BUILD_RUSTC_VERSION={}", rustc_version);
+    println!("cargo:rustc-env=BUILD_TIMESTAMP={}", build_timestamp);
+
+    embed_migrations();
+}
+
+/// 生成 `$OUT_DIR/migrations.rs`，按版本号排列的迁移列表
+///
+/// 文件名格式为 `{版本号}_{名称}.sql`，回滚脚本为同名的 `.down.sql`。
+fn embed_migrations() {
+    println!("cargo:rerun-if-changed=migrations");
+
+    let dir = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("migrations");
+    let mut migrations = Vec::new();
+    for entry in std::fs::read_dir(&dir).expect("无法读取 migrations 目录") {
+        let path = entry.expect("无法读取 migrations 目录").path();
+        let file_name = path.file_name().unwrap().to_string_lossy().to_string();
+        if file_name.ends_with(".down.sql") {
+            continue;
+        }
+        let Some(stem) = file_name.strip_suffix(".sql") else {
+            continue;
+        };
+        let (version, name) = stem
+            .split_once('_')
+            .and_then(|(v, n)| Some((v.parse::<i64>().ok()?, n.to_string())))
+            .unwrap_or_else(|| panic!("迁移文件名格式应为 版本号_名称.sql: {}", file_name));
+        let down = dir.join(format!("{}.down.sql", stem));
+        migrations.push((version, name, path.clone(), down.exists().then_some(down)));
+    }
+    migrations.sort_by_key(|(version, ..)| *version);
+    if let Some(w) = migrations.windows(2).find(|w| w[0].0 == w[1].0) {
+        panic!("迁移版本号重复: {}", w[0].0);
+    }
+
+    let mut out = String::from("pub static MIGRATIONS: &[Migration] = &[\n");
+    for (version, name, up, down) in &migrations {
+        let down = match down {
+            Some(path) => format!("Some(include_str!({:?}))", path.display().to_string()),
+            None => "None".to_string(),
+        };
+        writeln!(
+            out,
+            "    Migration {{ version: {}, name: {:?}, up: include_str!({:?}), down: {} }},",
+            version,
+            name,
+            up.display().to_string(),
+            down
+        )
+        .unwrap();
+    }
+    out.push_str("];\n");
+
+    let target = Path::new(&std::env::var("OUT_DIR").unwrap()).join("migrations.rs");
+    std::fs::write(target, out).expect("无法写入迁移列表");
+}
+
+fn command_output(program: &str, args: &[&str]) -> String {
+    Command::new(program)
+        .args(args)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS role_menus;
+DROP TABLE IF EXISTS menus;
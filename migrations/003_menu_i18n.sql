@@ -0,0 +1,13 @@
+-- 菜单名称多语言
+-- name 保存默认语言（中文）名称，name_i18n 保存其它语言的译名，如 {"en-US": "Home"}
+ALTER TABLE menus ADD COLUMN IF NOT EXISTS name_i18n JSONB;
+
+-- 默认菜单英文名称
+UPDATE menus SET name_i18n = '{"en-US": "Home"}'::JSONB WHERE id = 'c0000000-0000-0000-0000-000000000001'::UUID;
+UPDATE menus SET name_i18n = '{"en-US": "System"}'::JSONB WHERE id = 'c0000000-0000-0000-0000-000000000100'::UUID;
+UPDATE menus SET name_i18n = '{"en-US": "Users"}'::JSONB WHERE id = 'c0000000-0000-0000-0000-000000000101'::UUID;
+UPDATE menus SET name_i18n = '{"en-US": "Roles"}'::JSONB WHERE id = 'c0000000-0000-0000-0000-000000000102'::UUID;
+UPDATE menus SET name_i18n = '{"en-US": "Menus"}'::JSONB WHERE id = 'c0000000-0000-0000-0000-000000000103'::UUID;
+UPDATE menus SET name_i18n = '{"en-US": "Add User"}'::JSONB WHERE id = 'c0000000-0000-0000-0000-000000000111'::UUID;
+UPDATE menus SET name_i18n = '{"en-US": "Edit User"}'::JSONB WHERE id = 'c0000000-0000-0000-0000-000000000112'::UUID;
+UPDATE menus SET name_i18n = '{"en-US": "Delete User"}'::JSONB WHERE id = 'c0000000-0000-0000-0000-000000000113'::UUID;
@@ -0,0 +1,4 @@
+-- 角色菜单关联随菜单级联删除
+DELETE FROM menus WHERE id = 'c0000000-0000-0000-0000-000000000104'::UUID;
+
+DROP TABLE IF EXISTS system_params;
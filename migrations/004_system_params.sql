@@ -0,0 +1,48 @@
+-- 创建系统参数表
+CREATE TABLE IF NOT EXISTS system_params (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    param_key VARCHAR(100) NOT NULL UNIQUE,      -- 参数键，如 sys.site.title
+    param_value TEXT NOT NULL,                   -- 参数值（按 value_type 解析）
+    value_type VARCHAR(20) NOT NULL DEFAULT 'string', -- 类型：string/number/boolean/json
+    param_group VARCHAR(50) NOT NULL DEFAULT 'default', -- 参数分组
+    description VARCHAR(255),                    -- 描述
+    is_builtin BOOLEAN NOT NULL DEFAULT FALSE,   -- 是否内置（内置参数不可删除、不可修改键和类型）
+    is_public BOOLEAN NOT NULL DEFAULT FALSE,    -- 是否公开给前端（无需登录）
+    created_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    created_id UUID,
+    updated_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_id UUID,
+    deleted_time TIMESTAMP,
+    deleted_id UUID,
+    CONSTRAINT chk_system_params_value_type CHECK (value_type IN ('string', 'number', 'boolean', 'json'))
+);
+
+CREATE INDEX idx_system_params_group ON system_params(param_group);
+CREATE INDEX idx_system_params_deleted_time ON system_params(deleted_time);
+
+-- 内置参数
+INSERT INTO system_params (param_key, param_value, value_type, param_group, description, is_builtin, is_public)
+VALUES
+    ('sys.site.title', 'Maple Admin', 'string', 'site', '站点标题', TRUE, TRUE),
+    ('sys.site.default_locale', 'zh-CN', 'string', 'site', '前端默认语言', TRUE, TRUE),
+    ('sys.recycle_bin.retention_days', '30', 'number', 'recycle_bin', '回收站记录保留天数', TRUE, FALSE)
+ON CONFLICT (param_key) DO NOTHING;
+
+-- 系统参数菜单
+INSERT INTO menus (id, parent_id, name, name_i18n, menu_type, path, component, icon, permission, sort, is_show)
+VALUES (
+    'c0000000-0000-0000-0000-000000000104'::UUID,
+    'c0000000-0000-0000-0000-000000000100'::UUID,
+    '系统参数',
+    '{"en-US": "Parameters"}'::JSONB,
+    'menu',
+    '/system/param',
+    '/views/system/param/index',
+    'mdi:tune',
+    'system:param:list',
+    4,
+    TRUE
+);
+
+INSERT INTO role_menus (role_id, menu_id)
+VALUES ('a0000000-0000-0000-0000-000000000001'::UUID, 'c0000000-0000-0000-0000-000000000104'::UUID);
@@ -0,0 +1,6 @@
+DELETE FROM menus WHERE id = 'c0000000-0000-0000-0000-000000000105'::UUID;
+
+ALTER TABLE users DROP COLUMN IF EXISTS gender;
+
+DROP TABLE IF EXISTS dict_items;
+DROP TABLE IF EXISTS dict_types;
@@ -0,0 +1,80 @@
+-- 创建字典类型表
+CREATE TABLE IF NOT EXISTS dict_types (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    code VARCHAR(100) NOT NULL UNIQUE,           -- 字典编码，如 sys_status
+    name VARCHAR(100) NOT NULL,                  -- 字典名称
+    description VARCHAR(255),                    -- 描述
+    status SMALLINT NOT NULL DEFAULT 1,          -- 状态：1-正常，0-禁用
+    created_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    created_id UUID,
+    updated_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_id UUID,
+    deleted_time TIMESTAMP,
+    deleted_id UUID
+);
+
+CREATE INDEX idx_dict_types_status ON dict_types(status);
+CREATE INDEX idx_dict_types_deleted_time ON dict_types(deleted_time);
+
+-- 创建字典项表
+CREATE TABLE IF NOT EXISTS dict_items (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    dict_type_id UUID NOT NULL REFERENCES dict_types(id) ON DELETE CASCADE,
+    label VARCHAR(100) NOT NULL,                 -- 显示文本
+    value VARCHAR(100) NOT NULL,                 -- 字典值
+    sort INTEGER NOT NULL DEFAULT 0,             -- 排序值
+    color VARCHAR(20),                           -- 颜色，如 #52c41a
+    tag_type VARCHAR(20),                        -- 标签样式：default/primary/success/info/warning/danger
+    is_default BOOLEAN NOT NULL DEFAULT FALSE,   -- 是否默认值
+    status SMALLINT NOT NULL DEFAULT 1,          -- 状态：1-正常，0-禁用
+    remark VARCHAR(255),                         -- 备注
+    created_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    created_id UUID,
+    updated_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_id UUID,
+    deleted_time TIMESTAMP,
+    deleted_id UUID
+);
+
+CREATE INDEX idx_dict_items_dict_type_id ON dict_items(dict_type_id);
+CREATE INDEX idx_dict_items_deleted_time ON dict_items(deleted_time);
+
+-- 用户性别：0-未知，1-男，2-女
+ALTER TABLE users ADD COLUMN IF NOT EXISTS gender SMALLINT NOT NULL DEFAULT 0;
+
+-- 内置字典
+INSERT INTO dict_types (id, code, name, description)
+VALUES
+    ('d0000000-0000-0000-0000-000000000001'::UUID, 'sys_status', '通用状态', '启用/禁用状态'),
+    ('d0000000-0000-0000-0000-000000000002'::UUID, 'sys_user_gender', '用户性别', '用户性别'),
+    ('d0000000-0000-0000-0000-000000000003'::UUID, 'sys_menu_type', '菜单类型', '目录/菜单/按钮');
+
+INSERT INTO dict_items (dict_type_id, label, value, sort, tag_type, is_default)
+VALUES
+    ('d0000000-0000-0000-0000-000000000001'::UUID, '正常', '1', 1, 'success', TRUE),
+    ('d0000000-0000-0000-0000-000000000001'::UUID, '禁用', '0', 2, 'danger', FALSE),
+    ('d0000000-0000-0000-0000-000000000002'::UUID, '未知', '0', 1, 'info', TRUE),
+    ('d0000000-0000-0000-0000-000000000002'::UUID, '男', '1', 2, 'primary', FALSE),
+    ('d0000000-0000-0000-0000-000000000002'::UUID, '女', '2', 3, 'warning', FALSE),
+    ('d0000000-0000-0000-0000-000000000003'::UUID, '目录', 'catalog', 1, 'warning', FALSE),
+    ('d0000000-0000-0000-0000-000000000003'::UUID, '菜单', 'menu', 2, 'success', TRUE),
+    ('d0000000-0000-0000-0000-000000000003'::UUID, '按钮', 'button', 3, 'info', FALSE);
+
+-- 字典管理菜单
+INSERT INTO menus (id, parent_id, name, name_i18n, menu_type, path, component, icon, permission, sort, is_show)
+VALUES (
+    'c0000000-0000-0000-0000-000000000105'::UUID,
+    'c0000000-0000-0000-0000-000000000100'::UUID,
+    '字典管理',
+    '{"en-US": "Dictionaries"}'::JSONB,
+    'menu',
+    '/system/dict',
+    '/views/system/dict/index',
+    'mdi:book-open-variant',
+    'system:dict:list',
+    5,
+    TRUE
+);
+
+INSERT INTO role_menus (role_id, menu_id)
+VALUES ('a0000000-0000-0000-0000-000000000001'::UUID, 'c0000000-0000-0000-0000-000000000105'::UUID);
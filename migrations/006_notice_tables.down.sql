@@ -0,0 +1,5 @@
+DELETE FROM menus WHERE id = 'c0000000-0000-0000-0000-000000000106'::UUID;
+
+DROP TABLE IF EXISTS notice_reads;
+DROP TABLE IF EXISTS notice_roles;
+DROP TABLE IF EXISTS notices;
@@ -0,0 +1,64 @@
+-- 创建通知公告表
+CREATE TABLE IF NOT EXISTS notices (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    title VARCHAR(200) NOT NULL,                 -- 标题
+    content TEXT NOT NULL,                       -- 内容
+    status SMALLINT NOT NULL DEFAULT 0,          -- 状态：0-草稿，1-已发布，2-已撤回
+    is_pinned BOOLEAN NOT NULL DEFAULT FALSE,    -- 是否置顶
+    start_time TIMESTAMP,                        -- 生效开始时间，为空表示发布即生效
+    end_time TIMESTAMP,                          -- 生效结束时间，为空表示长期有效
+    published_time TIMESTAMP,                    -- 发布时间
+    published_id UUID,                           -- 发布人
+    created_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    created_id UUID,
+    updated_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_id UUID,
+    deleted_time TIMESTAMP,
+    deleted_id UUID
+);
+
+CREATE INDEX idx_notices_status ON notices(status);
+CREATE INDEX idx_notices_deleted_time ON notices(deleted_time);
+
+-- 创建通知目标角色表，公告没有目标角色时对所有用户可见
+CREATE TABLE IF NOT EXISTS notice_roles (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    notice_id UUID NOT NULL REFERENCES notices(id) ON DELETE CASCADE,
+    role_id UUID NOT NULL REFERENCES roles(id) ON DELETE CASCADE,
+    created_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    created_id UUID,
+    UNIQUE(notice_id, role_id)
+);
+
+CREATE INDEX idx_notice_roles_notice_id ON notice_roles(notice_id);
+CREATE INDEX idx_notice_roles_role_id ON notice_roles(role_id);
+
+-- 创建通知已读记录表
+CREATE TABLE IF NOT EXISTS notice_reads (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    notice_id UUID NOT NULL REFERENCES notices(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    read_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(notice_id, user_id)
+);
+
+CREATE INDEX idx_notice_reads_user_id ON notice_reads(user_id);
+
+-- 通知公告菜单
+INSERT INTO menus (id, parent_id, name, name_i18n, menu_type, path, component, icon, permission, sort, is_show)
+VALUES (
+    'c0000000-0000-0000-0000-000000000106'::UUID,
+    'c0000000-0000-0000-0000-000000000100'::UUID,
+    '通知公告',
+    '{"en-US": "Notices"}'::JSONB,
+    'menu',
+    '/system/notice',
+    '/views/system/notice/index',
+    'mdi:bullhorn',
+    'system:notice:list',
+    6,
+    TRUE
+);
+
+INSERT INTO role_menus (role_id, menu_id)
+VALUES ('a0000000-0000-0000-0000-000000000001'::UUID, 'c0000000-0000-0000-0000-000000000106'::UUID);
@@ -0,0 +1,6 @@
+DELETE FROM menus WHERE id = 'c0000000-0000-0000-0000-000000000107'::UUID;
+
+DELETE FROM system_params WHERE param_key = 'sys.job.run_retention_days';
+
+DROP TABLE IF EXISTS job_runs;
+DROP TABLE IF EXISTS scheduled_jobs;
@@ -0,0 +1,62 @@
+-- 创建定时任务表，任务由代码注册，启动时自动同步到此表
+CREATE TABLE IF NOT EXISTS scheduled_jobs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    job_key VARCHAR(100) NOT NULL UNIQUE,                -- 任务标识，对应代码中注册的任务
+    name VARCHAR(100) NOT NULL,                          -- 任务名称
+    description VARCHAR(255),                            -- 描述
+    cron_expression VARCHAR(100) NOT NULL,               -- cron 表达式（秒 分 时 日 月 周，也支持五段式）
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,               -- 是否启用
+    concurrency_policy VARCHAR(20) NOT NULL DEFAULT 'forbid', -- 并发策略：forbid-上次未结束则跳过，allow-允许并发
+    next_run_time TIMESTAMP,                             -- 下次执行时间
+    last_run_time TIMESTAMP,                             -- 上次执行时间
+    lease_owner VARCHAR(100),                            -- 当前持有执行租约的实例
+    lease_until TIMESTAMP,                               -- 租约到期时间
+    created_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    created_id UUID,
+    updated_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_id UUID
+);
+
+CREATE INDEX idx_scheduled_jobs_next_run_time ON scheduled_jobs(next_run_time);
+
+-- 创建任务执行记录表
+CREATE TABLE IF NOT EXISTS job_runs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    job_id UUID NOT NULL REFERENCES scheduled_jobs(id) ON DELETE CASCADE,
+    trigger_type VARCHAR(20) NOT NULL,                   -- 触发方式：schedule-定时，manual-手动
+    instance_id VARCHAR(100) NOT NULL,                   -- 执行实例
+    status VARCHAR(20) NOT NULL,                         -- 状态：running、success、failed、skipped
+    output TEXT,                                         -- 执行输出
+    error TEXT,                                          -- 错误信息
+    started_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    finished_time TIMESTAMP,
+    duration_ms BIGINT,                                  -- 执行耗时（毫秒）
+    created_id UUID                                      -- 手动触发人
+);
+
+CREATE INDEX idx_job_runs_job_id ON job_runs(job_id);
+CREATE INDEX idx_job_runs_started_time ON job_runs(started_time);
+
+-- 任务执行记录保留天数
+INSERT INTO system_params (param_key, param_value, value_type, param_group, description, is_builtin, is_public)
+VALUES ('sys.job.run_retention_days', '30', 'number', 'job', '任务执行记录保留天数', TRUE, FALSE)
+ON CONFLICT (param_key) DO NOTHING;
+
+-- 定时任务菜单
+INSERT INTO menus (id, parent_id, name, name_i18n, menu_type, path, component, icon, permission, sort, is_show)
+VALUES (
+    'c0000000-0000-0000-0000-000000000107'::UUID,
+    'c0000000-0000-0000-0000-000000000100'::UUID,
+    '定时任务',
+    '{"en-US": "Scheduled Jobs"}'::JSONB,
+    'menu',
+    '/system/job',
+    '/views/system/job/index',
+    'mdi:timer-cog-outline',
+    'system:job:list',
+    7,
+    TRUE
+);
+
+INSERT INTO role_menus (role_id, menu_id)
+VALUES ('a0000000-0000-0000-0000-000000000001'::UUID, 'c0000000-0000-0000-0000-000000000107'::UUID);
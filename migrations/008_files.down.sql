@@ -0,0 +1,4 @@
+-- 仅删除元数据，存储中的文件对象需手动清理
+DELETE FROM menus WHERE id = 'c0000000-0000-0000-0000-000000000108'::UUID;
+
+DROP TABLE IF EXISTS files;
@@ -0,0 +1,41 @@
+-- 创建文件表，相同内容的文件共用一个存储对象
+CREATE TABLE IF NOT EXISTS files (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    file_name VARCHAR(255) NOT NULL,             -- 原始文件名
+    storage_key VARCHAR(255) NOT NULL,           -- 存储键（按内容哈希生成）
+    backend VARCHAR(20) NOT NULL,                -- 存储后端：local、s3
+    content_type VARCHAR(100) NOT NULL,          -- MIME 类型
+    file_size BIGINT NOT NULL,                   -- 文件大小（字节）
+    sha256 VARCHAR(64) NOT NULL,                 -- 内容 SHA-256
+    biz_type VARCHAR(50) NOT NULL DEFAULT 'common', -- 业务类型，如 avatar
+    created_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    created_id UUID,
+    updated_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_id UUID,
+    deleted_time TIMESTAMP,
+    deleted_id UUID
+);
+
+CREATE INDEX idx_files_sha256 ON files(sha256);
+CREATE INDEX idx_files_storage_key ON files(storage_key);
+CREATE INDEX idx_files_created_id ON files(created_id);
+CREATE INDEX idx_files_deleted_time ON files(deleted_time);
+
+-- 文件管理菜单
+INSERT INTO menus (id, parent_id, name, name_i18n, menu_type, path, component, icon, permission, sort, is_show)
+VALUES (
+    'c0000000-0000-0000-0000-000000000108'::UUID,
+    'c0000000-0000-0000-0000-000000000100'::UUID,
+    '文件管理',
+    '{"en-US": "Files"}'::JSONB,
+    'menu',
+    '/system/file',
+    '/views/system/file/index',
+    'mdi:folder-file-outline',
+    'system:file:list',
+    8,
+    TRUE
+);
+
+INSERT INTO role_menus (role_id, menu_id)
+VALUES ('a0000000-0000-0000-0000-000000000001'::UUID, 'c0000000-0000-0000-0000-000000000108'::UUID);
@@ -0,0 +1,2 @@
+-- 用户头像缩略图，头像由用户自行上传时生成
+ALTER TABLE users ADD COLUMN IF NOT EXISTS avatar_thumb VARCHAR(500);
@@ -0,0 +1 @@
+DELETE FROM menus WHERE id = 'c0000000-0000-0000-0000-000000000109'::UUID;
@@ -0,0 +1,18 @@
+-- 系统监控菜单
+INSERT INTO menus (id, parent_id, name, name_i18n, menu_type, path, component, icon, permission, sort, is_show)
+VALUES (
+    'c0000000-0000-0000-0000-000000000109'::UUID,
+    'c0000000-0000-0000-0000-000000000100'::UUID,
+    '系统监控',
+    '{"en-US": "Monitor"}'::JSONB,
+    'menu',
+    '/system/monitor',
+    '/views/system/monitor/index',
+    'mdi:monitor-dashboard',
+    'system:monitor:server',
+    9,
+    TRUE
+);
+
+INSERT INTO role_menus (role_id, menu_id)
+VALUES ('a0000000-0000-0000-0000-000000000001'::UUID, 'c0000000-0000-0000-0000-000000000109'::UUID);
@@ -0,0 +1,18 @@
+-- 代码生成菜单
+INSERT INTO menus (id, parent_id, name, name_i18n, menu_type, path, component, icon, permission, sort, is_show)
+VALUES (
+    'c0000000-0000-0000-0000-00000000010a'::UUID,
+    'c0000000-0000-0000-0000-000000000100'::UUID,
+    '代码生成',
+    '{"en-US": "Code Generator"}'::JSONB,
+    'menu',
+    '/system/codegen',
+    '/views/system/codegen/index',
+    'mdi:code-braces',
+    'system:codegen:list',
+    10,
+    TRUE
+);
+
+INSERT INTO role_menus (role_id, menu_id)
+VALUES ('a0000000-0000-0000-0000-000000000001'::UUID, 'c0000000-0000-0000-0000-00000000010a'::UUID);
@@ -0,0 +1,2 @@
+-- 用户令牌吊销时间，强制下线时写入，签发时间不晚于该时间的令牌失效
+ALTER TABLE users ADD COLUMN IF NOT EXISTS tokens_revoked_time TIMESTAMP;
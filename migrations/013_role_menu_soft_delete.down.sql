@@ -0,0 +1,2 @@
+ALTER TABLE role_menus DROP COLUMN IF EXISTS deleted_id;
+ALTER TABLE role_menus DROP COLUMN IF EXISTS deleted_time;
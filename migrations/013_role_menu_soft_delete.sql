@@ -0,0 +1,3 @@
+-- 角色菜单关联软删除，级联删除菜单时随菜单一并软删除，从回收站恢复菜单时一并恢复
+ALTER TABLE role_menus ADD COLUMN IF NOT EXISTS deleted_time TIMESTAMP;
+ALTER TABLE role_menus ADD COLUMN IF NOT EXISTS deleted_id UUID;
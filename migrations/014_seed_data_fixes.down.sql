@@ -0,0 +1,2 @@
+UPDATE users SET real_name = 'superAdmin'
+WHERE id = 'b0000000-0000-0000-0000-000000000001'::UUID AND real_name = '超级管理员';
@@ -0,0 +1,3 @@
+-- 修正初始数据：超级管理员姓名与原先程序内初始化的数据保持一致
+UPDATE users SET real_name = '超级管理员'
+WHERE id = 'b0000000-0000-0000-0000-000000000001'::UUID AND real_name = 'superAdmin';
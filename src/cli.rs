@@ -0,0 +1,604 @@
+// 命令行子命令
+// 不带子命令时启动服务，其余子命令用于迁移、初始化数据和运维操作
+
+use anyhow::{anyhow, bail, Context};
+use base64::{engine::general_purpose, Engine as _};
+use chrono::Utc;
+use clap::{Parser, Subcommand};
+use rand::distributions::{Alphanumeric, DistString};
+use rand::RngCore;
+use rsa::pkcs8::{EncodePrivateKey, EncodePublicKey, LineEnding};
+use rsa::{RsaPrivateKey, RsaPublicKey};
+use sea_orm::{
+    sea_query::OnConflict, ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait,
+    QueryFilter, Set, TransactionTrait,
+};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+use crate::common::constants::{
+    PASSWORD_MAX_LENGTH, PASSWORD_MIN_LENGTH, SUPER_ADMIN_DEFAULT_PASSWORD_HASH,
+    SUPER_ADMIN_REAL_NAME, SUPER_ADMIN_ROLE_CODE, SUPER_ADMIN_ROLE_DESCRIPTION,
+    SUPER_ADMIN_ROLE_ID, SUPER_ADMIN_ROLE_NAME, SUPER_ADMIN_USERNAME, SUPER_ADMIN_USER_ID,
+};
+use crate::common::middleware::tokens_revoked_now;
+use crate::common::{self, crypto, migration, validation, AppConfig};
+use crate::models::{menu, role, role_menu, user, user_role};
+
+/// 未指定密码时随机生成的密码长度
+const GENERATED_PASSWORD_LENGTH: usize = 16;
+
+#[derive(Parser)]
+#[command(version, about = "Maple Admin 后台服务")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// 启动 HTTP 服务（默认）
+    Serve,
+    /// 数据库迁移
+    Migrate {
+        #[command(subcommand)]
+        action: Option<MigrateAction>,
+    },
+    /// 恢复内置数据：超级管理员角色、账号及其菜单权限
+    Seed,
+    /// 创建超级管理员账号
+    CreateAdmin {
+        username: String,
+        /// 不指定时随机生成并输出
+        #[arg(long)]
+        password: Option<String>,
+        /// 姓名，默认与用户名相同
+        #[arg(long)]
+        real_name: Option<String>,
+        #[arg(long)]
+        email: Option<String>,
+    },
+    /// 重置用户密码
+    ResetPassword {
+        username: String,
+        /// 不指定时随机生成并输出
+        #[arg(long)]
+        password: Option<String>,
+    },
+    /// 解锁或启用被禁用的用户
+    Unlock { username: String },
+    /// 生成 RSA 密钥对（PKCS#8 PEM）
+    GenRsaKeys {
+        /// 输出目录
+        #[arg(long, default_value = "config")]
+        out_dir: PathBuf,
+        #[arg(long, default_value_t = 2048)]
+        bits: usize,
+        /// 覆盖已存在的密钥文件
+        #[arg(long)]
+        force: bool,
+    },
+    /// 生成随机 JWT 密钥
+    GenJwtSecret,
+    /// 检查配置、数据库连接、RSA 密钥和文件存储
+    VerifyConfig,
+}
+
+#[derive(Subcommand)]
+pub enum MigrateAction {
+    /// 执行所有未执行的迁移（默认）
+    Up,
+    /// 回滚最近的迁移
+    Down {
+        /// 回滚的个数
+        #[arg(default_value_t = 1)]
+        steps: usize,
+    },
+    /// 查看迁移状态
+    Status,
+}
+
+/// 执行除 serve 以外的子命令
+pub async fn run(command: Command) -> anyhow::Result<()> {
+    match command {
+        Command::Serve => unreachable!("serve 由 main 处理"),
+        Command::Migrate { action } => {
+            migrate(&connect().await?, action.unwrap_or(MigrateAction::Up)).await
+        }
+        Command::Seed => seed(&connect().await?).await,
+        Command::CreateAdmin {
+            username,
+            password,
+            real_name,
+            email,
+        } => create_admin(&connect().await?, username, password, real_name, email).await,
+        Command::ResetPassword { username, password } => {
+            reset_password(&connect().await?, &username, password).await
+        }
+        Command::Unlock { username } => unlock(&connect().await?, &username).await,
+        Command::GenRsaKeys {
+            out_dir,
+            bits,
+            force,
+        } => gen_rsa_keys(out_dir, bits, force),
+        Command::GenJwtSecret => {
+            let mut bytes = [0u8; 48];
+            rand::thread_rng().fill_bytes(&mut bytes);
+            println!(
+                "JWT_SECRET={}",
+                general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+            );
+            Ok(())
+        }
+        Command::VerifyConfig => verify_config().await,
+    }
+}
+
+async fn connect() -> anyhow::Result<DatabaseConnection> {
+    let config = AppConfig::load()?;
+    common::database::init_db(&config.database)
+        .await
+        .ok_or_else(|| anyhow!("数据库连接失败，请检查 database.url 配置"))
+}
+
+async fn migrate(db: &DatabaseConnection, action: MigrateAction) -> anyhow::Result<()> {
+    match action {
+        MigrateAction::Up => {
+            let executed = migration::migrate_up(db).await?;
+            println!("已执行 {} 个迁移", executed.len());
+        }
+        MigrateAction::Down { steps } => {
+            let rolled_back = migration::migrate_down(db, steps).await?;
+            println!("已回滚 {} 个迁移", rolled_back.len());
+        }
+        MigrateAction::Status => {
+            for s in migration::status(db).await? {
+                let state = match (s.applied_time, s.modified, s.missing) {
+                    (_, _, true) => "程序中不存在".to_string(),
+                    (_, true, _) => "已修改".to_string(),
+                    (Some(time), _, _) => format!(
+                        "{}（{}ms）",
+                        time.format("%Y-%m-%d %H:%M:%S"),
+                        s.execution_ms.unwrap_or_default()
+                    ),
+                    (None, _, _) => "未执行".to_string(),
+                };
+                println!("{:03}  {:<30} {}", s.version, s.name, state);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 内置数据已存在时保持不变，只补齐缺失的部分
+async fn seed(db: &DatabaseConnection) -> anyhow::Result<()> {
+    let role_id = Uuid::parse_str(SUPER_ADMIN_ROLE_ID)?;
+    let user_id = Uuid::parse_str(SUPER_ADMIN_USER_ID)?;
+    let now = Utc::now().naive_utc();
+
+    let inserted = role::Entity::insert(role::ActiveModel {
+        id: Set(role_id),
+        code: Set(SUPER_ADMIN_ROLE_CODE.to_string()),
+        name: Set(SUPER_ADMIN_ROLE_NAME.to_string()),
+        description: Set(Some(SUPER_ADMIN_ROLE_DESCRIPTION.to_string())),
+        is_system: Set(true),
+        status: Set(1),
+        created_time: Set(now),
+        created_id: Set(None),
+        updated_time: Set(now),
+        updated_id: Set(None),
+        deleted_time: Set(None),
+        deleted_id: Set(None),
+    })
+    .on_conflict(OnConflict::new().do_nothing().to_owned())
+    .exec_without_returning(db)
+    .await?;
+    println!(
+        "超级管理员角色：{}",
+        if inserted > 0 {
+            "已创建"
+        } else {
+            "已存在"
+        }
+    );
+
+    let inserted = user::Entity::insert(user::ActiveModel {
+        id: Set(user_id),
+        username: Set(SUPER_ADMIN_USERNAME.to_string()),
+        password: Set(SUPER_ADMIN_DEFAULT_PASSWORD_HASH.to_string()),
+        real_name: Set(SUPER_ADMIN_REAL_NAME.to_string()),
+        email: Set(None),
+        phone: Set(None),
+        avatar: Set(None),
+        avatar_thumb: Set(None),
+        gender: Set(0),
+        status: Set(1),
+        created_time: Set(now),
+        created_id: Set(None),
+        updated_time: Set(now),
+        updated_id: Set(None),
+        deleted_time: Set(None),
+        deleted_id: Set(None),
+        tokens_revoked_time: Set(None),
+    })
+    .on_conflict(OnConflict::new().do_nothing().to_owned())
+    .exec_without_returning(db)
+    .await?;
+    println!(
+        "超级管理员账号：{}",
+        if inserted > 0 {
+            "已创建（用户名: superAdmin, 密码: superAdmin）"
+        } else {
+            "已存在"
+        }
+    );
+
+    user_role::Entity::insert(user_role::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(user_id),
+        role_id: Set(role_id),
+        created_time: Set(now),
+        created_id: Set(None),
+    })
+    .on_conflict(OnConflict::new().do_nothing().to_owned())
+    .exec_without_returning(db)
+    .await?;
+
+    let menus = menu::Entity::find()
+        .filter(menu::Column::DeletedTime.is_null())
+        .all(db)
+        .await?;
+    let granted = if menus.is_empty() {
+        0
+    } else {
+        role_menu::Entity::insert_many(menus.iter().map(|m| role_menu::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            role_id: Set(role_id),
+            menu_id: Set(m.id),
+            created_time: Set(now),
+            created_id: Set(None),
+            deleted_time: Set(None),
+            deleted_id: Set(None),
+        }))
+        .on_conflict(OnConflict::new().do_nothing().to_owned())
+        .exec_without_returning(db)
+        .await?
+    };
+    println!("超级管理员菜单权限：新增 {} 项", granted);
+    Ok(())
+}
+
+async fn create_admin(
+    db: &DatabaseConnection,
+    username: String,
+    password: Option<String>,
+    real_name: Option<String>,
+    email: Option<String>,
+) -> anyhow::Result<()> {
+    if !validation::is_valid_username(&username) {
+        bail!("用户名须以字母开头，由 3-50 位字母、数字、下划线、点或连字符组成");
+    }
+    if let Some(email) = email.as_deref().filter(|e| !validation::is_valid_email(e)) {
+        bail!("邮箱格式不正确: {}", email);
+    }
+    let (password, generated) = resolve_password(password)?;
+
+    let exists = user::Entity::find()
+        .filter(user::Column::Username.eq(&username))
+        .one(db)
+        .await?
+        .is_some();
+    if exists {
+        bail!("用户名 {} 已存在", username);
+    }
+
+    let hashed = crypto::hash_password(&password)?;
+    let now = Utc::now().naive_utc();
+    let user_id = Uuid::new_v4();
+
+    let txn = db.begin().await?;
+    user::ActiveModel {
+        id: Set(user_id),
+        real_name: Set(real_name.unwrap_or_else(|| username.clone())),
+        username: Set(username.clone()),
+        password: Set(hashed),
+        email: Set(email),
+        phone: Set(None),
+        avatar: Set(None),
+        avatar_thumb: Set(None),
+        gender: Set(0),
+        status: Set(1),
+        created_time: Set(now),
+        created_id: Set(None),
+        updated_time: Set(now),
+        updated_id: Set(None),
+        deleted_time: Set(None),
+        deleted_id: Set(None),
+        tokens_revoked_time: Set(None),
+    }
+    .insert(&txn)
+    .await?;
+    user_role::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(user_id),
+        role_id: Set(Uuid::parse_str(SUPER_ADMIN_ROLE_ID)?),
+        created_time: Set(now),
+        created_id: Set(None),
+    }
+    .insert(&txn)
+    .await
+    .context("分配超级管理员角色失败，请先执行 seed")?;
+    txn.commit().await?;
+
+    println!("已创建超级管理员 {}", username);
+    if generated {
+        println!("初始密码: {}", password);
+    }
+    Ok(())
+}
+
+async fn reset_password(
+    db: &DatabaseConnection,
+    username: &str,
+    password: Option<String>,
+) -> anyhow::Result<()> {
+    let existing = find_user(db, username).await?;
+    let (password, generated) = resolve_password(password)?;
+
+    // 重置密码通常意味着账号可能泄露，已签发的令牌一并失效
+    let mut active_model: user::ActiveModel = existing.into();
+    active_model.password = Set(crypto::hash_password(&password)?);
+    active_model.tokens_revoked_time = Set(Some(tokens_revoked_now()));
+    active_model.updated_time = Set(Utc::now().naive_utc());
+    active_model.update(db).await?;
+
+    println!("已重置用户 {} 的密码，已签发的令牌全部失效", username);
+    if generated {
+        println!("新密码: {}", password);
+    }
+    Ok(())
+}
+
+async fn unlock(db: &DatabaseConnection, username: &str) -> anyhow::Result<()> {
+    let existing = find_user(db, username).await?;
+    if existing.status == 1 {
+        println!("用户 {} 未被锁定或禁用", username);
+        return Ok(());
+    }
+
+    let previous = existing.status;
+    let mut active_model: user::ActiveModel = existing.into();
+    active_model.status = Set(1);
+    active_model.updated_time = Set(Utc::now().naive_utc());
+    active_model.update(db).await?;
+
+    println!("已启用用户 {}（原状态: {}）", username, previous);
+    Ok(())
+}
+
+fn gen_rsa_keys(out_dir: PathBuf, bits: usize, force: bool) -> anyhow::Result<()> {
+    if !matches!(bits, 2048 | 3072 | 4096) {
+        bail!("密钥长度只支持 2048、3072 或 4096");
+    }
+    let private_path = out_dir.join("rsa_private_key.pem");
+    let public_path = out_dir.join("rsa_public_key.pem");
+    if !force && (private_path.exists() || public_path.exists()) {
+        bail!(
+            "{} 下已存在密钥文件，使用 --force 覆盖（已加密存储的数据将无法用旧密钥解密）",
+            out_dir.display()
+        );
+    }
+
+    println!("正在生成 {} 位 RSA 密钥...", bits);
+    let private_key = RsaPrivateKey::new(&mut rand::rngs::OsRng, bits)?;
+    let private_pem = private_key.to_pkcs8_pem(LineEnding::LF)?;
+    let public_pem = RsaPublicKey::from(&private_key).to_public_key_pem(LineEnding::LF)?;
+
+    std::fs::create_dir_all(&out_dir)?;
+    std::fs::write(&private_path, private_pem.as_bytes())?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&private_path, std::fs::Permissions::from_mode(0o600))?;
+    }
+    std::fs::write(&public_path, public_pem)?;
+
+    println!("私钥: {}", private_path.display());
+    println!("公钥: {}", public_path.display());
+    Ok(())
+}
+
+/// 逐项检查，全部通过时返回成功
+async fn verify_config() -> anyhow::Result<()> {
+    let config = match AppConfig::load() {
+        Ok(config) => {
+            println!("✅ 配置文件: profile {}", config.profile);
+            config
+        }
+        Err(e) => {
+            for error in &e.errors {
+                println!("❌ {}", error);
+            }
+            bail!("{} 项配置错误", e.errors.len());
+        }
+    };
+    let mut failed = 0;
+    let mut check = |name: &str, result: anyhow::Result<String>| match result {
+        Ok(detail) => println!("✅ {}: {}", name, detail),
+        Err(e) => {
+            failed += 1;
+            println!("❌ {}: {:#}", name, e);
+        }
+    };
+
+    check(
+        "服务地址",
+        Ok(format!("{}:{}", config.server.host, config.server.port)),
+    );
+    check(
+        "JWT 密钥",
+        if config.jwt.secret.len() < 32 {
+            Err(anyhow!("长度不足 32 个字符，可使用 gen-jwt-secret 生成"))
+        } else {
+            Ok(format!(
+                "access token 有效期 {} 小时，refresh token 有效期 {} 天",
+                config.jwt.expiration_hours, config.jwt.refresh_expiration_days
+            ))
+        },
+    );
+    check(
+        "RSA 密钥",
+        common::rsa_crypto::verify_key_pair()
+            .map(|bits| format!("{} 位，公私钥匹配", bits))
+            .map_err(Into::into),
+    );
+    check(
+        "文件存储",
+        common::storage::init_storage(&config.storage)
+            .map(|_| config.storage.backend.clone())
+            .map_err(Into::into),
+    );
+
+    let database = match common::database::init_db(&config.database).await {
+        Some(db) => migration::status(&db)
+            .await
+            .map(|status| {
+                let pending = status.iter().filter(|s| s.applied_time.is_none()).count();
+                format!("连接成功，{} 个迁移未执行", pending)
+            })
+            .map_err(Into::into),
+        None => Err(anyhow!("连接失败")),
+    };
+    check("数据库", database);
+
+    if failed > 0 {
+        bail!("{} 项检查未通过", failed);
+    }
+    Ok(())
+}
+
+// ========== 辅助函数 ==========
+
+async fn find_user(db: &DatabaseConnection, username: &str) -> anyhow::Result<user::Model> {
+    user::Entity::find()
+        .filter(user::Column::Username.eq(username))
+        .filter(user::Column::DeletedTime.is_null())
+        .one(db)
+        .await?
+        .ok_or_else(|| anyhow!("用户 {} 不存在", username))
+}
+
+/// 校验指定的密码，未指定时随机生成；返回密码以及是否为生成的密码
+fn resolve_password(password: Option<String>) -> anyhow::Result<(String, bool)> {
+    match password {
+        Some(password) => {
+            let length = password.chars().count();
+            if !(PASSWORD_MIN_LENGTH..=PASSWORD_MAX_LENGTH).contains(&length) {
+                bail!(
+                    "密码长度必须在{}-{}之间",
+                    PASSWORD_MIN_LENGTH,
+                    PASSWORD_MAX_LENGTH
+                );
+            }
+            Ok((password, false))
+        }
+        None => Ok((
+            Alphanumeric.sample_string(&mut rand::thread_rng(), GENERATED_PASSWORD_LENGTH),
+            true,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::testing::TestDb;
+    use sea_orm::{ConnectionTrait, PaginatorTrait};
+
+    async fn super_admin(db: &DatabaseConnection) -> (role::Model, user::Model) {
+        let role = role::Entity::find_by_id(Uuid::parse_str(SUPER_ADMIN_ROLE_ID).unwrap())
+            .one(db)
+            .await
+            .unwrap()
+            .expect("role");
+        let user = user::Entity::find_by_id(Uuid::parse_str(SUPER_ADMIN_USER_ID).unwrap())
+            .one(db)
+            .await
+            .unwrap()
+            .expect("user");
+        (role, user)
+    }
+
+    #[tokio::test]
+    async fn seed_recreates_migration_data() {
+        let Some(db) = TestDb::migrated().await else {
+            return;
+        };
+        let (migrated_role, migrated_user) = super_admin(&db).await;
+
+        db.execute_unprepared(&format!(
+            "DELETE FROM users WHERE id = '{SUPER_ADMIN_USER_ID}';
+             DELETE FROM roles WHERE id = '{SUPER_ADMIN_ROLE_ID}';"
+        ))
+        .await
+        .unwrap();
+        seed(&db).await.unwrap();
+
+        // 与迁移写入的数据一致
+        let (role, user) = super_admin(&db).await;
+        assert_eq!(
+            (role.code, role.name, role.description, role.is_system, role.status),
+            (
+                migrated_role.code,
+                migrated_role.name,
+                migrated_role.description,
+                migrated_role.is_system,
+                migrated_role.status
+            )
+        );
+        assert_eq!(
+            (user.username, user.password, user.real_name, user.status),
+            (
+                migrated_user.username,
+                migrated_user.password,
+                migrated_user.real_name,
+                migrated_user.status
+            )
+        );
+        assert_eq!(
+            user_role::Entity::find()
+                .filter(user_role::Column::UserId.eq(user.id))
+                .count(&*db)
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            role_menu::Entity::find()
+                .filter(role_menu::Column::RoleId.eq(role.id))
+                .count(&*db)
+                .await
+                .unwrap(),
+            menu::Entity::find().count(&*db).await.unwrap()
+        );
+
+        // 再次执行不重复插入
+        seed(&db).await.unwrap();
+        assert_eq!(user_role::Entity::find().count(&*db).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn reset_password_revokes_tokens() {
+        let Some(db) = TestDb::migrated().await else {
+            return;
+        };
+        reset_password(&db, SUPER_ADMIN_USERNAME, Some("newPassword1".to_string()))
+            .await
+            .unwrap();
+        let (_, user) = super_admin(&db).await;
+        assert!(crypto::verify_password("newPassword1", &user.password).unwrap());
+        assert!(user.tokens_revoked_time.is_some());
+
+        assert!(reset_password(&db, "missing", None).await.is_err());
+    }
+}
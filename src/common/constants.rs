@@ -4,6 +4,9 @@
 pub const DEFAULT_PAGE_SIZE: u64 = 20;
 pub const MAX_PAGE_SIZE: u64 = 100;
 
+// 接口路径前缀
+pub const API_PREFIX: &str = "api/v1";
+
 // Token 相关
 pub const TOKEN_PREFIX: &str = "Bearer ";
 
@@ -11,3 +14,31 @@ pub const TOKEN_PREFIX: &str = "Bearer ";
 pub const USER_STATUS_ACTIVE: i32 = 1;
 pub const USER_STATUS_INACTIVE: i32 = 0;
 pub const USER_STATUS_LOCKED: i32 = -1;
+
+// 内置超级管理员，与迁移 001、014 写入的初始数据一致，seed 命令按此补齐
+pub const SUPER_ADMIN_ROLE_ID: &str = "a0000000-0000-0000-0000-000000000001";
+pub const SUPER_ADMIN_USER_ID: &str = "b0000000-0000-0000-0000-000000000001";
+pub const SUPER_ADMIN_ROLE_CODE: &str = "superAdmin";
+pub const SUPER_ADMIN_ROLE_NAME: &str = "超级管理员";
+pub const SUPER_ADMIN_ROLE_DESCRIPTION: &str = "系统超级管理员，拥有所有权限，不可编辑删除";
+pub const SUPER_ADMIN_USERNAME: &str = "superAdmin";
+pub const SUPER_ADMIN_REAL_NAME: &str = "超级管理员";
+/// 初始密码 superAdmin 的 bcrypt 哈希
+pub const SUPER_ADMIN_DEFAULT_PASSWORD_HASH: &str =
+    "$2b$12$qMUWsD1wyBanEjPn6uEjJ.mPfHrtpxfqgsIpOtX9.zgGyrStoNB2W";
+
+// 密码长度限制
+pub const PASSWORD_MIN_LENGTH: usize = 6;
+pub const PASSWORD_MAX_LENGTH: usize = 64;
+
+// 回收站记录默认保留天数
+pub const RECYCLE_BIN_RETENTION_DAYS: i64 = 30;
+pub const MAX_RECYCLE_BIN_RETENTION_DAYS: i64 = 3650;
+
+// 通知公告状态
+pub const NOTICE_STATUS_DRAFT: i16 = 0;
+pub const NOTICE_STATUS_PUBLISHED: i16 = 1;
+pub const NOTICE_STATUS_WITHDRAWN: i16 = 2;
+
+// 任务执行记录默认保留天数
+pub const JOB_RUN_RETENTION_DAYS: i64 = 30;
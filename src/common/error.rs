@@ -2,6 +2,9 @@ use salvo::prelude::*;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use super::i18n::{self, Locale};
+use super::telemetry;
+
 #[derive(Error, Debug)]
 pub enum AppError {
     #[error("数据库错误: {0}")]
@@ -39,6 +42,32 @@ impl AppError {
             _ => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
+
+    /// 按语言生成错误消息，中文时与 Display 输出一致
+    pub fn localized_message(&self, locale: Locale) -> String {
+        if locale == Locale::ZhCn {
+            return self.to_string();
+        }
+
+        let (category, detail) = match self {
+            AppError::DatabaseError(e) => ("数据库错误", Some(e.to_string())),
+            AppError::Unauthorized => ("未授权", None),
+            AppError::Forbidden(msg) => ("禁止访问", Some(i18n::translate(msg, locale))),
+            AppError::NotFound(msg) => ("未找到资源", Some(i18n::translate(msg, locale))),
+            AppError::BadRequest(msg) => ("请求参数错误", Some(i18n::translate(msg, locale))),
+            AppError::InternalServerError(msg) => {
+                ("内部服务器错误", Some(i18n::translate(msg, locale)))
+            }
+            AppError::JwtError(e) => ("JWT错误", Some(e.to_string())),
+            AppError::BcryptError(_) => ("密码哈希错误", None),
+        };
+
+        let category = i18n::translate(category, locale);
+        match detail {
+            Some(detail) => format!("{}: {}", category, detail),
+            None => category,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -48,6 +77,9 @@ pub struct ErrorResponse {
     pub message: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<String>,
+    /// 请求ID，与响应头 X-Request-Id 一致，便于按请求查找日志
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
 }
 
 impl ErrorResponse {
@@ -56,6 +88,7 @@ impl ErrorResponse {
             code,
             message,
             details: None,
+            request_id: None,
         }
     }
 
@@ -63,16 +96,22 @@ impl ErrorResponse {
         self.details = Some(details);
         self
     }
+
+    pub fn with_request_id(mut self, depot: &Depot) -> Self {
+        self.request_id = telemetry::current_request_id(depot);
+        self
+    }
 }
 
 #[async_trait]
 impl Writer for AppError {
-    async fn write(mut self, _req: &mut Request, _depot: &mut Depot, res: &mut Response) {
+    async fn write(mut self, _req: &mut Request, depot: &mut Depot, res: &mut Response) {
         let status_code = self.status_code();
         let error_response = ErrorResponse::new(
             status_code.as_u16(),
-            self.to_string(),
-        );
+            self.localized_message(Locale::from_depot(depot)),
+        )
+        .with_request_id(depot);
         
         res.status_code(status_code);
         res.render(Json(error_response));
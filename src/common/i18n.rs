@@ -0,0 +1,391 @@
+// 国际化模块
+// 负责根据 Accept-Language 协商语言，并将中文消息翻译为目标语言
+
+use salvo::prelude::*;
+use std::collections::HashMap;
+
+/// 支持的语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    /// 简体中文（默认）
+    #[default]
+    ZhCn,
+    /// 英文
+    EnUs,
+}
+
+impl Locale {
+    /// 语言标签，如 zh-CN
+    pub fn as_tag(&self) -> &'static str {
+        match self {
+            Locale::ZhCn => "zh-CN",
+            Locale::EnUs => "en-US",
+        }
+    }
+
+    /// 语言代码，如 zh
+    pub fn language(&self) -> &'static str {
+        match self {
+            Locale::ZhCn => "zh",
+            Locale::EnUs => "en",
+        }
+    }
+
+    /// 解析语言标签，只比较主语言部分（en-GB 视为 en-US）
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let language = tag.split(['-', '_']).next()?.trim().to_ascii_lowercase();
+        match language.as_str() {
+            "zh" => Some(Locale::ZhCn),
+            "en" => Some(Locale::EnUs),
+            _ => None,
+        }
+    }
+
+    /// 按 Accept-Language 的 q 值选择第一个支持的语言
+    pub fn from_accept_language(header: &str) -> Self {
+        let mut candidates: Vec<(f32, &str)> = header
+            .split(',')
+            .filter_map(|part| {
+                let mut pieces = part.trim().split(';');
+                let tag = pieces.next()?.trim();
+                if tag.is_empty() {
+                    return None;
+                }
+                let q = pieces
+                    .find_map(|p| p.trim().strip_prefix("q="))
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                Some((q, tag))
+            })
+            .collect();
+
+        // 稳定排序，q 值相同时保留原始顺序
+        candidates.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        candidates
+            .into_iter()
+            .filter(|(q, _)| *q > 0.0)
+            .find_map(|(_, tag)| Self::from_tag(tag))
+            .unwrap_or_default()
+    }
+
+    /// 从请求头协商语言
+    pub fn from_request(req: &Request) -> Self {
+        req.headers()
+            .get("Accept-Language")
+            .and_then(|h| h.to_str().ok())
+            .map(Self::from_accept_language)
+            .unwrap_or_default()
+    }
+
+    /// 获取 DepsMiddleware 写入 depot 的当前语言
+    pub fn from_depot(depot: &Depot) -> Self {
+        depot.get::<Locale>("locale").copied().unwrap_or_default()
+    }
+}
+
+/// 消息目录：中文原文 -> 英文译文，`{}` 为占位符，按顺序替换
+const MESSAGES: &[(&str, &str)] = &[
+    // 错误类别
+    ("数据库错误", "Database error"),
+    ("未授权", "Unauthorized"),
+    ("禁止访问", "Forbidden"),
+    ("未找到资源", "Not found"),
+    ("请求参数错误", "Bad request"),
+    ("内部服务器错误", "Internal server error"),
+    ("JWT错误", "JWT error"),
+    ("密码哈希错误", "Password hashing error"),
+    // 通用
+    ("数据库服务不可用", "Database service unavailable"),
+    ("数据库服务不可用，请稍后重试", "Database service unavailable, please try again later"),
+    ("未提供认证令牌", "Authentication token not provided"),
+    ("无效的认证令牌", "Invalid authentication token"),
+    ("需要管理员权限", "Administrator privileges required"),
+    ("JWT 服务不可用", "JWT service unavailable"),
+    ("无效的用户ID", "Invalid user ID"),
+    ("读取请求体失败: {}", "Failed to read request body: {}"),
+    ("JSON 解析失败: {}", "Failed to parse JSON: {}"),
+    ("YAML 解析失败: {}", "Failed to parse YAML: {}"),
+    ("创建成功", "Created successfully"),
+    ("更新成功", "Updated successfully"),
+    ("删除成功", "Deleted successfully"),
+    ("移动成功", "Moved successfully"),
+    ("导入成功", "Imported successfully"),
+    ("操作成功", "Operation successful"),
+    // 认证
+    ("用户账号不存在", "User account does not exist"),
+    ("该账号已被删除，无法登录", "This account has been deleted and cannot log in"),
+    ("该账号已被禁用，请联系管理员", "This account is disabled, please contact the administrator"),
+    ("密码错误", "Incorrect password"),
+    ("用户没有分配角色", "User has no roles assigned"),
+    ("用户没有该角色权限", "User does not have this role"),
+    ("无效的角色ID", "Invalid role ID"),
+    ("角色不存在", "Role does not exist"),
+    ("角色不存在: {}", "Role does not exist: {}"),
+    ("用户不存在", "User does not exist"),
+    ("密钥管理器未初始化", "Key manager is not initialized"),
+    ("密码Base64解码失败: {}", "Failed to decode password Base64: {}"),
+    ("密码RSA解密失败，请检查密码格式: {}", "Failed to decrypt password with RSA, please check the format: {}"),
+    ("密码UTF-8解码失败: {}", "Failed to decode password as UTF-8: {}"),
+    ("真实姓名长度必须在1-100之间", "Real name must be between 1 and 100 characters"),
+    ("邮箱格式不正确", "Invalid email format"),
+    ("手机号格式不正确", "Invalid phone number format"),
+    ("无效的性别", "Invalid gender"),
+    ("密码长度必须在{}-{}之间", "Password must be between {} and {} characters"),
+    ("新密码不能与原密码相同", "The new password must differ from the old one"),
+    ("原密码错误", "Incorrect old password"),
+    ("密码修改成功", "Password changed successfully"),
+    ("无法识别的图片格式", "Unrecognized image format"),
+    // 用户管理
+    ("不支持的文件格式", "Unsupported file format"),
+    ("CSV 解析失败: {}", "Failed to parse CSV: {}"),
+    ("Excel 解析失败: {}", "Failed to parse Excel: {}"),
+    ("Excel 文件中没有工作表", "The Excel file has no worksheet"),
+    ("导入文件中没有数据", "The import file has no data"),
+    ("单次最多导入 {} 行", "At most {} rows can be imported at once"),
+    ("缺少必填列: {}", "Missing required column: {}"),
+    ("用户名不能为空", "Username cannot be empty"),
+    ("用户名格式不正确", "Invalid username format"),
+    ("用户名 {} 已存在", "Username {} already exists"),
+    ("用户名 {} 在文件中重复", "Username {} is duplicated in the file"),
+    ("性别必须为 0、1 或 2", "Gender must be 0, 1 or 2"),
+    ("状态必须为 0 或 1", "Status must be 0 or 1"),
+    ("角色代码 {} 不存在", "Role code {} does not exist"),
+    ("无权分配角色 {}", "Not allowed to assign role {}"),
+    ("校验通过", "Validation passed"),
+    ("数据校验未通过，未导入任何数据", "Validation failed, no data was imported"),
+    ("导出数据过多，请缩小筛选范围（最多 {} 条）", "Too many records to export, please narrow the filters (at most {})"),
+    // 菜单
+    ("菜单不存在", "Menu does not exist"),
+    ("无效的菜单ID", "Invalid menu ID"),
+    ("无效的菜单ID: {}", "Invalid menu ID: {}"),
+    ("无效的父菜单ID", "Invalid parent menu ID"),
+    ("无效的父菜单ID: {}", "Invalid parent menu ID: {}"),
+    ("无效的菜单类型", "Invalid menu type"),
+    ("父菜单不存在", "Parent menu does not exist"),
+    ("父菜单 {} 不存在", "Parent menu {} does not exist"),
+    ("父菜单不能是菜单自身", "A menu cannot be its own parent"),
+    ("父菜单不能是当前菜单的子菜单", "The parent menu cannot be a descendant of the menu"),
+    ("按钮不能包含子菜单", "Buttons cannot have child menus"),
+    ("按钮不能包含子菜单: {}", "Buttons cannot have child menus: {}"),
+    ("按钮 {} 不能包含子菜单", "Button {} cannot have child menus"),
+    ("存在子菜单的菜单不能改为按钮", "A menu with children cannot be changed to a button"),
+    ("该菜单存在子菜单，请先删除子菜单或使用级联删除", "The menu has child menus; delete them first or use cascade delete"),
+    ("移动列表不能为空", "The move list cannot be empty"),
+    ("菜单 {} 不存在", "Menu {} does not exist"),
+    ("菜单 {} 重复出现", "Menu {} appears more than once"),
+    ("移动后菜单 {} 形成循环引用", "Moving would create a cycle at menu {}"),
+    ("不支持的导出格式", "Unsupported export format"),
+    ("不支持的文档版本: {}", "Unsupported document version: {}"),
+    ("文档中存在重复的菜单: {}", "Duplicate menu in document: {}"),
+    ("现有菜单 {} 和 {} 的匹配键重复: {}", "Existing menus {} and {} share the same match key: {}"),
+    ("菜单 {} 的类型无效: {}", "Menu {} has an invalid type: {}"),
+    ("不支持的路由格式", "Unsupported route format"),
+    ("搜索关键字不能为空", "The search keyword cannot be empty"),
+    ("无效的搜索字段", "Invalid search field"),
+    // 回收站
+    ("恢复成功", "Restored successfully"),
+    ("无效的实体类型", "Invalid entity type"),
+    ("无效的记录ID", "Invalid record ID"),
+    ("回收站中不存在该记录", "Record not found in the recycle bin"),
+    ("用户名 {} 已被使用，无法恢复", "Username {} is already in use and cannot be restored"),
+    ("角色代码 {} 已被使用，无法恢复", "Role code {} is already in use and cannot be restored"),
+    ("父菜单已被删除，请先恢复父菜单", "The parent menu is deleted; restore it first"),
+    ("该菜单存在未删除的子菜单，无法彻底删除", "The menu has active child menus and cannot be purged"),
+    ("保留天数必须在0-{}之间", "Retention days must be between 0 and {}"),
+    ("保留天数超出范围", "Retention days out of range"),
+    // 系统参数
+    ("参数不存在", "Parameter does not exist"),
+    ("无效的参数ID", "Invalid parameter ID"),
+    ("无效的参数类型", "Invalid parameter type"),
+    ("参数键格式无效", "Invalid parameter key format"),
+    ("参数键 {} 已存在", "Parameter key {} already exists"),
+    ("参数值与类型 {} 不匹配", "The parameter value does not match type {}"),
+    ("内置参数不能修改键和类型", "The key and type of a built-in parameter cannot be changed"),
+    ("内置参数不可删除", "Built-in parameters cannot be deleted"),
+    // 数据字典
+    ("字典类型不存在", "Dictionary type does not exist"),
+    ("字典项不存在", "Dictionary item does not exist"),
+    ("无效的字典类型ID", "Invalid dictionary type ID"),
+    ("无效的字典项ID", "Invalid dictionary item ID"),
+    ("请指定字典类型", "Please specify a dictionary type"),
+    ("一次最多获取 {} 个字典", "At most {} dictionaries can be fetched at once"),
+    ("字典编码格式无效", "Invalid dictionary code format"),
+    ("字典编码 {} 已存在", "Dictionary code {} already exists"),
+    ("字典值 {} 已存在", "Dictionary value {} already exists"),
+    ("无效的颜色值", "Invalid color value"),
+    ("无效的标签样式", "Invalid tag type"),
+    // 通知公告
+    ("公告不存在", "Notice does not exist"),
+    ("无效的公告ID", "Invalid notice ID"),
+    ("公告标题不能为空", "The notice title cannot be empty"),
+    ("无效的时间格式: {}", "Invalid time format: {}"),
+    ("结束时间必须晚于开始时间", "The end time must be later than the start time"),
+    ("无效的角色ID: {}", "Invalid role ID: {}"),
+    ("已发布的公告请先撤回再修改", "Withdraw the published notice before editing it"),
+    ("公告已发布", "The notice is already published"),
+    ("只能撤回已发布的公告", "Only published notices can be withdrawn"),
+    ("发布成功", "Published successfully"),
+    ("撤回成功", "Withdrawn successfully"),
+    // 定时任务
+    ("任务不存在", "Job does not exist"),
+    ("无效的任务ID", "Invalid job ID"),
+    ("无效的并发策略", "Invalid concurrency policy"),
+    ("无效的 cron 表达式: {}", "Invalid cron expression: {}"),
+    ("任务 {} 未注册", "Job {} is not registered"),
+    ("任务正在执行中，请稍后再试", "The job is running, please try again later"),
+    ("触发成功", "Triggered successfully"),
+    ("暂停成功", "Paused successfully"),
+    // 文件管理
+    ("文件不存在", "File does not exist"),
+    ("无效的文件ID", "Invalid file ID"),
+    ("存储服务未初始化", "Storage service is not initialized"),
+    ("无效的存储键", "Invalid storage key"),
+    ("请选择要上传的文件", "Please choose a file to upload"),
+    ("文件名不能为空", "File name cannot be empty"),
+    ("文件内容为空", "The file is empty"),
+    ("文件大小不能超过 {}MB", "File size cannot exceed {}MB"),
+    ("无法识别文件类型", "Unable to determine the file type"),
+    ("不允许上传 {} 类型的文件", "Files of type {} are not allowed"),
+    ("不支持的业务类型: {}", "Unsupported business type: {}"),
+    ("下载链接无效或已过期", "The download link is invalid or has expired"),
+    ("上传成功", "Uploaded successfully"),
+    // 代码生成
+    ("表 {} 不存在", "Table {} does not exist"),
+    ("表名不能为空", "Table name cannot be empty"),
+    ("模块名只能包含小写字母、数字和下划线，且以字母开头", "Module name must start with a letter and contain only lowercase letters, digits and underscores"),
+    ("无效的上级菜单ID", "Invalid parent menu ID"),
+    ("无效的权限标识前缀", "Invalid permission prefix"),
+    ("业务名称不能包含换行等控制字符", "The business name must not contain line breaks or other control characters"),
+    ("表必须以 UUID 类型的 id 列为主键", "The table must use a UUID column named id as its primary key"),
+    ("不支持联合主键", "Composite primary keys are not supported"),
+    ("不支持的列类型: {} ({})", "Unsupported column type: {} ({})"),
+    // 健康检查
+    ("服务未就绪", "Service not ready"),
+    ("服务正在停止", "Service is shutting down"),
+    ("数据库未连接", "Database not connected"),
+    ("数据库响应超时", "Database did not respond in time"),
+];
+
+/// 将消息翻译为目标语言，目录中找不到时原样返回
+pub fn translate(message: &str, locale: Locale) -> String {
+    if locale == Locale::ZhCn {
+        return message.to_string();
+    }
+
+    for (zh, en) in MESSAGES {
+        if let Some(captures) = match_template(zh, message) {
+            let mut parts = en.split("{}");
+            let mut result = parts.next().unwrap_or_default().to_string();
+            for (capture, part) in captures.iter().zip(parts) {
+                result.push_str(capture);
+                result.push_str(part);
+            }
+            return result;
+        }
+    }
+    message.to_string()
+}
+
+/// 使用 depot 中的当前语言翻译消息
+pub fn t(depot: &Depot, message: &str) -> String {
+    translate(message, Locale::from_depot(depot))
+}
+
+/// 从多语言映射中取出目标语言的文本，依次匹配完整标签和语言代码
+pub fn pick_translation(translations: &HashMap<String, String>, locale: Locale) -> Option<&str> {
+    translations
+        .get(locale.as_tag())
+        .or_else(|| translations.get(locale.language()))
+        .map(String::as_str)
+}
+
+/// 按模板匹配消息，返回各占位符捕获的文本
+fn match_template<'a>(template: &str, message: &'a str) -> Option<Vec<&'a str>> {
+    let parts: Vec<&str> = template.split("{}").collect();
+    if parts.len() == 1 {
+        return (template == message).then(Vec::new);
+    }
+
+    let mut rest = message.strip_prefix(parts[0])?;
+    let mut captures = Vec::with_capacity(parts.len() - 1);
+    for (i, part) in parts[1..].iter().enumerate() {
+        if i == parts.len() - 2 {
+            captures.push(rest.strip_suffix(part)?);
+        } else {
+            let idx = rest.find(part)?;
+            captures.push(&rest[..idx]);
+            rest = &rest[idx + part.len()..];
+        }
+    }
+    Some(captures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accept_language_picks_highest_supported_quality() {
+        assert_eq!(Locale::from_accept_language("en-US"), Locale::EnUs);
+        assert_eq!(Locale::from_accept_language("en-GB,en;q=0.9"), Locale::EnUs);
+        assert_eq!(
+            Locale::from_accept_language("zh-CN;q=0.5, en;q=0.8"),
+            Locale::EnUs
+        );
+        assert_eq!(
+            Locale::from_accept_language("fr-FR, zh_TW;q=0.7, en;q=0.3"),
+            Locale::ZhCn
+        );
+    }
+
+    #[test]
+    fn accept_language_keeps_header_order_on_equal_quality() {
+        assert_eq!(Locale::from_accept_language("en, zh"), Locale::EnUs);
+        assert_eq!(
+            Locale::from_accept_language("zh;q=0.8, en;q=0.8"),
+            Locale::ZhCn
+        );
+    }
+
+    #[test]
+    fn accept_language_falls_back_to_default() {
+        assert_eq!(Locale::from_accept_language(""), Locale::ZhCn);
+        assert_eq!(Locale::from_accept_language("fr, de;q=0.5"), Locale::ZhCn);
+        assert_eq!(Locale::from_accept_language("en;q=0"), Locale::ZhCn);
+        assert_eq!(Locale::from_accept_language("*"), Locale::ZhCn);
+    }
+
+    #[test]
+    fn translate_fills_placeholders() {
+        assert_eq!(
+            translate("不支持的列类型: {} ({})", Locale::ZhCn),
+            "不支持的列类型: {} ({})"
+        );
+        assert_eq!(
+            translate("不支持的列类型: tags (_text)", Locale::EnUs),
+            "Unsupported column type: tags (_text)"
+        );
+        assert_eq!(
+            translate("数据库未连接", Locale::EnUs),
+            "Database not connected"
+        );
+        assert_eq!(translate("未收录的消息", Locale::EnUs), "未收录的消息");
+    }
+
+    #[test]
+    fn catalogue_templates_have_matching_placeholders() {
+        for (zh, en) in MESSAGES {
+            assert_eq!(zh.matches("{}").count(), en.matches("{}").count(), "{}", zh);
+        }
+    }
+
+    #[test]
+    fn pick_translation_falls_back_to_language() {
+        let translations: HashMap<String, String> = [("en".to_string(), "Home".to_string())]
+            .into_iter()
+            .collect();
+        assert_eq!(pick_translation(&translations, Locale::EnUs), Some("Home"));
+        assert_eq!(pick_translation(&translations, Locale::ZhCn), None);
+    }
+}
@@ -1,10 +1,15 @@
 use chrono::{Duration, Utc};
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use salvo::http::cookie::{time, Cookie, SameSite};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::config::{CookieConfig, JwtConfig};
 use super::error::AppError;
 
+/// 存放 refresh token 的 Cookie 名称
+pub const REFRESH_TOKEN_COOKIE: &str = "refresh_token";
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Claims {
     pub sub: String,
@@ -52,15 +57,45 @@ pub struct JwtService {
     secret: String,
     access_token_expiration_hours: i64,
     refresh_token_expiration_days: i64,
+    cookie: CookieConfig,
 }
 
 impl JwtService {
-    pub fn new(secret: String, expiration_hours: i64) -> Self {
+    pub fn new(config: &JwtConfig, cookie: &CookieConfig) -> Self {
         Self {
-            secret,
-            access_token_expiration_hours: expiration_hours,
-            refresh_token_expiration_days: 7,
+            secret: config.secret.clone(),
+            access_token_expiration_hours: config.expiration_hours,
+            refresh_token_expiration_days: config.refresh_expiration_days,
+            cookie: cookie.clone(),
+        }
+    }
+
+    /// 存放 refresh token 的 Cookie，有效期与 refresh token 一致
+    pub fn refresh_token_cookie(&self, token: String) -> Cookie<'static> {
+        let max_age = time::Duration::days(self.refresh_token_expiration_days);
+        self.build_cookie(token, max_age)
+    }
+
+    /// 清除 refresh token 的 Cookie
+    pub fn removal_cookie(&self) -> Cookie<'static> {
+        self.build_cookie(String::new(), time::Duration::ZERO)
+    }
+
+    fn build_cookie(&self, value: String, max_age: time::Duration) -> Cookie<'static> {
+        let mut cookie = Cookie::new(REFRESH_TOKEN_COOKIE, value);
+        cookie.set_path(self.cookie.path.clone());
+        if let Some(domain) = &self.cookie.domain {
+            cookie.set_domain(domain.clone());
         }
+        cookie.set_http_only(true);
+        cookie.set_secure(self.cookie.secure);
+        cookie.set_same_site(match self.cookie.same_site.as_str() {
+            "strict" => SameSite::Strict,
+            "none" => SameSite::None,
+            _ => SameSite::Lax,
+        });
+        cookie.set_max_age(max_age);
+        cookie
     }
 
     pub fn generate_access_token(&self, user_id: Uuid, role_id: Uuid, role_code: String) -> Result<String, AppError> {
@@ -0,0 +1,250 @@
+// 监控指标模块
+// 以 Prometheus 文本格式导出 HTTP 请求、登录、令牌刷新、数据库连接池和 SQL 耗时等指标。
+// HTTP 指标按路由模板（如 /api/v1/users/{id}）统计，未匹配路由的请求统一记为 unmatched，避免标签基数失控。
+
+use prometheus::{
+    register_histogram_vec, register_int_counter_vec, register_int_gauge_vec, Encoder,
+    HistogramVec, IntCounterVec, IntGaugeVec, TextEncoder,
+};
+use salvo::http::header::{HeaderValue, CONTENT_TYPE};
+use salvo::prelude::*;
+use sea_orm::{metric, DatabaseConnection};
+use std::sync::{Arc, LazyLock};
+use std::time::Instant;
+
+/// 未匹配任何路由的请求使用的路由标签
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// SQL 耗时分桶（秒），比 HTTP 请求更细
+const QUERY_BUCKETS: &[f64] = &[
+    0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5,
+];
+
+static HTTP_REQUESTS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!(
+        "http_requests_total",
+        "HTTP 请求数",
+        &["method", "route", "status"]
+    )
+    .expect("指标 http_requests_total 注册失败")
+});
+
+static HTTP_DURATION: LazyLock<HistogramVec> = LazyLock::new(|| {
+    register_histogram_vec!(
+        "http_request_duration_seconds",
+        "HTTP 请求耗时（秒）",
+        &["method", "route", "status"]
+    )
+    .expect("指标 http_request_duration_seconds 注册失败")
+});
+
+static LOGINS: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!("auth_login_total", "登录次数", &["result"])
+        .expect("指标 auth_login_total 注册失败")
+});
+
+static TOKEN_REFRESHES: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register_int_counter_vec!("auth_token_refresh_total", "令牌刷新次数", &["result"])
+        .expect("指标 auth_token_refresh_total 注册失败")
+});
+
+static DB_POOL: LazyLock<IntGaugeVec> = LazyLock::new(|| {
+    register_int_gauge_vec!(
+        "db_pool_connections",
+        "数据库连接池连接数，state 为 idle、in_use、max",
+        &["state"]
+    )
+    .expect("指标 db_pool_connections 注册失败")
+});
+
+static DB_QUERY_DURATION: LazyLock<HistogramVec> = LazyLock::new(|| {
+    register_histogram_vec!(
+        "db_query_duration_seconds",
+        "SQL 执行耗时（秒）",
+        &["operation", "result"],
+        QUERY_BUCKETS.to_vec()
+    )
+    .expect("指标 db_query_duration_seconds 注册失败")
+});
+
+/// 统计 HTTP 请求数和耗时，挂在根路由上
+#[handler]
+pub async fn track_requests(
+    req: &mut Request,
+    depot: &mut Depot,
+    res: &mut Response,
+    ctrl: &mut FlowCtrl,
+) {
+    let started = Instant::now();
+    ctrl.call_next(req, depot, res).await;
+
+    let route = route_template(req);
+    let status = res.status_code.unwrap_or(StatusCode::OK);
+    let labels = [req.method().as_str(), route.as_str(), status.as_str()];
+    HTTP_REQUESTS.with_label_values(&labels).inc();
+    HTTP_DURATION
+        .with_label_values(&labels)
+        .observe(started.elapsed().as_secs_f64());
+}
+
+/// 记录一次登录结果
+pub fn record_login(success: bool) {
+    LOGINS.with_label_values(&[result_label(success)]).inc();
+}
+
+/// 记录一次令牌刷新结果
+pub fn record_token_refresh(success: bool) {
+    TOKEN_REFRESHES
+        .with_label_values(&[result_label(success)])
+        .inc();
+}
+
+/// 记录 SQL 执行耗时，作为 SeaORM 的 metric 回调
+pub fn observe_query(info: &metric::Info<'_>) {
+    DB_QUERY_DURATION
+        .with_label_values(&[
+            query_operation(&info.statement.sql),
+            result_label(!info.failed),
+        ])
+        .observe(info.elapsed.as_secs_f64());
+}
+
+/// 导出 Prometheus 指标
+#[handler]
+pub async fn export(depot: &Depot, res: &mut Response) {
+    // 连接池状态在抓取时读取
+    let (idle, in_use, max) = match depot.get::<Arc<DatabaseConnection>>("db") {
+        Ok(db) => {
+            let pool = db.get_postgres_connection_pool();
+            let size = i64::from(pool.size());
+            let idle = pool.num_idle() as i64;
+            (
+                idle,
+                (size - idle).max(0),
+                i64::from(pool.options().get_max_connections()),
+            )
+        }
+        Err(_) => (0, 0, 0),
+    };
+    DB_POOL.with_label_values(&["idle"]).set(idle);
+    DB_POOL.with_label_values(&["in_use"]).set(in_use);
+    DB_POOL.with_label_values(&["max"]).set(max);
+
+    let encoder = TextEncoder::new();
+    let mut body = Vec::new();
+    if let Err(e) = encoder.encode(&prometheus::gather(), &mut body) {
+        tracing::error!("导出监控指标失败: {}", e);
+        res.status_code(StatusCode::INTERNAL_SERVER_ERROR);
+        return;
+    }
+    if let Ok(value) = HeaderValue::from_str(encoder.format_type()) {
+        res.headers_mut().insert(CONTENT_TYPE, value);
+    }
+    let _ = res.write_body(body);
+}
+
+// ========== 辅助函数 ==========
+
+fn result_label(success: bool) -> &'static str {
+    if success {
+        "success"
+    } else {
+        "failure"
+    }
+}
+
+/// SQL 语句类型，取第一个关键字
+pub(crate) fn query_operation(sql: &str) -> &'static str {
+    let keyword = sql.split_whitespace().next().unwrap_or_default();
+    ["SELECT", "INSERT", "UPDATE", "DELETE", "WITH"]
+        .into_iter()
+        .find(|op| keyword.eq_ignore_ascii_case(op))
+        .unwrap_or("OTHER")
+}
+
+/// 请求匹配的路由模板，如 /api/v1/users/3f2a... -> /api/v1/users/{id}
+///
+/// 取路由匹配时记录的路径，参数段已是参数名，不受请求路径中编码方式的影响；
+/// 未匹配任何路由时返回 unmatched，扫描器请求的随机路径不会产生新的标签。
+pub(crate) fn route_template(req: &Request) -> String {
+    match req.matched_path() {
+        "" if req.uri().path() != "/" => UNMATCHED_ROUTE.to_string(),
+        matched => format!("/{}", matched),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use salvo::test::TestClient;
+
+    /// 请求处理完成后把路由模板写入响应头，作为服务级中间件也能看到未匹配的请求
+    #[handler]
+    async fn record_route(
+        req: &mut Request,
+        depot: &mut Depot,
+        res: &mut Response,
+        ctrl: &mut FlowCtrl,
+    ) {
+        ctrl.call_next(req, depot, res).await;
+        let route = route_template(req);
+        res.headers_mut()
+            .insert("x-route", HeaderValue::from_str(&route).unwrap());
+    }
+
+    #[handler]
+    async fn ok() -> &'static str {
+        "ok"
+    }
+
+    async fn route_of(path: &str) -> String {
+        let service = Service::new(
+            Router::with_path("api/v1")
+                .push(Router::with_path("users/{id}").get(ok))
+                .push(Router::with_path("files/{**rest}").get(ok)),
+        )
+        .hoop(record_route);
+        let res = TestClient::get(format!("http://127.0.0.1{}", path))
+            .send(&service)
+            .await;
+        res.headers()["x-route"].to_str().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn route_template_uses_parameter_names() {
+        assert_eq!(
+            route_of("/api/v1/users/3f2a0c1e-0000-0000-0000-000000000001").await,
+            "/api/v1/users/{id}"
+        );
+        assert_eq!(
+            route_of("/api/v1/users/%E5%BC%A0").await,
+            "/api/v1/users/{id}"
+        );
+        assert_eq!(
+            route_of("/api/v1/files/a/b/c.txt").await,
+            "/api/v1/files/{**rest}"
+        );
+    }
+
+    #[tokio::test]
+    async fn unmatched_requests_share_one_label() {
+        assert_eq!(route_of("/wp-login.php").await, UNMATCHED_ROUTE);
+        assert_eq!(route_of("/api/v1/users").await, UNMATCHED_ROUTE);
+    }
+
+    #[test]
+    fn query_operation_takes_first_keyword() {
+        assert_eq!(query_operation("SELECT * FROM users"), "SELECT");
+        assert_eq!(query_operation("  insert INTO users VALUES ($1)"), "INSERT");
+        assert_eq!(
+            query_operation("\nWITH t AS (SELECT 1) SELECT * FROM t"),
+            "WITH"
+        );
+        assert_eq!(
+            query_operation("SELECT pg_advisory_xact_lock($1)"),
+            "SELECT"
+        );
+        assert_eq!(query_operation("BEGIN"), "OTHER");
+        assert_eq!(query_operation(""), "OTHER");
+    }
+}
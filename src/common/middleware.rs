@@ -1,18 +1,24 @@
+use chrono::{SubsecRound, Utc};
 use salvo::prelude::*;
+use sea_orm::{DatabaseConnection, EntityTrait, QuerySelect};
 use std::sync::Arc;
-use sea_orm::DatabaseConnection;
+use uuid::Uuid;
 
+use super::constants::SUPER_ADMIN_ROLE_ID;
+use super::database::SharedDb;
 use super::jwt::{JwtService, Claims};
 use super::error::ErrorResponse;
+use super::i18n::{self, Locale};
+use crate::models::user;
 
 // 依赖注入中间件
 pub struct DepsMiddleware {
-    db: Option<Arc<DatabaseConnection>>,
+    db: SharedDb,
     jwt_service: Arc<JwtService>,
 }
 
 impl DepsMiddleware {
-    pub fn new(db: Option<Arc<DatabaseConnection>>, jwt_service: Arc<JwtService>) -> Self {
+    pub fn new(db: SharedDb, jwt_service: Arc<JwtService>) -> Self {
         Self { db, jwt_service }
     }
 }
@@ -20,10 +26,11 @@ impl DepsMiddleware {
 #[async_trait]
 impl Handler for DepsMiddleware {
     async fn handle(&self, _req: &mut Request, depot: &mut Depot, _res: &mut Response, ctrl: &mut FlowCtrl) {
-        if let Some(db) = &self.db {
-            depot.insert("db", db.clone());
+        if let Some(db) = self.db.get() {
+            depot.insert("db", db);
         }
         depot.insert("jwt_service", self.jwt_service.clone());
+        depot.insert("locale", Locale::from_request(_req));
         ctrl.call_next(_req, depot, _res).await;
     }
 }
@@ -42,8 +49,9 @@ pub async fn auth_middleware(
         None => {
             res.render(Json(ErrorResponse::new(
                 401,
-                "未提供认证令牌".to_string(),
-            )));
+                i18n::translate("未提供认证令牌", Locale::from_depot(depot)),
+            )
+            .with_request_id(depot)));
             res.status_code(StatusCode::UNAUTHORIZED);
             ctrl.skip_rest();
             return;
@@ -53,26 +61,88 @@ pub async fn auth_middleware(
     // 获取 JWT 服务
     let jwt_service = depot.get::<Arc<JwtService>>("jwt_service").unwrap();
     
-    // 验证 token
-    match jwt_service.validate_token(&token) {
+    // 验证 token，被强制下线的用户签发过的令牌一并失效
+    let validated = match jwt_service.validate_token(&token) {
+        Ok(claims) => match depot.get::<Arc<DatabaseConnection>>("db") {
+            Ok(db) if is_token_revoked(db, &claims).await => Err(()),
+            _ => Ok(claims),
+        },
+        Err(_) => Err(()),
+    };
+    match validated {
         Ok(claims) => {
             depot.insert("user_id", claims.sub.clone());
             depot.insert("role_id", claims.role_id.clone());
             depot.insert("role_code", claims.role_code.clone());
             depot.insert("claims", claims);
         }
-        Err(_) => {
+        Err(()) => {
             res.render(Json(ErrorResponse::new(
                 401,
-                "无效的认证令牌".to_string(),
-            )));
+                i18n::translate("无效的认证令牌", Locale::from_depot(depot)),
+            )
+            .with_request_id(depot)));
             res.status_code(StatusCode::UNAUTHORIZED);
             ctrl.skip_rest();
         }
     }
 }
 
-fn extract_token_from_header(req: &Request) -> Option<String> {
+// 管理员权限中间件，需挂在 auth_middleware 之后
+#[handler]
+pub async fn admin_only(depot: &mut Depot, res: &mut Response, ctrl: &mut FlowCtrl) {
+    if !is_admin(depot) {
+        res.render(Json(
+            ErrorResponse::new(
+                403,
+                i18n::translate("需要管理员权限", Locale::from_depot(depot)),
+            )
+            .with_request_id(depot),
+        ));
+        res.status_code(StatusCode::FORBIDDEN);
+        ctrl.skip_rest();
+    }
+}
+
+/// 当前登录用户是否为超级管理员，需在 auth_middleware 之后调用
+pub fn is_admin(depot: &Depot) -> bool {
+    depot
+        .get::<String>("role_id")
+        .is_ok_and(|role_id| role_id == SUPER_ADMIN_ROLE_ID)
+}
+
+/// 当前时间按秒截断，作为用户的令牌吊销时间写入 tokens_revoked_time
+///
+/// 令牌的签发时间只精确到秒，吊销时间同样按秒保存，吊销之后签发的令牌
+/// 即使与吊销发生在同一秒内也仍然有效。
+pub fn tokens_revoked_now() -> chrono::NaiveDateTime {
+    Utc::now().trunc_subsecs(0).naive_utc()
+}
+
+/// 令牌是否已被吊销：签发时间早于用户的令牌吊销时间
+///
+/// 查询失败时不拦截，由后续数据库操作报告错误。
+pub async fn is_token_revoked(db: &DatabaseConnection, claims: &Claims) -> bool {
+    let Ok(user_id) = Uuid::parse_str(&claims.sub) else {
+        return true;
+    };
+    let revoked_time = user::Entity::find_by_id(user_id)
+        .select_only()
+        .column(user::Column::TokensRevokedTime)
+        .into_tuple::<Option<chrono::NaiveDateTime>>()
+        .one(db)
+        .await;
+    match revoked_time {
+        Ok(Some(Some(revoked_time))) => claims.iat < revoked_time.and_utc().timestamp(),
+        Ok(_) => false,
+        Err(e) => {
+            tracing::warn!("查询令牌吊销状态失败: {}", e);
+            false
+        }
+    }
+}
+
+pub fn extract_token_from_header(req: &Request) -> Option<String> {
     req.headers()
         .get("Authorization")
         .and_then(|h| h.to_str().ok())
@@ -0,0 +1,458 @@
+// 数据库迁移模块
+// migrations 目录下的 SQL 在构建时嵌入程序，已执行的版本及其校验和记录在 schema_migrations 表中。
+// 每个迁移在独立事务中执行，并持有事务级咨询锁，多实例同时启动时只有一个实例执行。
+// 迁移机制引入前手动初始化的数据库（已有 001、002 创建的表）首次迁移时自动接管，无需额外操作。
+
+use chrono::NaiveDateTime;
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, DbBackend, DbErr, FromQueryResult, Statement,
+    TransactionTrait,
+};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// 嵌入的迁移
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    pub up: &'static str,
+    /// 回滚脚本，没有时该迁移不可回滚
+    pub down: Option<&'static str>,
+}
+
+include!(concat!(env!("OUT_DIR"), "/migrations.rs"));
+
+/// 迁移咨询锁的键
+const LOCK_KEY: i64 = 0x6d61_706c_656d_6967;
+/// 迁移机制引入前需手动执行的迁移及其创建的表，用于接管已有数据库
+const BASELINE: &[(i64, &[&str])] = &[
+    (1, &["users", "roles", "user_roles"]),
+    (2, &["menus", "role_menus"]),
+];
+
+/// 已执行的迁移记录
+#[derive(Debug, FromQueryResult)]
+pub struct AppliedMigration {
+    pub version: i64,
+    pub name: String,
+    pub checksum: String,
+    pub execution_ms: i64,
+    pub applied_time: NaiveDateTime,
+}
+
+/// 迁移状态
+#[derive(Debug)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub name: String,
+    /// 未执行时为 None
+    pub applied_time: Option<NaiveDateTime>,
+    pub execution_ms: Option<i64>,
+    /// 已执行且脚本在执行后被修改
+    pub modified: bool,
+    /// 数据库中有记录但程序中不存在（数据库由更新版本的程序迁移过）
+    pub missing: bool,
+}
+
+/// 执行所有未执行的迁移，返回本次执行的版本
+///
+/// 已执行的迁移脚本被修改过时拒绝执行，避免数据库结构与代码不一致。
+pub async fn migrate_up(db: &DatabaseConnection) -> Result<Vec<&'static Migration>, DbErr> {
+    ensure_table(db).await?;
+    adopt_existing_schema(db).await?;
+    let applied = applied(db).await?;
+    verify(&applied)?;
+
+    let mut executed = Vec::new();
+    for migration in MIGRATIONS {
+        if applied.iter().any(|r| r.version == migration.version) {
+            continue;
+        }
+        let txn = db.begin().await?;
+        lock(&txn).await?;
+        // 持锁后重新检查，其他实例可能已执行
+        if is_applied(&txn, migration.version).await? {
+            txn.rollback().await?;
+            continue;
+        }
+
+        let started = Instant::now();
+        txn.execute_unprepared(migration.up).await.map_err(|e| {
+            DbErr::Custom(format!(
+                "迁移 {:03}_{} 执行失败: {}",
+                migration.version, migration.name, e
+            ))
+        })?;
+        txn.execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "INSERT INTO schema_migrations (version, name, checksum, execution_ms) VALUES ($1, $2, $3, $4)",
+            [
+                migration.version.into(),
+                migration.name.into(),
+                checksum(migration.up).into(),
+                (started.elapsed().as_millis() as i64).into(),
+            ],
+        ))
+        .await?;
+        txn.commit().await?;
+
+        tracing::info!(
+            "✅ 已执行迁移 {:03}_{}（{}ms）",
+            migration.version,
+            migration.name,
+            started.elapsed().as_millis()
+        );
+        executed.push(migration);
+    }
+    Ok(executed)
+}
+
+/// 按版本号倒序回滚最近的 steps 个迁移，返回被回滚的版本
+pub async fn migrate_down(
+    db: &DatabaseConnection,
+    steps: usize,
+) -> Result<Vec<&'static Migration>, DbErr> {
+    ensure_table(db).await?;
+    let applied = applied(db).await?;
+    verify(&applied)?;
+
+    let mut rolled_back = Vec::new();
+    for record in applied.iter().rev().take(steps) {
+        let migration = find(record.version)
+            .ok_or_else(|| DbErr::Custom(format!("迁移 {} 在当前程序中不存在", record.version)))?;
+        let down = migration.down.ok_or_else(|| {
+            DbErr::Custom(format!(
+                "迁移 {:03}_{} 没有回滚脚本",
+                migration.version, migration.name
+            ))
+        })?;
+
+        let txn = db.begin().await?;
+        lock(&txn).await?;
+        if !is_applied(&txn, migration.version).await? {
+            txn.rollback().await?;
+            continue;
+        }
+        txn.execute_unprepared(down).await.map_err(|e| {
+            DbErr::Custom(format!(
+                "迁移 {:03}_{} 回滚失败: {}",
+                migration.version, migration.name, e
+            ))
+        })?;
+        txn.execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "DELETE FROM schema_migrations WHERE version = $1",
+            [migration.version.into()],
+        ))
+        .await?;
+        txn.commit().await?;
+
+        tracing::info!("✅ 已回滚迁移 {:03}_{}", migration.version, migration.name);
+        rolled_back.push(migration);
+    }
+    Ok(rolled_back)
+}
+
+/// 查询所有迁移的执行状态
+pub async fn status(db: &DatabaseConnection) -> Result<Vec<MigrationStatus>, DbErr> {
+    ensure_table(db).await?;
+    let mut applied: HashMap<i64, AppliedMigration> = applied(db)
+        .await?
+        .into_iter()
+        .map(|m| (m.version, m))
+        .collect();
+
+    let mut result: Vec<MigrationStatus> = MIGRATIONS
+        .iter()
+        .map(|migration| {
+            let record = applied.remove(&migration.version);
+            MigrationStatus {
+                version: migration.version,
+                name: migration.name.to_string(),
+                modified: record
+                    .as_ref()
+                    .is_some_and(|r| r.checksum != checksum(migration.up)),
+                execution_ms: record.as_ref().map(|r| r.execution_ms),
+                applied_time: record.map(|r| r.applied_time),
+                missing: false,
+            }
+        })
+        .collect();
+    result.extend(applied.into_values().map(|record| MigrationStatus {
+        version: record.version,
+        name: record.name,
+        applied_time: Some(record.applied_time),
+        execution_ms: Some(record.execution_ms),
+        modified: false,
+        missing: true,
+    }));
+    result.sort_by_key(|s| s.version);
+    Ok(result)
+}
+
+// ========== 辅助函数 ==========
+
+async fn ensure_table(db: &DatabaseConnection) -> Result<(), DbErr> {
+    db.execute_unprepared(
+        r#"
+        CREATE TABLE IF NOT EXISTS schema_migrations (
+            version BIGINT PRIMARY KEY,
+            name VARCHAR(255) NOT NULL,
+            checksum VARCHAR(64) NOT NULL,              -- 迁移脚本的 SHA-256
+            execution_ms BIGINT NOT NULL,               -- 执行耗时（毫秒）
+            applied_time TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )
+        "#,
+    )
+    .await
+    .map(|_| ())
+}
+
+/// 接管迁移机制引入前手动初始化的数据库
+///
+/// 此前 001、002 通过 init-db.sh 手动执行，数据库中有表但没有迁移记录，直接执行会因索引和
+/// 初始数据已存在而失败。迁移记录为空且对应的表已存在时，将这两个迁移记为已执行。
+async fn adopt_existing_schema(db: &DatabaseConnection) -> Result<(), DbErr> {
+    let txn = db.begin().await?;
+    lock(&txn).await?;
+    let recorded = txn
+        .query_one(Statement::from_string(
+            DbBackend::Postgres,
+            "SELECT 1 FROM schema_migrations LIMIT 1",
+        ))
+        .await?
+        .is_some();
+    if recorded {
+        return txn.rollback().await;
+    }
+
+    for (version, tables) in BASELINE {
+        if !tables_exist(&txn, tables).await? {
+            break;
+        }
+        let Some(migration) = find(*version) else {
+            break;
+        };
+        txn.execute(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            "INSERT INTO schema_migrations (version, name, checksum, execution_ms) VALUES ($1, $2, $3, 0)",
+            [
+                migration.version.into(),
+                migration.name.into(),
+                checksum(migration.up).into(),
+            ],
+        ))
+        .await?;
+        tracing::info!(
+            "✅ 数据库已存在 {} 表，迁移 {:03}_{} 记为已执行",
+            tables.join("、"),
+            migration.version,
+            migration.name
+        );
+    }
+    txn.commit().await
+}
+
+async fn tables_exist<C: ConnectionTrait>(conn: &C, tables: &[&str]) -> Result<bool, DbErr> {
+    for table in tables {
+        let exists = conn
+            .query_one(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                "SELECT to_regclass($1) IS NOT NULL AS exists",
+                [table.to_string().into()],
+            ))
+            .await?
+            .map(|row| row.try_get::<bool>("", "exists"))
+            .transpose()?
+            .unwrap_or(false);
+        if !exists {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+async fn applied(db: &DatabaseConnection) -> Result<Vec<AppliedMigration>, DbErr> {
+    AppliedMigration::find_by_statement(Statement::from_string(
+        DbBackend::Postgres,
+        "SELECT version, name, checksum, execution_ms, applied_time FROM schema_migrations ORDER BY version",
+    ))
+    .all(db)
+    .await
+}
+
+async fn is_applied<C: ConnectionTrait>(conn: &C, version: i64) -> Result<bool, DbErr> {
+    conn.query_one(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "SELECT 1 FROM schema_migrations WHERE version = $1",
+        [version.into()],
+    ))
+    .await
+    .map(|row| row.is_some())
+}
+
+async fn lock<C: ConnectionTrait>(conn: &C) -> Result<(), DbErr> {
+    conn.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "SELECT pg_advisory_xact_lock($1)",
+        [LOCK_KEY.into()],
+    ))
+    .await
+    .map(|_| ())
+}
+
+/// 校验已执行的迁移与程序中嵌入的脚本一致
+fn verify(applied: &[AppliedMigration]) -> Result<(), DbErr> {
+    for record in applied {
+        let migration = find(record.version).ok_or_else(|| {
+            DbErr::Custom(format!(
+                "数据库已执行迁移 {:03}_{}，但当前程序中不存在，请升级程序",
+                record.version, record.name
+            ))
+        })?;
+        if record.checksum != checksum(migration.up) {
+            return Err(DbErr::Custom(format!(
+                "迁移 {:03}_{} 在执行后被修改（校验和不一致），请新增迁移而不是修改已执行的迁移",
+                migration.version, migration.name
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn find(version: i64) -> Option<&'static Migration> {
+    MIGRATIONS.iter().find(|m| m.version == version)
+}
+
+/// 脚本的 SHA-256，统一换行符，避免不同平台检出的文件校验和不同
+fn checksum(sql: &str) -> String {
+    hex::encode(Sha256::digest(sql.replace("\r\n", "\n").as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::testing::TestDb;
+
+    fn versions(migrations: &[&Migration]) -> Vec<i64> {
+        migrations.iter().map(|m| m.version).collect()
+    }
+
+    #[test]
+    fn embedded_migrations_are_ordered() {
+        assert!(MIGRATIONS.windows(2).all(|w| w[0].version < w[1].version));
+        assert!(MIGRATIONS.iter().all(|m| m.down.is_some()));
+    }
+
+    #[tokio::test]
+    async fn up_down_and_status() {
+        let Some(db) = TestDb::empty().await else {
+            return;
+        };
+        let all: Vec<i64> = MIGRATIONS.iter().map(|m| m.version).collect();
+
+        let executed = migrate_up(&db).await.unwrap();
+        assert_eq!(versions(&executed), all);
+        assert!(migrate_up(&db).await.unwrap().is_empty());
+        let status = status(&db).await.unwrap();
+        assert!(
+            status
+                .iter()
+                .all(|s| s.applied_time.is_some() && !s.modified)
+        );
+
+        let rolled_back = migrate_down(&db, 2).await.unwrap();
+        assert_eq!(
+            versions(&rolled_back),
+            all[all.len() - 2..]
+                .iter()
+                .rev()
+                .copied()
+                .collect::<Vec<_>>()
+        );
+        let pending: Vec<i64> = super::status(&db)
+            .await
+            .unwrap()
+            .iter()
+            .filter(|s| s.applied_time.is_none())
+            .map(|s| s.version)
+            .collect();
+        assert_eq!(pending, all[all.len() - 2..]);
+
+        // 全部回滚后重新执行，校验每个回滚脚本都能撤销对应的迁移
+        migrate_down(&db, all.len()).await.unwrap();
+        assert_eq!(versions(&migrate_up(&db).await.unwrap()), all);
+    }
+
+    #[tokio::test]
+    async fn adopts_schema_initialized_by_hand() {
+        let Some(db) = TestDb::empty().await else {
+            return;
+        };
+        // 迁移机制引入前由 init-db.sh 手动执行 001、002
+        for migration in &MIGRATIONS[..2] {
+            db.execute_unprepared(migration.up).await.unwrap();
+        }
+
+        let executed = migrate_up(&db).await.unwrap();
+        assert_eq!(
+            versions(&executed),
+            MIGRATIONS[2..]
+                .iter()
+                .map(|m| m.version)
+                .collect::<Vec<_>>()
+        );
+        let status = status(&db).await.unwrap();
+        assert!(
+            status
+                .iter()
+                .all(|s| s.applied_time.is_some() && !s.modified)
+        );
+
+        let real_name = db
+            .query_one(Statement::from_string(
+                DbBackend::Postgres,
+                "SELECT real_name FROM users WHERE username = 'superAdmin'",
+            ))
+            .await
+            .unwrap()
+            .unwrap()
+            .try_get::<String>("", "real_name")
+            .unwrap();
+        assert_eq!(real_name, "超级管理员");
+    }
+
+    #[tokio::test]
+    async fn does_not_adopt_partial_schema() {
+        let Some(db) = TestDb::empty().await else {
+            return;
+        };
+        db.execute_unprepared(MIGRATIONS[0].up).await.unwrap();
+        db.execute_unprepared("DROP TABLE user_roles")
+            .await
+            .unwrap();
+
+        // 001 未被接管，重新执行时因索引已存在而失败
+        assert!(migrate_up(&db).await.is_err());
+        assert!(applied(&db).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn rejects_modified_migration() {
+        let Some(db) = TestDb::migrated().await else {
+            return;
+        };
+        db.execute_unprepared(
+            "UPDATE schema_migrations SET checksum = 'changed' WHERE version = 1",
+        )
+        .await
+        .unwrap();
+
+        assert!(migrate_up(&db).await.is_err());
+        assert!(migrate_down(&db, 1).await.is_err());
+        let status = status(&db).await.unwrap();
+        assert!(status[0].modified);
+        assert!(status[1..].iter().all(|s| !s.modified));
+    }
+}
@@ -1,14 +1,25 @@
 pub mod config;
 pub mod database;
+pub mod migration;
 pub mod postgres_service;
 pub mod error;
 pub mod response;
 pub mod middleware;
+pub mod metrics;
 pub mod jwt;
 pub mod crypto;
 pub mod rsa_crypto;
 pub mod key_manager;
 pub mod constants;
+pub mod i18n;
+pub mod push;
+pub mod scheduler;
+pub mod shutdown;
+pub mod storage;
+pub mod telemetry;
+pub mod validation;
+#[cfg(test)]
+pub mod testing;
 
 pub use config::AppConfig;
 pub use error::{AppError, ErrorResponse};
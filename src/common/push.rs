@@ -0,0 +1,237 @@
+// 实时推送模块
+// 维护在线用户的推送连接（WebSocket / SSE），供业务代码向指定用户或全部在线用户推送事件
+
+use rand::RngCore;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{self, error::TrySendError, Receiver, Sender};
+use uuid::Uuid;
+
+/// 单个连接待发送事件的上限，客户端消费过慢超过上限时断开连接
+const CONNECTION_BUFFER: usize = 64;
+/// 连接凭证有效期
+const TICKET_TTL: Duration = Duration::from_secs(30);
+
+/// 推送事件，序列化为 {"type": "...", "data": {...}}
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", content = "data", rename_all = "camelCase")]
+pub enum PushEvent {
+    /// 连接建立
+    Connected { connection_id: u64 },
+    /// 会话被强制下线，客户端应清除令牌并跳转登录页
+    #[serde(rename_all = "camelCase")]
+    SessionKilled { reason: Option<String> },
+    /// 权限发生变化，客户端应重新拉取菜单和权限
+    PermissionsChanged,
+    /// 有新的通知公告
+    #[serde(rename_all = "camelCase")]
+    NewNotice { id: String, title: String },
+}
+
+impl PushEvent {
+    /// 事件名称，用作 SSE 的 event 字段
+    pub fn name(&self) -> &'static str {
+        match self {
+            PushEvent::Connected { .. } => "connected",
+            PushEvent::SessionKilled { .. } => "sessionKilled",
+            PushEvent::PermissionsChanged => "permissionsChanged",
+            PushEvent::NewNotice { .. } => "newNotice",
+        }
+    }
+}
+
+struct Connection {
+    id: u64,
+    sender: Sender<PushEvent>,
+}
+
+struct Ticket {
+    user_id: Uuid,
+    expires_at: Instant,
+}
+
+// 全局连接表：用户ID -> 该用户的所有连接（同一用户可在多个页面或设备上在线）
+static CONNECTIONS: LazyLock<RwLock<HashMap<Uuid, Vec<Connection>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+static NEXT_CONNECTION_ID: AtomicU64 = AtomicU64::new(1);
+
+// 未使用的连接凭证：凭证 -> 用户
+static TICKETS: LazyLock<Mutex<HashMap<String, Ticket>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// 签发一次性连接凭证
+///
+/// 浏览器的 WebSocket 和 EventSource 无法设置请求头，用凭证代替访问令牌放在 URL 中，
+/// 避免令牌出现在访问日志里。凭证只能使用一次，有效期 30 秒。
+pub fn issue_ticket(user_id: Uuid) -> (String, Duration) {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let ticket = hex::encode(bytes);
+    if let Ok(mut tickets) = TICKETS.lock() {
+        let now = Instant::now();
+        tickets.retain(|_, t| t.expires_at > now);
+        tickets.insert(
+            ticket.clone(),
+            Ticket {
+                user_id,
+                expires_at: now + TICKET_TTL,
+            },
+        );
+    }
+    (ticket, TICKET_TTL)
+}
+
+/// 兑换连接凭证，返回签发对象；凭证不存在、已使用或已过期时返回 None
+pub fn redeem_ticket(ticket: &str) -> Option<Uuid> {
+    let ticket = TICKETS.lock().ok()?.remove(ticket)?;
+    (ticket.expires_at > Instant::now()).then_some(ticket.user_id)
+}
+
+/// 登记一个新连接，返回连接ID和事件接收端
+pub fn register(user_id: Uuid) -> (u64, Receiver<PushEvent>) {
+    let id = NEXT_CONNECTION_ID.fetch_add(1, Ordering::Relaxed);
+    let (sender, receiver) = mpsc::channel(CONNECTION_BUFFER);
+    if let Ok(mut connections) = CONNECTIONS.write() {
+        connections
+            .entry(user_id)
+            .or_default()
+            .push(Connection { id, sender });
+    }
+    tracing::debug!("推送连接建立: user={}, connection={}", user_id, id);
+    (id, receiver)
+}
+
+/// 注销连接，连接断开时调用
+pub fn unregister(user_id: Uuid, connection_id: u64) {
+    if let Ok(mut connections) = CONNECTIONS.write() {
+        if let Some(list) = connections.get_mut(&user_id) {
+            list.retain(|c| c.id != connection_id);
+            if list.is_empty() {
+                connections.remove(&user_id);
+            }
+        }
+    }
+    tracing::debug!("推送连接断开: user={}, connection={}", user_id, connection_id);
+}
+
+/// 向指定用户的所有连接推送事件，返回成功投递的连接数
+pub fn send_to_user(user_id: Uuid, event: PushEvent) -> usize {
+    send_to_users(&[user_id], event)
+}
+
+/// 向多个用户推送事件，返回成功投递的连接数
+pub fn send_to_users(user_ids: &[Uuid], event: PushEvent) -> usize {
+    let Ok(connections) = CONNECTIONS.read() else {
+        return 0;
+    };
+    let targets = user_ids
+        .iter()
+        .filter_map(|user_id| connections.get_key_value(user_id));
+    let (delivered, overflowed) = deliver(targets, &event);
+    drop(connections);
+    disconnect_overflowed(overflowed);
+    delivered
+}
+
+/// 向全部在线用户推送事件，返回成功投递的连接数
+pub fn broadcast(event: PushEvent) -> usize {
+    let Ok(connections) = CONNECTIONS.read() else {
+        return 0;
+    };
+    let (delivered, overflowed) = deliver(connections.iter(), &event);
+    drop(connections);
+    disconnect_overflowed(overflowed);
+    delivered
+}
+
+/// 断开用户的全部连接，已排队的事件发送完后连接关闭
+pub fn disconnect_user(user_id: Uuid) {
+    if let Ok(mut connections) = CONNECTIONS.write() {
+        connections.remove(&user_id);
+    }
+}
+
+/// 关闭全部连接，停机时调用，避免长连接阻塞停机
+pub fn close_all() {
+    if let Ok(mut connections) = CONNECTIONS.write() {
+        connections.clear();
+    }
+}
+
+// ========== 辅助函数 ==========
+
+/// 投递事件，返回成功投递的连接数和积压已满的连接
+fn deliver<'a>(
+    targets: impl Iterator<Item = (&'a Uuid, &'a Vec<Connection>)>,
+    event: &PushEvent,
+) -> (usize, Vec<(Uuid, u64)>) {
+    let mut delivered = 0;
+    let mut overflowed = Vec::new();
+    for (user_id, list) in targets {
+        for connection in list {
+            match connection.sender.try_send(event.clone()) {
+                Ok(()) => delivered += 1,
+                Err(TrySendError::Full(_)) => overflowed.push((*user_id, connection.id)),
+                Err(TrySendError::Closed(_)) => {}
+            }
+        }
+    }
+    (delivered, overflowed)
+}
+
+/// 注销积压已满的连接，发送端释放后接收端结束，连接随之关闭
+fn disconnect_overflowed(overflowed: Vec<(Uuid, u64)>) {
+    for (user_id, connection_id) in overflowed {
+        tracing::warn!(
+            "推送连接积压超过 {} 条，断开连接: user={}, connection={}",
+            CONNECTION_BUFFER,
+            user_id,
+            connection_id
+        );
+        unregister(user_id, connection_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ticket_can_be_redeemed_once() {
+        let user_id = Uuid::new_v4();
+        let (ticket, ttl) = issue_ticket(user_id);
+        assert_eq!(ttl, TICKET_TTL);
+        assert_eq!(redeem_ticket(&ticket), Some(user_id));
+        assert_eq!(redeem_ticket(&ticket), None);
+        assert_eq!(redeem_ticket("unknown"), None);
+    }
+
+    #[test]
+    fn expired_ticket_is_rejected() {
+        let (ticket, _) = issue_ticket(Uuid::new_v4());
+        TICKETS.lock().unwrap().get_mut(&ticket).unwrap().expires_at = Instant::now();
+        assert_eq!(redeem_ticket(&ticket), None);
+        assert!(!TICKETS.lock().unwrap().contains_key(&ticket));
+    }
+
+    #[test]
+    fn close_all_disconnects_every_receiver() {
+        let user_id = Uuid::new_v4();
+        let (_, mut first) = register(user_id);
+        let (_, mut second) = register(user_id);
+        let (_, mut other) = register(Uuid::new_v4());
+
+        close_all();
+        for receiver in [&mut first, &mut second, &mut other] {
+            assert_eq!(
+                receiver.try_recv().unwrap_err(),
+                mpsc::error::TryRecvError::Disconnected
+            );
+        }
+        assert_eq!(send_to_user(user_id, PushEvent::PermissionsChanged), 0);
+    }
+}
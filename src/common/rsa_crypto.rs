@@ -1,5 +1,5 @@
-use rsa::{RsaPrivateKey, Pkcs1v15Encrypt};
-use rsa::pkcs8::DecodePrivateKey;
+use rsa::{RsaPrivateKey, RsaPublicKey, Pkcs1v15Encrypt};
+use rsa::pkcs8::{DecodePrivateKey, DecodePublicKey};
 use rsa::traits::PublicKeyParts;
 use base64::{Engine as _, engine::general_purpose};
 use super::error::AppError;
@@ -29,6 +29,19 @@ fn get_key_manager() -> Result<KeyManager, AppError> {
         .ok_or_else(|| AppError::InternalServerError("密钥管理器未初始化".to_string()))
 }
 
+/// 校验当前配置的密钥对可以解析且相互匹配，返回密钥位数
+pub fn verify_key_pair() -> Result<usize, AppError> {
+    let key_manager = KeyManager::new()?;
+    let private_key = RsaPrivateKey::from_pkcs8_pem(key_manager.get_private_key())
+        .map_err(|e| AppError::InternalServerError(format!("私钥解析失败: {}", e)))?;
+    let public_key = RsaPublicKey::from_public_key_pem(key_manager.get_public_key())
+        .map_err(|e| AppError::InternalServerError(format!("公钥解析失败: {}", e)))?;
+    if RsaPublicKey::from(&private_key) != public_key {
+        return Err(AppError::InternalServerError("公钥与私钥不匹配".to_string()));
+    }
+    Ok(private_key.size() * 8)
+}
+
 /// 解密密码
 pub fn decrypt_password(encrypted_base64: &str) -> Result<String, AppError> {
     let key_manager = get_key_manager()?;
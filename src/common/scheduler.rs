@@ -0,0 +1,636 @@
+// 定时任务调度模块
+// 任务由代码注册，定义持久化在 scheduled_jobs 表中，各实例轮询到期任务，
+// 通过对 next_run_time 的条件更新抢占本次触发，通过数据库租约保证同一任务不被并发执行
+
+use chrono::{Local, NaiveDateTime, TimeZone, Utc};
+use cron::Schedule;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, DbErr, EntityTrait, QueryFilter,
+    Set, sea_query::{Expr, OnConflict},
+};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock, RwLock};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use super::{shutdown, AppError};
+use crate::models::{job_run, scheduled_job};
+
+/// 轮询到期任务的间隔
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// 执行租约时长，实例异常退出后租约到期即可被其他实例接管
+const LEASE_SECONDS: i64 = 60;
+/// 租约续期间隔
+const LEASE_RENEW_INTERVAL: Duration = Duration::from_secs(20);
+
+/// 任务执行结果：成功时为输出文本，失败时为错误信息
+pub type JobResult = Result<String, String>;
+pub type JobFuture = Pin<Box<dyn Future<Output = JobResult> + Send>>;
+
+/// 任务执行上下文
+#[derive(Clone)]
+pub struct JobContext {
+    pub db: Arc<DatabaseConnection>,
+}
+
+/// 并发策略
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConcurrencyPolicy {
+    /// 上一次执行未结束时跳过本次触发
+    Forbid,
+    /// 允许并发执行
+    Allow,
+}
+
+impl ConcurrencyPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConcurrencyPolicy::Forbid => "forbid",
+            ConcurrencyPolicy::Allow => "allow",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "forbid" => Some(ConcurrencyPolicy::Forbid),
+            "allow" => Some(ConcurrencyPolicy::Allow),
+            _ => None,
+        }
+    }
+}
+
+/// 触发方式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TriggerType {
+    Schedule,
+    Manual,
+}
+
+impl TriggerType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TriggerType::Schedule => "schedule",
+            TriggerType::Manual => "manual",
+        }
+    }
+}
+
+/// 代码中注册的任务定义，cron 和并发策略为首次同步到数据库时的默认值
+#[derive(Clone, Copy)]
+pub struct JobDefinition {
+    pub key: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub cron: &'static str,
+    pub concurrency: ConcurrencyPolicy,
+    pub handler: fn(JobContext) -> JobFuture,
+}
+
+// 全局任务注册表：任务标识 -> 任务定义
+static JOBS: LazyLock<RwLock<HashMap<&'static str, JobDefinition>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+// 本实例正在执行的任务数，停机时等待归零
+static RUNNING: AtomicUsize = AtomicUsize::new(0);
+
+// 当前实例标识，用于租约和执行记录
+static INSTANCE_ID: LazyLock<String> = LazyLock::new(|| {
+    let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "instance".to_string());
+    format!("{}-{}", host, &Uuid::new_v4().simple().to_string()[..8])
+});
+
+/// 注册任务，需在 start 之前调用
+pub fn register(definition: JobDefinition) {
+    if let Ok(mut jobs) = JOBS.write() {
+        jobs.insert(definition.key, definition);
+    }
+}
+
+/// 获取已注册的任务定义
+pub fn definition(key: &str) -> Option<JobDefinition> {
+    JOBS.read().ok().and_then(|jobs| jobs.get(key).copied())
+}
+
+/// 当前实例标识
+pub fn instance_id() -> &'static str {
+    INSTANCE_ID.as_str()
+}
+
+/// 解析 cron 表达式，支持 6/7 段（含秒）和标准 5 段格式
+pub fn parse_cron(expression: &str) -> Result<Schedule, AppError> {
+    let expression = expression.trim();
+    let normalized = if expression.split_whitespace().count() == 5 {
+        format!("0 {}", expression)
+    } else {
+        expression.to_string()
+    };
+    Schedule::from_str(&normalized)
+        .map_err(|e| AppError::BadRequest(format!("无效的 cron 表达式: {}", e)))
+}
+
+/// 计算下次执行时间，cron 按服务器本地时区解释，返回 UTC 时间
+pub fn next_run_time(schedule: &Schedule, after: NaiveDateTime) -> Option<NaiveDateTime> {
+    schedule
+        .after(&Local.from_utc_datetime(&after))
+        .next()
+        .map(|t| t.naive_utc())
+}
+
+/// 同步任务定义并启动调度循环
+pub async fn start(db: Arc<DatabaseConnection>) {
+    if let Err(e) = sync_jobs(db.as_ref()).await {
+        tracing::error!("同步定时任务失败: {}", e);
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            ticker.tick().await;
+            if shutdown::is_shutting_down() {
+                tracing::info!("定时任务调度已停止");
+                break;
+            }
+            if let Err(e) = poll(&db).await {
+                tracing::warn!("轮询定时任务失败: {}", e);
+            }
+        }
+    });
+    tracing::info!("定时任务调度已启动，实例: {}", instance_id());
+}
+
+/// 执行一次任务
+///
+/// 并发策略为 forbid 时先获取租约，获取失败时定时触发记录为 skipped，手动触发返回错误。
+/// 任务在后台执行，立即返回执行记录。
+pub async fn trigger(
+    db: Arc<DatabaseConnection>,
+    job: &scheduled_job::Model,
+    trigger_type: TriggerType,
+    user_id: Option<Uuid>,
+) -> Result<job_run::Model, AppError> {
+    let definition = definition(&job.job_key)
+        .ok_or(AppError::BadRequest(format!("任务 {} 未注册", job.job_key)))?;
+
+    let leased = ConcurrencyPolicy::from_str(&job.concurrency_policy)
+        .unwrap_or(definition.concurrency)
+        == ConcurrencyPolicy::Forbid;
+    if leased
+        && !acquire_lease(db.as_ref(), job.id)
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?
+    {
+        if trigger_type == TriggerType::Manual {
+            return Err(AppError::BadRequest("任务正在执行中，请稍后再试".to_string()));
+        }
+        let skipped = new_run(job.id, trigger_type, "skipped", user_id);
+        return finish_run(skipped, 0, None, Some("上一次执行尚未结束".to_string()))
+            .insert(db.as_ref())
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()));
+    }
+
+    // 持有租约后，该任务遗留的 running 记录都来自租约已过期的实例
+    if leased {
+        if let Err(e) = fail_orphaned_runs(db.as_ref(), job.id).await {
+            tracing::warn!("清理任务 {} 的遗留执行记录失败: {}", job.job_key, e);
+        }
+    }
+
+    let run = match new_run(job.id, trigger_type, "running", user_id)
+        .insert(db.as_ref())
+        .await
+    {
+        Ok(run) => run,
+        Err(e) => {
+            if leased {
+                release_lease(db.as_ref(), job.id).await;
+            }
+            return Err(AppError::InternalServerError(e.to_string()));
+        }
+    };
+
+    RUNNING.fetch_add(1, Ordering::SeqCst);
+    tokio::spawn(execute(db, definition, job.id, run.id, leased));
+    Ok(run)
+}
+
+// ========== 调度实现 ==========
+
+/// 将已注册的任务写入数据库，已存在的任务保留用户修改过的配置
+async fn sync_jobs(db: &DatabaseConnection) -> Result<(), DbErr> {
+    let definitions: Vec<JobDefinition> = JOBS
+        .read()
+        .map(|jobs| jobs.values().copied().collect())
+        .unwrap_or_default();
+
+    let now = Utc::now().naive_utc();
+    for definition in definitions {
+        let next = match parse_cron(definition.cron) {
+            Ok(schedule) => next_run_time(&schedule, now),
+            Err(e) => {
+                tracing::error!("任务 {} 的默认 cron 表达式无效: {}", definition.key, e);
+                continue;
+            }
+        };
+
+        scheduled_job::Entity::insert(scheduled_job::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            job_key: Set(definition.key.to_string()),
+            name: Set(definition.name.to_string()),
+            description: Set(Some(definition.description.to_string())),
+            cron_expression: Set(definition.cron.to_string()),
+            enabled: Set(true),
+            concurrency_policy: Set(definition.concurrency.as_str().to_string()),
+            next_run_time: Set(next),
+            last_run_time: Set(None),
+            lease_owner: Set(None),
+            lease_until: Set(None),
+            created_time: Set(now),
+            created_id: Set(None),
+            updated_time: Set(now),
+            updated_id: Set(None),
+        })
+        .on_conflict(
+            OnConflict::column(scheduled_job::Column::JobKey)
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await?;
+    }
+
+    // 租约已过期的任务不再有实例在执行，遗留的 running 记录来自异常退出的实例
+    let idle_jobs = scheduled_job::Entity::find()
+        .filter(scheduled_job::Column::ConcurrencyPolicy.eq(ConcurrencyPolicy::Forbid.as_str()))
+        .filter(
+            Condition::any()
+                .add(scheduled_job::Column::LeaseUntil.is_null())
+                .add(scheduled_job::Column::LeaseUntil.lt(now)),
+        )
+        .all(db)
+        .await?;
+    for job in idle_jobs {
+        fail_orphaned_runs(db, job.id).await?;
+    }
+
+    // 已启用但缺少下次执行时间的任务（如刚恢复的任务）补算一次
+    let jobs = scheduled_job::Entity::find()
+        .filter(scheduled_job::Column::Enabled.eq(true))
+        .filter(scheduled_job::Column::NextRunTime.is_null())
+        .all(db)
+        .await?;
+    for job in jobs {
+        if let Ok(schedule) = parse_cron(&job.cron_expression) {
+            let mut active_model: scheduled_job::ActiveModel = job.into();
+            active_model.next_run_time = Set(next_run_time(&schedule, now));
+            active_model.update(db).await?;
+        }
+    }
+    Ok(())
+}
+
+/// 查找到期任务，抢占成功的任务在本实例执行
+async fn poll(db: &Arc<DatabaseConnection>) -> Result<(), DbErr> {
+    let now = Utc::now().naive_utc();
+    let due_jobs = scheduled_job::Entity::find()
+        .filter(scheduled_job::Column::Enabled.eq(true))
+        .filter(scheduled_job::Column::NextRunTime.lte(now))
+        .all(db.as_ref())
+        .await?;
+
+    for job in due_jobs {
+        if definition(&job.job_key).is_none() {
+            continue;
+        }
+        let Some(due) = job.next_run_time else {
+            continue;
+        };
+        let next = match parse_cron(&job.cron_expression) {
+            Ok(schedule) => next_run_time(&schedule, now),
+            Err(e) => {
+                tracing::warn!("任务 {} 的 cron 表达式无效: {}", job.job_key, e);
+                None
+            }
+        };
+
+        // 条件更新 next_run_time，只有一个实例能抢占本次触发；
+        // 停机期间错过的多次触发合并为一次
+        let claimed = scheduled_job::Entity::update_many()
+            .col_expr(scheduled_job::Column::NextRunTime, Expr::value(next))
+            .col_expr(scheduled_job::Column::LastRunTime, Expr::value(Some(now)))
+            .filter(scheduled_job::Column::Id.eq(job.id))
+            .filter(scheduled_job::Column::NextRunTime.eq(due))
+            .exec(db.as_ref())
+            .await?
+            .rows_affected
+            == 1;
+        if !claimed {
+            continue;
+        }
+
+        if let Err(e) = trigger(db.clone(), &job, TriggerType::Schedule, None).await {
+            tracing::warn!("任务 {} 触发失败: {}", job.job_key, e);
+        }
+    }
+    Ok(())
+}
+
+async fn execute(
+    db: Arc<DatabaseConnection>,
+    definition: JobDefinition,
+    job_id: Uuid,
+    run_id: Uuid,
+    leased: bool,
+) {
+    let started = Instant::now();
+    let context = JobContext { db: db.clone() };
+    let mut handle = tokio::spawn((definition.handler)(context));
+
+    // 租约丢失后其他实例可能已接管，取消本次执行，避免同一任务并发执行
+    let result = if leased {
+        tokio::select! {
+            result = &mut handle => result.map_err(|e| format!("任务异常终止: {}", e)),
+            reason = hold_lease(db.as_ref(), job_id) => {
+                handle.abort();
+                Err(reason)
+            }
+        }
+    } else {
+        handle.await.map_err(|e| format!("任务异常终止: {}", e))
+    };
+
+    let (status, output, error) = match result {
+        Ok(Ok(output)) => ("success", Some(output), None),
+        Ok(Err(error)) | Err(error) => ("failed", None, Some(error)),
+    };
+    if let Some(error) = &error {
+        tracing::warn!("任务 {} 执行失败: {}", definition.key, error);
+    }
+
+    let finished = finish_run(
+        job_run::ActiveModel {
+            id: Set(run_id),
+            status: Set(status.to_string()),
+            ..Default::default()
+        },
+        started.elapsed().as_millis() as i64,
+        output,
+        error,
+    );
+    if let Err(e) = finished.update(db.as_ref()).await {
+        tracing::warn!("更新任务执行记录失败: {}", e);
+    }
+
+    if leased {
+        release_lease(db.as_ref(), job_id).await;
+    }
+    RUNNING.fetch_sub(1, Ordering::SeqCst);
+}
+
+/// 等待本实例正在执行的任务结束，超时返回 false，未结束的任务租约到期后可由其他实例接管
+pub async fn wait_idle(timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    while RUNNING.load(Ordering::SeqCst) > 0 {
+        if Instant::now() >= deadline {
+            return false;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    true
+}
+
+fn new_run(
+    job_id: Uuid,
+    trigger_type: TriggerType,
+    status: &str,
+    user_id: Option<Uuid>,
+) -> job_run::ActiveModel {
+    job_run::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        job_id: Set(job_id),
+        trigger_type: Set(trigger_type.as_str().to_string()),
+        instance_id: Set(instance_id().to_string()),
+        status: Set(status.to_string()),
+        output: Set(None),
+        error: Set(None),
+        started_time: Set(Utc::now().naive_utc()),
+        finished_time: Set(None),
+        duration_ms: Set(None),
+        created_id: Set(user_id),
+    }
+}
+
+fn finish_run(
+    mut run: job_run::ActiveModel,
+    duration_ms: i64,
+    output: Option<String>,
+    error: Option<String>,
+) -> job_run::ActiveModel {
+    run.finished_time = Set(Some(Utc::now().naive_utc()));
+    run.duration_ms = Set(Some(duration_ms));
+    run.output = Set(output);
+    run.error = Set(error);
+    run
+}
+
+/// 获取执行租约，租约空闲或已过期时成功
+async fn acquire_lease(db: &DatabaseConnection, job_id: Uuid) -> Result<bool, DbErr> {
+    let now = Utc::now().naive_utc();
+    let result = scheduled_job::Entity::update_many()
+        .col_expr(
+            scheduled_job::Column::LeaseOwner,
+            Expr::value(Some(instance_id().to_string())),
+        )
+        .col_expr(
+            scheduled_job::Column::LeaseUntil,
+            Expr::value(Some(now + chrono::Duration::seconds(LEASE_SECONDS))),
+        )
+        .filter(scheduled_job::Column::Id.eq(job_id))
+        .filter(
+            Condition::any()
+                .add(scheduled_job::Column::LeaseUntil.is_null())
+                .add(scheduled_job::Column::LeaseUntil.lt(now)),
+        )
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected == 1)
+}
+
+/// 定期续期租约，直到租约丢失才返回，返回值为丢失原因
+///
+/// 续期失败时继续重试，超过租约时长仍未续期成功则视为丢失。
+async fn hold_lease(db: &DatabaseConnection, job_id: Uuid) -> String {
+    let mut ticker = tokio::time::interval(LEASE_RENEW_INTERVAL);
+    ticker.tick().await;
+    let mut renewed_at = Instant::now();
+    loop {
+        ticker.tick().await;
+        match renew_lease(db, job_id).await {
+            Ok(true) => renewed_at = Instant::now(),
+            Ok(false) => {
+                tracing::warn!("任务租约已被其他实例接管，取消执行: {}", job_id);
+                return "任务租约已被其他实例接管，执行已取消".to_string();
+            }
+            Err(e) => {
+                tracing::warn!("任务租约续期失败: {}", e);
+                if renewed_at.elapsed() >= Duration::from_secs(LEASE_SECONDS as u64) {
+                    return "任务租约续期失败，执行已取消".to_string();
+                }
+            }
+        }
+    }
+}
+
+/// 续期租约，租约已不属于本实例时返回 false
+async fn renew_lease(db: &DatabaseConnection, job_id: Uuid) -> Result<bool, DbErr> {
+    let until = Utc::now().naive_utc() + chrono::Duration::seconds(LEASE_SECONDS);
+    let result = scheduled_job::Entity::update_many()
+        .col_expr(scheduled_job::Column::LeaseUntil, Expr::value(Some(until)))
+        .filter(scheduled_job::Column::Id.eq(job_id))
+        .filter(scheduled_job::Column::LeaseOwner.eq(instance_id()))
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected == 1)
+}
+
+async fn release_lease(db: &DatabaseConnection, job_id: Uuid) {
+    let result = scheduled_job::Entity::update_many()
+        .col_expr(scheduled_job::Column::LeaseOwner, Expr::value(Option::<String>::None))
+        .col_expr(
+            scheduled_job::Column::LeaseUntil,
+            Expr::value(Option::<NaiveDateTime>::None),
+        )
+        .filter(scheduled_job::Column::Id.eq(job_id))
+        .filter(scheduled_job::Column::LeaseOwner.eq(instance_id()))
+        .exec(db)
+        .await;
+    if let Err(e) = result {
+        tracing::warn!("释放任务租约失败: {}", e);
+    }
+}
+
+/// 将任务遗留的 running 记录标记为失败，调用方需确认当前没有实例在执行该任务
+async fn fail_orphaned_runs(db: &DatabaseConnection, job_id: Uuid) -> Result<(), DbErr> {
+    let result = job_run::Entity::update_many()
+        .col_expr(job_run::Column::Status, Expr::value("failed"))
+        .col_expr(
+            job_run::Column::Error,
+            Expr::value(Some("执行实例异常退出，租约已过期".to_string())),
+        )
+        .col_expr(
+            job_run::Column::FinishedTime,
+            Expr::value(Some(Utc::now().naive_utc())),
+        )
+        .filter(job_run::Column::JobId.eq(job_id))
+        .filter(job_run::Column::Status.eq("running"))
+        .exec(db)
+        .await?;
+    if result.rows_affected > 0 {
+        tracing::warn!(
+            "任务 {} 有 {} 条执行记录因实例异常退出未结束，已标记为失败",
+            job_id,
+            result.rows_affected
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, Timelike};
+
+    fn at(h: u32, m: u32, s: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 1, 1)
+            .unwrap()
+            .and_hms_opt(h, m, s)
+            .unwrap()
+    }
+
+    #[test]
+    fn five_field_cron_runs_at_second_zero() {
+        let schedule = parse_cron(" * * * * * ").unwrap();
+        assert_eq!(next_run_time(&schedule, at(10, 0, 30)), Some(at(10, 1, 0)));
+    }
+
+    #[test]
+    fn six_field_cron_keeps_seconds() {
+        let schedule = parse_cron("*/10 * * * * *").unwrap();
+        assert_eq!(next_run_time(&schedule, at(10, 0, 31)), Some(at(10, 0, 40)));
+
+        let schedule = parse_cron("15 * * * * * 2024").unwrap();
+        let next = next_run_time(&schedule, at(10, 0, 0)).unwrap();
+        assert_eq!(next.second(), 15);
+    }
+
+    #[test]
+    fn invalid_cron_is_bad_request() {
+        for expression in ["", "* * *", "61 * * * * *", "not a cron"] {
+            assert!(
+                matches!(parse_cron(expression), Err(AppError::BadRequest(_))),
+                "{}",
+                expression
+            );
+        }
+    }
+
+    #[test]
+    fn concurrency_policy_round_trip() {
+        for policy in [ConcurrencyPolicy::Forbid, ConcurrencyPolicy::Allow] {
+            assert_eq!(ConcurrencyPolicy::from_str(policy.as_str()), Some(policy));
+        }
+        assert_eq!(ConcurrencyPolicy::from_str("replace"), None);
+    }
+
+    fn slow_job(_: JobContext) -> JobFuture {
+        Box::pin(async {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            Ok("done".to_string())
+        })
+    }
+
+    #[tokio::test]
+    async fn wait_idle_waits_for_running_jobs() {
+        let Some(db) = crate::common::testing::TestDb::migrated().await else {
+            return;
+        };
+        register(JobDefinition {
+            key: "test.slow",
+            name: "慢任务",
+            description: "",
+            cron: "0 0 0 * * *",
+            concurrency: ConcurrencyPolicy::Forbid,
+            handler: slow_job,
+        });
+        let job = scheduled_job::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            job_key: Set("test.slow".to_string()),
+            name: Set("慢任务".to_string()),
+            cron_expression: Set("0 0 0 * * *".to_string()),
+            concurrency_policy: Set("forbid".to_string()),
+            ..Default::default()
+        }
+        .insert(&*db)
+        .await
+        .unwrap();
+
+        let run = trigger(db.arc(), &job, TriggerType::Manual, None).await.unwrap();
+        // 停机时先等待执行中的任务写完执行记录，再关闭连接池
+        assert!(!wait_idle(Duration::from_millis(100)).await);
+        assert!(wait_idle(Duration::from_secs(5)).await);
+
+        let run = job_run::Entity::find_by_id(run.id)
+            .one(&*db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(run.status, "success");
+        assert_eq!(run.output.as_deref(), Some("done"));
+        assert!(run.finished_time.is_some());
+    }
+}
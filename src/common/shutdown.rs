@@ -0,0 +1,44 @@
+// 优雅停机模块
+// 收到 SIGTERM / SIGINT 后进入停机状态：就绪检查返回 503，定时任务停止触发，
+// 服务器停止接受新连接并等待进行中的请求完成，超时后强制关闭。
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// 是否已进入停机状态
+pub fn is_shutting_down() -> bool {
+    SHUTTING_DOWN.load(Ordering::Relaxed)
+}
+
+/// 等待停机信号并进入停机状态
+pub async fn wait_for_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("监听 SIGINT 失败: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut stream) => {
+                stream.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("监听 SIGTERM 失败: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => tracing::info!("收到 SIGINT，开始停机"),
+        _ = terminate => tracing::info!("收到 SIGTERM，开始停机"),
+    }
+    SHUTTING_DOWN.store(true, Ordering::Relaxed);
+}
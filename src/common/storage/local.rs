@@ -0,0 +1,82 @@
+// 本地磁盘存储
+
+use salvo::async_trait;
+use std::path::{Component, Path, PathBuf};
+use uuid::Uuid;
+
+use super::StorageBackend;
+use crate::common::AppError;
+
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Result<Self, AppError> {
+        let root = root.into();
+        std::fs::create_dir_all(&root).map_err(|e| {
+            AppError::InternalServerError(format!("创建存储目录 {} 失败: {}", root.display(), e))
+        })?;
+        Ok(Self { root })
+    }
+
+    /// 将存储键映射为根目录下的路径，拒绝绝对路径和 `..`
+    fn resolve(&self, key: &str) -> Result<PathBuf, AppError> {
+        let path = Path::new(key);
+        if !path.components().all(|c| matches!(c, Component::Normal(_))) {
+            return Err(AppError::BadRequest("无效的存储键".to_string()));
+        }
+        Ok(self.root.join(path))
+    }
+}
+
+#[async_trait]
+impl StorageBackend for LocalStorage {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    async fn put(&self, key: &str, data: &[u8], _content_type: &str) -> Result<(), AppError> {
+        let path = self.resolve(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| AppError::InternalServerError(format!("写入文件失败: {}", e)))?;
+        }
+
+        // 先写临时文件再重命名，避免读到写了一半的文件
+        let temp = path.with_extension(format!("{}.tmp", Uuid::new_v4().simple()));
+        tokio::fs::write(&temp, data)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("写入文件失败: {}", e)))?;
+        tokio::fs::rename(&temp, &path)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("写入文件失败: {}", e)))
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, AppError> {
+        let path = self.resolve(key)?;
+        tokio::fs::read(&path).await.map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => AppError::NotFound("文件不存在".to_string()),
+            _ => AppError::InternalServerError(format!("读取文件失败: {}", e)),
+        })
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        let path = self.resolve(key)?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(AppError::InternalServerError(format!("删除文件失败: {}", e))),
+        }
+    }
+
+    async fn presigned_url(
+        &self,
+        _key: &str,
+        _file_name: &str,
+        _expires_secs: u32,
+    ) -> Result<Option<String>, AppError> {
+        Ok(None)
+    }
+}
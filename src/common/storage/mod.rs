@@ -0,0 +1,254 @@
+// 文件存储模块
+// 定义可插拔的存储后端（本地磁盘、S3 兼容对象存储），并负责下载链接的签名与校验
+
+mod local;
+mod s3_storage;
+
+use hmac::{Hmac, Mac};
+use salvo::async_trait;
+use sha2::Sha256;
+use std::sync::{Arc, OnceLock};
+use uuid::Uuid;
+
+use super::config::StorageConfig;
+use super::constants::API_PREFIX;
+use super::AppError;
+use local::LocalStorage;
+use s3_storage::S3Storage;
+
+/// 存储后端
+#[async_trait]
+pub trait StorageBackend: Send + Sync {
+    /// 后端名称，记录在文件元数据中
+    fn name(&self) -> &'static str;
+
+    /// 写入对象
+    async fn put(&self, key: &str, data: &[u8], content_type: &str) -> Result<(), AppError>;
+
+    /// 读取对象
+    async fn get(&self, key: &str) -> Result<Vec<u8>, AppError>;
+
+    /// 删除对象，对象不存在时视为成功
+    async fn delete(&self, key: &str) -> Result<(), AppError>;
+
+    /// 生成直接访问对象的预签名地址，不支持时返回 None（改由应用签名下载）
+    async fn presigned_url(
+        &self,
+        key: &str,
+        file_name: &str,
+        expires_secs: u32,
+    ) -> Result<Option<String>, AppError>;
+}
+
+/// 存储服务：当前后端 + 上传限制 + 下载签名
+pub struct StorageService {
+    backend: Box<dyn StorageBackend>,
+    signing_key: Vec<u8>,
+    pub max_file_size: u64,
+    pub allowed_extensions: Vec<String>,
+    pub url_expire_seconds: u32,
+}
+
+static STORAGE: OnceLock<Arc<StorageService>> = OnceLock::new();
+
+/// 初始化存储服务，配置中的签名密钥用于生成应用自身的下载链接
+pub fn init_storage(config: &StorageConfig) -> Result<(), AppError> {
+    let backend: Box<dyn StorageBackend> = match config.backend.as_str() {
+        "local" => Box::new(LocalStorage::new(&config.local_root)?),
+        "s3" => Box::new(S3Storage::new(&config.s3)?),
+        other => {
+            return Err(AppError::InternalServerError(format!(
+                "不支持的存储后端: {}",
+                other
+            )))
+        }
+    };
+
+    let service = StorageService {
+        backend,
+        signing_key: config.signing_key.as_bytes().to_vec(),
+        max_file_size: config.max_file_size_mb * 1024 * 1024,
+        allowed_extensions: config.allowed_extensions.clone(),
+        url_expire_seconds: config.url_expire_seconds,
+    };
+    STORAGE.get_or_init(|| Arc::new(service));
+    Ok(())
+}
+
+/// 获取存储服务
+pub fn storage() -> Result<Arc<StorageService>, AppError> {
+    STORAGE
+        .get()
+        .cloned()
+        .ok_or_else(|| AppError::InternalServerError("存储服务未初始化".to_string()))
+}
+
+impl StorageService {
+    pub fn backend(&self) -> &dyn StorageBackend {
+        self.backend.as_ref()
+    }
+
+    /// 生成文件下载地址：后端支持预签名时直接访问后端，否则使用应用签名的下载接口
+    pub async fn download_url(
+        &self,
+        file_id: Uuid,
+        storage_key: &str,
+        file_name: &str,
+    ) -> Result<String, AppError> {
+        if let Some(url) = self
+            .backend
+            .presigned_url(storage_key, file_name, self.url_expire_seconds)
+            .await?
+        {
+            return Ok(url);
+        }
+
+        let expires = chrono::Utc::now().timestamp() + i64::from(self.url_expire_seconds);
+        Ok(format!(
+            "/{}/file/download/{}?expires={}&signature={}",
+            API_PREFIX,
+            file_id,
+            expires,
+            self.sign(file_id, expires)
+        ))
+    }
+
+    /// 校验下载签名和有效期
+    pub fn verify(&self, file_id: Uuid, expires: i64, signature: &str) -> bool {
+        if expires < chrono::Utc::now().timestamp() {
+            return false;
+        }
+        let Ok(signature) = hex::decode(signature) else {
+            return false;
+        };
+        self.mac(file_id, expires).verify_slice(&signature).is_ok()
+    }
+
+    fn sign(&self, file_id: Uuid, expires: i64) -> String {
+        hex::encode(self.mac(file_id, expires).finalize().into_bytes())
+    }
+
+    fn mac(&self, file_id: Uuid, expires: i64) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.signing_key)
+            .expect("HMAC 可接受任意长度的密钥");
+        mac.update(format!("{}:{}", file_id, expires).as_bytes());
+        mac
+    }
+}
+
+#[cfg(test)]
+impl StorageService {
+    /// 使用本地目录的存储服务，供其他模块的测试使用
+    pub fn local(root: &std::path::Path) -> Self {
+        Self {
+            backend: Box::new(LocalStorage::new(root).unwrap()),
+            signing_key: b"signing-key".to_vec(),
+            max_file_size: 1024 * 1024,
+            allowed_extensions: vec!["txt".to_string()],
+            url_expire_seconds: 60,
+        }
+    }
+}
+
+/// 生成 Content-Disposition 头，文件名按 RFC 5987 编码以支持中文
+pub fn content_disposition(file_name: &str) -> String {
+    let encoded: String = file_name
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect();
+    format!("attachment; filename*=UTF-8''{}", encoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 不支持预签名的后端，下载地址由应用签名
+    struct NoopBackend;
+
+    #[async_trait]
+    impl StorageBackend for NoopBackend {
+        fn name(&self) -> &'static str {
+            "noop"
+        }
+
+        async fn put(&self, _key: &str, _data: &[u8], _content_type: &str) -> Result<(), AppError> {
+            Ok(())
+        }
+
+        async fn get(&self, _key: &str) -> Result<Vec<u8>, AppError> {
+            Ok(Vec::new())
+        }
+
+        async fn delete(&self, _key: &str) -> Result<(), AppError> {
+            Ok(())
+        }
+
+        async fn presigned_url(
+            &self,
+            _key: &str,
+            _file_name: &str,
+            _expires_secs: u32,
+        ) -> Result<Option<String>, AppError> {
+            Ok(None)
+        }
+    }
+
+    fn service(signing_key: &str) -> StorageService {
+        StorageService {
+            backend: Box::new(NoopBackend),
+            signing_key: signing_key.as_bytes().to_vec(),
+            max_file_size: 1024,
+            allowed_extensions: vec!["txt".to_string()],
+            url_expire_seconds: 60,
+        }
+    }
+
+    #[test]
+    fn signature_round_trip() {
+        let service = service("signing-key");
+        let file_id = Uuid::new_v4();
+        let expires = chrono::Utc::now().timestamp() + 60;
+        let signature = service.sign(file_id, expires);
+
+        assert!(service.verify(file_id, expires, &signature));
+        assert!(!service.verify(Uuid::new_v4(), expires, &signature));
+        assert!(!service.verify(file_id, expires + 1, &signature));
+        assert!(!service.verify(file_id, expires, "not-hex"));
+        assert!(!service.verify(file_id, expires, ""));
+        assert!(!self::service("other-key").verify(file_id, expires, &signature));
+    }
+
+    #[test]
+    fn expired_signature_is_rejected() {
+        let service = service("signing-key");
+        let file_id = Uuid::new_v4();
+        let expires = chrono::Utc::now().timestamp() - 1;
+        assert!(!service.verify(file_id, expires, &service.sign(file_id, expires)));
+    }
+
+    #[tokio::test]
+    async fn download_url_is_signed_by_app() {
+        let service = service("signing-key");
+        let file_id = Uuid::new_v4();
+        let url = service.download_url(file_id, "key", "a.txt").await.unwrap();
+
+        let prefix = format!("/{}/file/download/{}?expires=", API_PREFIX, file_id);
+        let query = url.strip_prefix(&prefix).expect(&url);
+        let (expires, signature) = query.split_once("&signature=").unwrap();
+        assert!(service.verify(file_id, expires.parse().unwrap(), signature));
+    }
+
+    #[test]
+    fn content_disposition_encodes_file_name() {
+        assert_eq!(
+            content_disposition("报告 v1.pdf"),
+            "attachment; filename*=UTF-8''%E6%8A%A5%E5%91%8A%20v1.pdf"
+        );
+    }
+}
@@ -0,0 +1,105 @@
+// S3 兼容对象存储（AWS S3、MinIO 等）
+
+use s3::creds::Credentials;
+use s3::{Bucket, Region};
+use salvo::async_trait;
+use std::collections::HashMap;
+
+use super::{content_disposition, StorageBackend};
+use crate::common::config::S3Config;
+use crate::common::AppError;
+
+pub struct S3Storage {
+    bucket: Box<Bucket>,
+}
+
+impl S3Storage {
+    pub fn new(config: &S3Config) -> Result<Self, AppError> {
+        let region = Region::Custom {
+            region: config.region.clone(),
+            endpoint: config.endpoint.clone(),
+        };
+        let credentials = Credentials::new(
+            Some(&config.access_key),
+            Some(&config.secret_key),
+            None,
+            None,
+            None,
+        )
+        .map_err(|e| AppError::InternalServerError(format!("S3 凭证无效: {}", e)))?;
+
+        let bucket = Bucket::new(&config.bucket, region, credentials)
+            .map_err(|e| AppError::InternalServerError(format!("S3 配置无效: {}", e)))?;
+        let bucket = if config.path_style {
+            bucket.with_path_style()
+        } else {
+            bucket
+        };
+        Ok(Self { bucket })
+    }
+}
+
+fn s3_error(action: &str, e: impl std::fmt::Display) -> AppError {
+    AppError::InternalServerError(format!("S3 {}失败: {}", action, e))
+}
+
+#[async_trait]
+impl StorageBackend for S3Storage {
+    fn name(&self) -> &'static str {
+        "s3"
+    }
+
+    async fn put(&self, key: &str, data: &[u8], content_type: &str) -> Result<(), AppError> {
+        let response = self
+            .bucket
+            .put_object_with_content_type(key, data, content_type)
+            .await
+            .map_err(|e| s3_error("上传", e))?;
+        match response.status_code() {
+            200..=299 => Ok(()),
+            code => Err(s3_error("上传", format!("状态码 {}", code))),
+        }
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, AppError> {
+        let response = self
+            .bucket
+            .get_object(key)
+            .await
+            .map_err(|e| s3_error("下载", e))?;
+        match response.status_code() {
+            200..=299 => Ok(response.to_vec()),
+            404 => Err(AppError::NotFound("文件不存在".to_string())),
+            code => Err(s3_error("下载", format!("状态码 {}", code))),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        let response = self
+            .bucket
+            .delete_object(key)
+            .await
+            .map_err(|e| s3_error("删除", e))?;
+        match response.status_code() {
+            200..=299 | 404 => Ok(()),
+            code => Err(s3_error("删除", format!("状态码 {}", code))),
+        }
+    }
+
+    async fn presigned_url(
+        &self,
+        key: &str,
+        file_name: &str,
+        expires_secs: u32,
+    ) -> Result<Option<String>, AppError> {
+        let queries = HashMap::from([(
+            "response-content-disposition".to_string(),
+            content_disposition(file_name),
+        )]);
+        self.bucket
+            .presign_get(key, expires_secs, Some(queries))
+            .await
+            .map(Some)
+            .map_err(|e| s3_error("签名", e))
+    }
+}
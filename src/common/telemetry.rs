@@ -0,0 +1,239 @@
+// 链路追踪模块
+// 为每个请求分配请求ID（沿用客户端传入的 X-Request-Id 或新生成），记录到请求 span、
+// 日志、响应头和错误响应中，便于串联同一请求的日志。
+// 配置了 OTLP 地址时，请求 span 和 SQL 执行 span 通过 OTLP/HTTP 导出，并沿用上游的 traceparent。
+
+use opentelemetry::propagation::Extractor;
+use opentelemetry::trace::{
+    Span as _, SpanKind, Status, TraceContextExt as _, Tracer as _, TracerProvider as _,
+};
+use opentelemetry::{global, KeyValue};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{Sampler, SdkTracerProvider};
+use opentelemetry_sdk::Resource;
+use salvo::http::header::{HeaderMap, HeaderValue};
+use salvo::prelude::*;
+use sea_orm::metric;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::SystemTime;
+use tracing::field::Empty;
+use tracing::Instrument;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::EnvFilter;
+
+use super::config::TelemetryConfig;
+use super::metrics;
+
+/// 请求ID的请求头和响应头
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+/// 接受的客户端请求ID最大长度
+const MAX_REQUEST_ID_LEN: usize = 128;
+/// 导出的 SQL 语句最大长度
+const MAX_STATEMENT_LEN: usize = 2048;
+
+/// 是否已启用 OTLP 导出，未启用时不创建 SQL span，也不解析 traceparent
+static EXPORT_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// 链路追踪句柄，停机时调用 shutdown 导出剩余的 span
+pub struct Telemetry {
+    provider: Option<SdkTracerProvider>,
+}
+
+impl Telemetry {
+    pub async fn shutdown(self) {
+        let Some(provider) = self.provider else {
+            return;
+        };
+        // 导出器使用阻塞 HTTP 客户端，不能在异步线程上等待
+        match tokio::task::spawn_blocking(move || provider.shutdown()).await {
+            Ok(Ok(())) => tracing::info!("✅ 链路数据已导出"),
+            Ok(Err(e)) => tracing::warn!("⚠️  导出剩余链路数据失败: {}", e),
+            Err(e) => tracing::warn!("⚠️  导出剩余链路数据失败: {}", e),
+        }
+    }
+}
+
+/// 初始化日志，传入的配置设置了 OTLP 地址时同时导出链路数据
+///
+/// 导出器创建失败时只输出日志，不影响服务启动。
+pub fn init(config: Option<&TelemetryConfig>) -> Telemetry {
+    let filter = EnvFilter::from_default_env().add_directive(tracing::Level::INFO.into());
+    let provider = config.and_then(|c| c.otlp_endpoint.as_deref().map(|endpoint| (c, endpoint)));
+    let provider = match provider.map(|(config, endpoint)| build_provider(config, endpoint)) {
+        Some(Ok(provider)) => Some(provider),
+        Some(Err(e)) => {
+            eprintln!("⚠️  OTLP 导出器创建失败，仅输出日志: {}", e);
+            None
+        }
+        None => None,
+    };
+    let otel_layer = provider.as_ref().map(|provider| {
+        tracing_opentelemetry::layer().with_tracer(provider.tracer("maple-admin"))
+    });
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer())
+        .with(otel_layer)
+        .init();
+
+    if let (Some(provider), Some(config)) = (&provider, config) {
+        global::set_tracer_provider(provider.clone());
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        EXPORT_ENABLED.store(true, Ordering::Relaxed);
+        tracing::info!(
+            "✅ 链路追踪已启用，导出地址: {}",
+            traces_endpoint(config.otlp_endpoint.as_deref().unwrap_or_default())
+        );
+    }
+
+    Telemetry { provider }
+}
+
+/// 分配请求ID并创建请求 span，挂在 Service 上，未匹配路由的请求同样带有请求ID
+#[handler]
+pub async fn trace_requests(
+    req: &mut Request,
+    depot: &mut Depot,
+    res: &mut Response,
+    ctrl: &mut FlowCtrl,
+) {
+    let request_id = req
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| is_valid_request_id(value))
+        .map(String::from)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    // 路由在执行 Service 中间件前已匹配，span 创建后名称不能再修改
+    let route = metrics::route_template(req);
+    let span = tracing::info_span!(
+        "request",
+        request_id = %request_id,
+        method = %req.method(),
+        route = %route,
+        status = Empty,
+        otel.name = %format!("{} {}", req.method(), route),
+        otel.kind = "server",
+        otel.status_code = Empty,
+    );
+    if EXPORT_ENABLED.load(Ordering::Relaxed) {
+        let parent = global::get_text_map_propagator(|propagator| {
+            propagator.extract(&HeaderExtractor(req.headers()))
+        });
+        let _ = span.set_parent(parent);
+    }
+
+    depot.insert("request_id", request_id.clone());
+    ctrl.call_next(req, depot, res).instrument(span.clone()).await;
+
+    let status = res.status_code.unwrap_or(StatusCode::OK);
+    span.record("status", status.as_u16());
+    if status.is_server_error() {
+        span.record("otel.status_code", "error");
+    }
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        res.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+}
+
+/// 当前请求的请求ID
+pub fn current_request_id(depot: &Depot) -> Option<String> {
+    depot.get::<String>("request_id").ok().cloned()
+}
+
+/// 为 SQL 执行创建 span，作为 SeaORM 的 metric 回调，父 span 为当前请求 span
+///
+/// 定时任务轮询等请求之外的 SQL 不创建 span，避免产生大量孤立的链路。
+pub fn record_query(info: &metric::Info<'_>) {
+    if !EXPORT_ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let parent = tracing::Span::current().context();
+    if !parent.has_active_span() {
+        return;
+    }
+
+    let end = SystemTime::now();
+    let operation = metrics::query_operation(&info.statement.sql);
+    let tracer = global::tracer("sea-orm");
+    let mut span = tracer
+        .span_builder(operation)
+        .with_kind(SpanKind::Client)
+        .with_start_time(end - info.elapsed)
+        .with_attributes([
+            KeyValue::new("db.system.name", "postgresql"),
+            KeyValue::new("db.operation.name", operation),
+            KeyValue::new("db.query.text", truncate(&info.statement.sql, MAX_STATEMENT_LEN)),
+        ])
+        .start_with_context(&tracer, &parent);
+    if info.failed {
+        span.set_status(Status::error("SQL 执行失败"));
+    }
+    span.end_with_timestamp(end);
+}
+
+// ========== 辅助函数 ==========
+
+fn build_provider(
+    config: &TelemetryConfig,
+    endpoint: &str,
+) -> Result<SdkTracerProvider, opentelemetry_otlp::ExporterBuildError> {
+    use opentelemetry_otlp::WithExportConfig;
+
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .with_endpoint(traces_endpoint(endpoint))
+        .build()?;
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+            config.sample_ratio,
+        ))))
+        .with_resource(
+            Resource::builder()
+                .with_service_name(config.service_name.clone())
+                .build(),
+        )
+        .build())
+}
+
+/// 配置的是 Collector 的基础地址，与 OTEL_EXPORTER_OTLP_ENDPOINT 一致，需补上 /v1/traces
+fn traces_endpoint(endpoint: &str) -> String {
+    let endpoint = endpoint.trim_end_matches('/');
+    if endpoint.ends_with("/v1/traces") {
+        endpoint.to_string()
+    } else {
+        format!("{}/v1/traces", endpoint)
+    }
+}
+
+/// 客户端传入的请求ID只接受不含空白的可见 ASCII 字符，避免污染日志
+fn is_valid_request_id(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= MAX_REQUEST_ID_LEN
+        && value.bytes().all(|b| b.is_ascii_graphic())
+}
+
+fn truncate(text: &str, max_len: usize) -> String {
+    match text.char_indices().nth(max_len) {
+        Some((index, _)) => format!("{}...", &text[..index]),
+        None => text.to_string(),
+    }
+}
+
+/// 从请求头读取 traceparent 等传播字段
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|key| key.as_str()).collect()
+    }
+}
@@ -0,0 +1,29 @@
+// 通用字段校验
+
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// 用户名：字母开头，3-50 位字母、数字、下划线、点或连字符
+pub fn is_valid_username(username: &str) -> bool {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN
+        .get_or_init(|| Regex::new(r"^[A-Za-z][A-Za-z0-9_.\-]{2,49}$").unwrap())
+        .is_match(username)
+}
+
+/// 邮箱：只做基本格式校验，长度不超过 100
+pub fn is_valid_email(email: &str) -> bool {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    email.len() <= 100
+        && PATTERN
+            .get_or_init(|| Regex::new(r"^[^@\s]+@[^@\s]+\.[^@\s]+$").unwrap())
+            .is_match(email)
+}
+
+/// 手机号：可带 + 前缀的数字和连字符，5-20 位
+pub fn is_valid_phone(phone: &str) -> bool {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN
+        .get_or_init(|| Regex::new(r"^\+?[0-9][0-9-]{4,19}$").unwrap())
+        .is_match(phone)
+}
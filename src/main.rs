@@ -1,30 +1,47 @@
+mod cli;
 mod common;
 mod models;
 mod modules;
 mod routes;
 
 use std::sync::Arc;
+use std::time::Duration;
 use salvo::prelude::*;
 use salvo::cors::Cors;
 use salvo::http::Method;
 use salvo::logging::Logger;
 use salvo::compression::Compression;
 use salvo::oapi::swagger_ui::SwaggerUi;
-use tracing_subscriber;
+use clap::Parser;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    // 初始化日志
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive(tracing::Level::INFO.into()),
-        )
-        .init();
-
-    // 加载配置
-    let config = common::AppConfig::from_env();
-    tracing::info!("配置加载成功");
+    match cli::Cli::parse().command {
+        None | Some(cli::Command::Serve) => serve().await,
+        Some(command) => {
+            // 初始化日志，命令行工具不导出链路数据
+            common::telemetry::init(None);
+            cli::run(command).await
+        }
+    }
+}
+
+/// 启动 HTTP 服务
+async fn serve() -> anyhow::Result<()> {
+    // 记录启动时间，供系统监控计算运行时长
+    modules::monitor::init();
+
+    // 加载配置，再按配置初始化日志和链路追踪
+    let config = match common::AppConfig::load() {
+        Ok(config) => config,
+        Err(e) => {
+            common::telemetry::init(None);
+            tracing::error!("❌ {}", e);
+            return Err(e.into());
+        }
+    };
+    let telemetry = common::telemetry::init(Some(&config.telemetry));
+    tracing::info!("配置加载成功（profile: {}）: {:?}", config.profile, config);
 
     // 确保 PostgreSQL 服务已启动
     if let Err(e) = common::postgres_service::ensure_postgres_running().await {
@@ -33,12 +50,15 @@ async fn main() -> anyhow::Result<()> {
     }
 
     // 初始化数据库
-    let db = common::database::init_db().await;
-    
-    if db.is_some() {
+    let db = common::database::init_db(&config.database).await.map(Arc::new);
+
+    // 执行数据库迁移，失败时不启动服务，避免在结构不一致的数据库上运行
+    if let Some(db) = &db {
         tracing::info!("✅ 数据库初始化成功");
-    } else {
-        tracing::warn!("⚠️  数据库未连接，应用将在无数据库模式下运行");
+        if let Err(e) = run_migrations(db, &config).await {
+            tracing::error!("❌ 数据库迁移失败: {}", e);
+            return Err(e.into());
+        }
     }
 
     // 初始化 RSA 密钥管理器
@@ -48,10 +68,42 @@ async fn main() -> anyhow::Result<()> {
     }
     tracing::info!("✅ RSA 密钥管理器初始化成功");
 
+    // 初始化文件存储
+    if let Err(e) = common::storage::init_storage(&config.storage) {
+        tracing::error!("❌ 文件存储初始化失败: {}", e);
+        return Err(e.into());
+    }
+    tracing::info!("✅ 文件存储初始化成功: {}", config.storage.backend);
+
+    // 数据库已连接时启动定时任务调度；未连接时在后台重连，连接成功后完成迁移和调度，
+    // 处理器通过 DepsMiddleware 获得新连接，无需重启服务
+    let shared_db = common::database::SharedDb::new(db.clone());
+    match db {
+        Some(db) => {
+            modules::system::service::spawn_change_listener(db.clone());
+            start_scheduler(db, &config).await;
+        }
+        None => {
+            tracing::warn!("⚠️  数据库未连接，应用暂以无数据库模式运行，后台将持续重连");
+            let config = config.clone();
+            let shared_db = shared_db.clone();
+            tokio::spawn(async move {
+                let db = Arc::new(common::database::connect_with_retry(&config.database).await);
+                if let Err(e) = run_migrations(&db, &config).await {
+                    tracing::error!("❌ 数据库迁移失败，数据库保持不可用，修复后请重启服务: {}", e);
+                    return;
+                }
+                shared_db.set(db.clone());
+                modules::system::service::spawn_change_listener(db.clone());
+                start_scheduler(db, &config).await;
+            });
+        }
+    }
+
     // 创建 JWT 服务
     let jwt_service = Arc::new(common::jwt::JwtService::new(
-        config.jwt.secret.clone(),
-        config.jwt.expiration_hours,
+        &config.jwt,
+        &config.cookie,
     ));
 
     // 配置 CORS  
@@ -65,7 +117,16 @@ async fn main() -> anyhow::Result<()> {
             Method::OPTIONS,
             Method::PATCH,
         ])
-        .allow_headers(vec!["Content-Type", "Authorization", "Accept", "X-Requested-With"])
+        .allow_headers(vec![
+            "Content-Type",
+            "Authorization",
+            "Accept",
+            "X-Requested-With",
+            "X-Request-Id",
+            "traceparent",
+            "tracestate",
+        ])
+        .expose_headers(vec!["X-Request-Id"])
         .allow_credentials(true);
 
     // 创建 OpenAPI 文档
@@ -74,10 +135,12 @@ async fn main() -> anyhow::Result<()> {
     // 创建路由
     let router = Router::new()
         .hoop(Logger::new())
+        .hoop(common::metrics::track_requests)
         .hoop(cors.into_handler())
         .hoop(Compression::new())
-        .hoop(common::middleware::DepsMiddleware::new(db.map(Arc::new), jwt_service))
+        .hoop(common::middleware::DepsMiddleware::new(shared_db.clone(), jwt_service))
         .push(routes::create_router())
+        .push(Router::with_path("metrics").get(common::metrics::export))
         .push(doc.into_router("/api-doc/openapi.json"))
         .push(SwaggerUi::new("/api-doc/openapi.json").into_router("/swagger"));
 
@@ -88,8 +151,16 @@ async fn main() -> anyhow::Result<()> {
     
     let server = Server::new(acceptor);
 
+    // 收到停机信号后停止接受新连接，等待进行中的请求完成，超时后强制关闭
+    let shutdown_timeout = Duration::from_secs(config.server.shutdown_timeout_secs);
+    let handle = server.handle();
+    tokio::spawn(async move {
+        common::shutdown::wait_for_signal().await;
+        // 推送连接是长连接，主动关闭，避免一直占用到超时
+        common::push::close_all();
+        handle.stop_graceful(shutdown_timeout);
+    });
 
-    
     tracing::info!(
         "🚀 服务器启动成功，监听地址: http://{}:{}",
         config.server.host,
@@ -102,11 +173,49 @@ async fn main() -> anyhow::Result<()> {
         config.server.port,
     );
 
-    // 创建 Service
-    let service = Service::new(router);
+    // 创建 Service，请求ID挂在 Service 上，未匹配路由的请求也能在日志中关联
+    let service = Service::new(router).hoop(common::telemetry::trace_requests);
 
     // 启动服务器
     server.serve(service).await;
+    tracing::info!("HTTP 服务已停止");
+
+    // 等待执行中的定时任务，再关闭数据库连接池
+    if !common::scheduler::wait_idle(shutdown_timeout).await {
+        tracing::warn!("⚠️  等待定时任务结束超时，未完成的任务租约到期后由其他实例接管");
+    }
+    if let Some(db) = shared_db.get() {
+        match db.close_by_ref().await {
+            Ok(()) => tracing::info!("✅ 数据库连接池已关闭"),
+            Err(e) => tracing::warn!("⚠️  关闭数据库连接池失败: {}", e),
+        }
+    }
+    telemetry.shutdown().await;
+    tracing::info!("👋 服务已停止");
 
     Ok(())
 }
+
+/// 按配置执行数据库迁移
+async fn run_migrations(
+    db: &sea_orm::DatabaseConnection,
+    config: &common::AppConfig,
+) -> Result<(), sea_orm::DbErr> {
+    if !config.database.auto_migrate {
+        tracing::info!("已禁用自动迁移，可执行 migrate 子命令手动迁移");
+        return Ok(());
+    }
+    let executed = common::migration::migrate_up(db).await?;
+    tracing::info!("✅ 数据库迁移完成，本次执行 {} 个", executed.len());
+    Ok(())
+}
+
+/// 按配置启动定时任务调度
+async fn start_scheduler(db: Arc<sea_orm::DatabaseConnection>, config: &common::AppConfig) {
+    if config.scheduler.enabled {
+        modules::job::register_builtin_jobs();
+        common::scheduler::start(db).await;
+    } else {
+        tracing::info!("定时任务调度已禁用");
+    }
+}
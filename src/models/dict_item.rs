@@ -0,0 +1,42 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "dict_items")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    pub dict_type_id: Uuid,
+    pub label: String,
+    pub value: String,
+    pub sort: i32,
+    pub color: Option<String>,
+    pub tag_type: Option<String>,
+    pub is_default: bool,
+    pub status: i16,
+    pub remark: Option<String>,
+    pub created_time: DateTime,
+    pub created_id: Option<Uuid>,
+    pub updated_time: DateTime,
+    pub updated_id: Option<Uuid>,
+    pub deleted_time: Option<DateTime>,
+    pub deleted_id: Option<Uuid>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::dict_type::Entity",
+        from = "Column::DictTypeId",
+        to = "super::dict_type::Column::Id"
+    )]
+    DictType,
+}
+
+impl Related<super::dict_type::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DictType.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,34 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "dict_types")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    #[sea_orm(unique)]
+    pub code: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub status: i16,
+    pub created_time: DateTime,
+    pub created_id: Option<Uuid>,
+    pub updated_time: DateTime,
+    pub updated_id: Option<Uuid>,
+    pub deleted_time: Option<DateTime>,
+    pub deleted_id: Option<Uuid>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::dict_item::Entity")]
+    DictItems,
+}
+
+impl Related<super::dict_item::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DictItems.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,27 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "files")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    pub file_name: String,
+    pub storage_key: String,
+    pub backend: String,
+    pub content_type: String,
+    pub file_size: i64,
+    pub sha256: String,
+    pub biz_type: String,
+    pub created_time: DateTime,
+    pub created_id: Option<Uuid>,
+    pub updated_time: DateTime,
+    pub updated_id: Option<Uuid>,
+    pub deleted_time: Option<DateTime>,
+    pub deleted_id: Option<Uuid>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
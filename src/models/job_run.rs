@@ -0,0 +1,39 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "job_runs")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    pub job_id: Uuid,
+    pub trigger_type: String,
+    pub instance_id: String,
+    pub status: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub output: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub error: Option<String>,
+    pub started_time: DateTime,
+    pub finished_time: Option<DateTime>,
+    pub duration_ms: Option<i64>,
+    pub created_id: Option<Uuid>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::scheduled_job::Entity",
+        from = "Column::JobId",
+        to = "super::scheduled_job::Column::Id"
+    )]
+    ScheduledJob,
+}
+
+impl Related<super::scheduled_job::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ScheduledJob.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -8,6 +8,8 @@ pub struct Model {
     pub id: Uuid,
     pub parent_id: Option<Uuid>,
     pub name: String,
+    /// 其它语言的菜单名称，如 {"en-US": "Home"}
+    pub name_i18n: Option<Json>,
     pub menu_type: String,
     pub path: Option<String>,
     pub component: Option<String>,
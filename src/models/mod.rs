@@ -3,3 +3,12 @@ pub mod role;
 pub mod user_role;
 pub mod menu;
 pub mod role_menu;
+pub mod system_param;
+pub mod dict_type;
+pub mod dict_item;
+pub mod notice;
+pub mod notice_role;
+pub mod notice_read;
+pub mod scheduled_job;
+pub mod job_run;
+pub mod file;
@@ -0,0 +1,46 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "notices")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    pub title: String,
+    #[sea_orm(column_type = "Text")]
+    pub content: String,
+    pub status: i16,
+    pub is_pinned: bool,
+    pub start_time: Option<DateTime>,
+    pub end_time: Option<DateTime>,
+    pub published_time: Option<DateTime>,
+    pub published_id: Option<Uuid>,
+    pub created_time: DateTime,
+    pub created_id: Option<Uuid>,
+    pub updated_time: DateTime,
+    pub updated_id: Option<Uuid>,
+    pub deleted_time: Option<DateTime>,
+    pub deleted_id: Option<Uuid>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::notice_role::Entity")]
+    NoticeRoles,
+    #[sea_orm(has_many = "super::notice_read::Entity")]
+    NoticeReads,
+}
+
+impl Related<super::notice_role::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::NoticeRoles.def()
+    }
+}
+
+impl Related<super::notice_read::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::NoticeReads.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
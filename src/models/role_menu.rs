@@ -10,6 +10,8 @@ pub struct Model {
     pub menu_id: Uuid,
     pub created_time: DateTime,
     pub created_id: Option<Uuid>,
+    pub deleted_time: Option<DateTime>,
+    pub deleted_id: Option<Uuid>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
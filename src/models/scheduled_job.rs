@@ -0,0 +1,38 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "scheduled_jobs")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: Uuid,
+    #[sea_orm(unique)]
+    pub job_key: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub cron_expression: String,
+    pub enabled: bool,
+    pub concurrency_policy: String,
+    pub next_run_time: Option<DateTime>,
+    pub last_run_time: Option<DateTime>,
+    pub lease_owner: Option<String>,
+    pub lease_until: Option<DateTime>,
+    pub created_time: DateTime,
+    pub created_id: Option<Uuid>,
+    pub updated_time: DateTime,
+    pub updated_id: Option<Uuid>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::job_run::Entity")]
+    JobRuns,
+}
+
+impl Related<super::job_run::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::JobRuns.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -13,6 +13,9 @@ pub struct Model {
     pub email: Option<String>,
     pub phone: Option<String>,
     pub avatar: Option<String>,
+    pub avatar_thumb: Option<String>,
+    /// 性别：0-未知，1-男，2-女（字典 sys_user_gender）
+    pub gender: i16,
     pub status: i16,
     pub created_time: DateTime,
     pub created_id: Option<Uuid>,
@@ -20,6 +23,8 @@ pub struct Model {
     pub updated_id: Option<Uuid>,
     pub deleted_time: Option<DateTime>,
     pub deleted_id: Option<Uuid>,
+    /// 令牌吊销时间（精确到秒），签发时间早于该时间的令牌失效
+    pub tokens_revoked_time: Option<DateTime>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -134,6 +134,10 @@ pub struct UserInfoResponse {
     pub phone: Option<String>,
     /// 头像
     pub avatar: Option<String>,
+    /// 头像缩略图
+    pub avatar_thumb: Option<String>,
+    /// 性别：0-未知，1-男，2-女
+    pub gender: i16,
     /// 状态：1-正常，0-禁用
     pub status: i16,
     /// 用户拥有的所有角色
@@ -143,3 +147,46 @@ pub struct UserInfoResponse {
     /// 当前角色代码
     pub current_role_code: String,
 }
+
+/// 更新个人资料请求，未传的字段保持不变，email/phone 传空字符串表示清空
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[salvo(schema(example = json!({
+    "realName": "张三",
+    "email": "zhangsan@example.com",
+    "phone": "13800138000",
+    "gender": 1
+})))]
+pub struct UpdateProfileRequest {
+    /// 真实姓名
+    pub real_name: Option<String>,
+    /// 邮箱
+    pub email: Option<String>,
+    /// 手机号
+    pub phone: Option<String>,
+    /// 性别：0-未知，1-男，2-女
+    pub gender: Option<i16>,
+}
+
+/// 修改密码请求
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangePasswordRequest {
+    /// 原密码（RSA加密后的Base64字符串，与登录相同）
+    pub old_password: String,
+    /// 新密码（RSA加密后的Base64字符串）
+    pub new_password: String,
+    /// 密码是否已加密，默认为 true
+    #[serde(default = "default_is_encrypted")]
+    pub is_encrypted: bool,
+}
+
+/// 上传头像响应
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AvatarResponse {
+    /// 头像地址
+    pub avatar: String,
+    /// 缩略图地址
+    pub avatar_thumb: String,
+}
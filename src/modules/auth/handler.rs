@@ -1,17 +1,36 @@
-use salvo::http::cookie::{Cookie, SameSite};
+use chrono::Utc;
+use image::imageops::FilterType;
+use image::{ImageFormat, ImageReader, Limits};
 use salvo::oapi::extract::JsonBody;
 use salvo::prelude::*;
-use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
+use std::io::Cursor;
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::common::{crypto, jwt::JwtService, rsa_crypto, ApiResponse, AppError};
+use crate::common::constants::{PASSWORD_MAX_LENGTH, PASSWORD_MIN_LENGTH};
+use crate::common::{
+    crypto, i18n,
+    jwt::{JwtService, REFRESH_TOKEN_COOKIE},
+    metrics,
+    middleware::{is_token_revoked, tokens_revoked_now},
+    rsa_crypto, storage, validation, ApiResponse, AppError,
+};
 use crate::models::{role, user, user_role};
+use crate::modules::file::{self, NewFile};
 use super::dto::{
-    LoginRequest, LoginResponse, RefreshTokenRequest, RefreshTokenResponse, RegisterRequest,
-    SwitchRoleRequest, SwitchRoleResponse, UserInfoResponse, UserRole,
+    AvatarResponse, ChangePasswordRequest, LoginRequest, LoginResponse, RefreshTokenRequest,
+    RefreshTokenResponse, RegisterRequest, SwitchRoleRequest, SwitchRoleResponse,
+    UpdateProfileRequest, UserInfoResponse, UserRole,
 };
 
+/// 头像边长（像素）
+const AVATAR_SIZE: u32 = 256;
+/// 头像缩略图边长（像素）
+const AVATAR_THUMB_SIZE: u32 = 64;
+/// 上传头像原图允许的最大边长，防止超大图片耗尽内存
+const AVATAR_MAX_SOURCE_DIMENSION: u32 = 8192;
+
 /// 用户登录
 #[endpoint(
     tags("认证"),
@@ -26,8 +45,16 @@ pub async fn login(
     depot: &Depot,
     res: &mut Response,
 ) -> Result<Json<ApiResponse<LoginResponse>>, AppError> {
-    let login_data = req.into_inner();
+    let result = try_login(req.into_inner(), depot, res).await;
+    metrics::record_login(result.is_ok());
+    result
+}
 
+async fn try_login(
+    login_data: LoginRequest,
+    depot: &Depot,
+    res: &mut Response,
+) -> Result<Json<ApiResponse<LoginResponse>>, AppError> {
     let db = match depot.get::<Arc<DatabaseConnection>>("db") {
         Ok(db) => db,
         Err(_) => {
@@ -140,11 +167,7 @@ pub async fn login(
         selected_role.code.clone(),
     )?;
 
-    let mut cookie = Cookie::new("refresh_token", refresh_token_value.clone());
-    cookie.set_path("/");
-    cookie.set_http_only(true);
-    cookie.set_same_site(SameSite::Lax);
-    res.add_cookie(cookie);
+    res.add_cookie(jwt_service.refresh_token_cookie(refresh_token_value.clone()));
 
     let response = LoginResponse {
         id: user.id.to_string(),
@@ -178,11 +201,10 @@ pub async fn register(
 
 /// 用户登出
 #[endpoint(tags("认证"))]
-pub async fn logout(res: &mut Response) -> Json<ApiResponse<serde_json::Value>> {
-    let mut cookie = Cookie::new("refresh_token", "");
-    cookie.set_path("/");
-    cookie.set_http_only(true);
-    res.add_cookie(cookie);
+pub async fn logout(depot: &Depot, res: &mut Response) -> Json<ApiResponse<serde_json::Value>> {
+    if let Ok(jwt_service) = depot.get::<Arc<JwtService>>("jwt_service") {
+        res.add_cookie(jwt_service.removal_cookie());
+    }
 
     Json(ApiResponse::success_with_message(
         serde_json::json!({}),
@@ -198,18 +220,33 @@ pub async fn refresh_token(
     req_raw: &Request,
     res: &mut Response,
 ) -> Result<Json<ApiResponse<RefreshTokenResponse>>, AppError> {
-    let req_data = req.into_inner();
+    let result = try_refresh_token(req.into_inner(), depot, req_raw, res).await;
+    metrics::record_token_refresh(result.is_ok());
+    result
+}
+
+async fn try_refresh_token(
+    req_data: RefreshTokenRequest,
+    depot: &Depot,
+    req_raw: &Request,
+    res: &mut Response,
+) -> Result<Json<ApiResponse<RefreshTokenResponse>>, AppError> {
     let jwt_service = depot.get::<Arc<JwtService>>("jwt_service").unwrap();
 
     let refresh_token_value = req_data
         .refresh_token
-        .or_else(|| req_raw.cookie("refresh_token").map(|c| c.value().to_string()))
+        .or_else(|| req_raw.cookie(REFRESH_TOKEN_COOKIE).map(|c| c.value().to_string()))
         .ok_or(AppError::Unauthorized)?;
 
     let claims = jwt_service.validate_token(&refresh_token_value)?;
     if claims.token_type != "refresh" {
         return Err(AppError::Unauthorized);
     }
+    if let Ok(db) = depot.get::<Arc<DatabaseConnection>>("db") {
+        if is_token_revoked(db, &claims).await {
+            return Err(AppError::Unauthorized);
+        }
+    }
 
     let user_id = Uuid::parse_str(&claims.sub).map_err(|_| AppError::Unauthorized)?;
     let role_id = Uuid::parse_str(&claims.role_id).map_err(|_| AppError::Unauthorized)?;
@@ -219,11 +256,7 @@ pub async fn refresh_token(
     let new_refresh_token =
         jwt_service.generate_refresh_token(user_id, role_id, claims.role_code)?;
 
-    let mut cookie = Cookie::new("refresh_token", new_refresh_token.clone());
-    cookie.set_path("/");
-    cookie.set_http_only(true);
-    cookie.set_same_site(SameSite::Lax);
-    res.add_cookie(cookie);
+    res.add_cookie(jwt_service.refresh_token_cookie(new_refresh_token.clone()));
 
     Ok(Json(ApiResponse::success(RefreshTokenResponse {
         access_token,
@@ -279,11 +312,7 @@ pub async fn switch_role(
     let refresh_token_value =
         jwt_service.generate_refresh_token(user_id, role.id, role.code.clone())?;
 
-    let mut cookie = Cookie::new("refresh_token", refresh_token_value.clone());
-    cookie.set_path("/");
-    cookie.set_http_only(true);
-    cookie.set_same_site(SameSite::Lax);
-    res.add_cookie(cookie);
+    res.add_cookie(jwt_service.refresh_token_cookie(refresh_token_value.clone()));
 
     let response = SwitchRoleResponse {
         access_token,
@@ -334,10 +363,328 @@ pub async fn get_user_info(depot: &Depot) -> Result<Json<ApiResponse<UserInfoRes
         .map_err(|e| AppError::InternalServerError(e.to_string()))?
         .ok_or(AppError::NotFound("用户不存在".to_string()))?;
 
+    let response =
+        build_user_info(db.as_ref(), user, current_role_id, current_role_code).await?;
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
+/// 获取 RSA 公钥
+#[endpoint(
+    tags("认证"),
+    responses(
+        (status_code = 200, description = "成功获取公钥")
+    )
+)]
+pub async fn get_public_key() -> Result<Json<ApiResponse<serde_json::Value>>, AppError> {
+    let public_key = rsa_crypto::get_public_key()?;
+    let response = serde_json::json!({
+        "public_key": public_key
+    });
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
+/// 更新个人资料
+#[endpoint(
+    tags("认证"),
+    responses(
+        (status_code = 200, description = "更新成功"),
+        (status_code = 400, description = "参数错误"),
+        (status_code = 401, description = "未授权"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn update_profile(
+    req: JsonBody<UpdateProfileRequest>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<UserInfoResponse>>, AppError> {
+    let data = req.into_inner();
+    let user_id = current_user_id(depot)?;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let existing = find_active_user(db.as_ref(), user_id).await?;
+    let mut active_model: user::ActiveModel = existing.into();
+
+    if let Some(real_name) = data.real_name {
+        let real_name = real_name.trim();
+        if real_name.is_empty() || real_name.chars().count() > 100 {
+            return Err(AppError::BadRequest("真实姓名长度必须在1-100之间".to_string()));
+        }
+        active_model.real_name = Set(real_name.to_string());
+    }
+    if let Some(email) = data.email {
+        let email = email.trim();
+        if !email.is_empty() && !validation::is_valid_email(email) {
+            return Err(AppError::BadRequest("邮箱格式不正确".to_string()));
+        }
+        active_model.email = Set(Some(email.to_string()).filter(|e| !e.is_empty()));
+    }
+    if let Some(phone) = data.phone {
+        let phone = phone.trim();
+        if !phone.is_empty() && !validation::is_valid_phone(phone) {
+            return Err(AppError::BadRequest("手机号格式不正确".to_string()));
+        }
+        active_model.phone = Set(Some(phone.to_string()).filter(|p| !p.is_empty()));
+    }
+    if let Some(gender) = data.gender {
+        if !(0..=2).contains(&gender) {
+            return Err(AppError::BadRequest("无效的性别".to_string()));
+        }
+        active_model.gender = Set(gender);
+    }
+
+    active_model.updated_time = Set(Utc::now().naive_utc());
+    active_model.updated_id = Set(Some(user_id));
+    let user = active_model
+        .update(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let current_role_id = depot
+        .get::<String>("role_id")
+        .map_err(|_| AppError::Unauthorized)?
+        .clone();
+    let current_role_code = depot
+        .get::<String>("role_code")
+        .map_err(|_| AppError::Unauthorized)?
+        .clone();
+    let response =
+        build_user_info(db.as_ref(), user, current_role_id, current_role_code).await?;
+
+    Ok(Json(ApiResponse::success_with_message(
+        response,
+        i18n::t(depot, "更新成功"),
+    )))
+}
+
+/// 修改密码
+///
+/// 原密码和新密码都按登录接口的方式使用 RSA 公钥加密后传输。修改成功后
+/// 此前签发的令牌全部失效（其它设备需重新登录），当前会话使用返回的新令牌。
+#[endpoint(
+    tags("认证"),
+    responses(
+        (status_code = 200, description = "修改成功，返回新的令牌"),
+        (status_code = 400, description = "原密码错误或新密码不符合要求"),
+        (status_code = 401, description = "未授权"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn change_password(
+    req: JsonBody<ChangePasswordRequest>,
+    depot: &Depot,
+    res: &mut Response,
+) -> Result<Json<ApiResponse<RefreshTokenResponse>>, AppError> {
+    let data = req.into_inner();
+    let user_id = current_user_id(depot)?;
+
+    let (old_password, new_password) = if data.is_encrypted {
+        (
+            rsa_crypto::decrypt_password(&data.old_password)?,
+            rsa_crypto::decrypt_password(&data.new_password)?,
+        )
+    } else {
+        tracing::warn!("使用明文密码修改密码，请确认当前为测试环境");
+        (data.old_password, data.new_password)
+    };
+
+    let length = new_password.chars().count();
+    if !(PASSWORD_MIN_LENGTH..=PASSWORD_MAX_LENGTH).contains(&length) {
+        return Err(AppError::BadRequest(format!(
+            "密码长度必须在{}-{}之间",
+            PASSWORD_MIN_LENGTH, PASSWORD_MAX_LENGTH
+        )));
+    }
+    if new_password == old_password {
+        return Err(AppError::BadRequest("新密码不能与原密码相同".to_string()));
+    }
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let existing = find_active_user(db.as_ref(), user_id).await?;
+    if !crypto::verify_password(&old_password, &existing.password)? {
+        tracing::warn!("修改密码失败：用户 '{}' 原密码错误", existing.username);
+        return Err(AppError::BadRequest("原密码错误".to_string()));
+    }
+
+    let mut active_model: user::ActiveModel = existing.into();
+    active_model.password = Set(crypto::hash_password(&new_password)?);
+    active_model.tokens_revoked_time = Set(Some(tokens_revoked_now()));
+    active_model.updated_time = Set(Utc::now().naive_utc());
+    active_model.updated_id = Set(Some(user_id));
+    active_model
+        .update(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    // 吊销时间按秒截断，此后签发的新令牌不受影响
+    let role_id = depot
+        .get::<String>("role_id")
+        .ok()
+        .and_then(|id| Uuid::parse_str(id).ok())
+        .ok_or(AppError::Unauthorized)?;
+    let role_code = depot
+        .get::<String>("role_code")
+        .map_err(|_| AppError::Unauthorized)?
+        .clone();
+    let jwt_service = depot
+        .get::<Arc<JwtService>>("jwt_service")
+        .map_err(|_| AppError::InternalServerError("JWT 服务不可用".to_string()))?;
+    let access_token = jwt_service.generate_access_token(user_id, role_id, role_code.clone())?;
+    let refresh_token_value = jwt_service.generate_refresh_token(user_id, role_id, role_code)?;
+    res.add_cookie(jwt_service.refresh_token_cookie(refresh_token_value.clone()));
+
+    Ok(Json(ApiResponse::success_with_message(
+        RefreshTokenResponse {
+            access_token,
+            refresh_token: refresh_token_value,
+        },
+        i18n::t(depot, "密码修改成功"),
+    )))
+}
+
+/// 上传头像
+///
+/// 使用 multipart/form-data，文件字段名为 `file`。图片居中裁剪为正方形后
+/// 生成 256px 头像和 64px 缩略图，原头像文件随之删除。
+#[endpoint(
+    tags("认证"),
+    responses(
+        (status_code = 200, description = "上传成功"),
+        (status_code = 400, description = "文件为空、过大或不是图片"),
+        (status_code = 401, description = "未授权"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn upload_avatar(
+    req: &mut Request,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<AvatarResponse>>, AppError> {
+    let user_id = current_user_id(depot)?;
+    let storage = storage::storage()?;
+
+    let part = req
+        .file("file")
+        .await
+        .ok_or_else(|| AppError::BadRequest("请选择要上传的文件".to_string()))?;
+    if part.size() == 0 {
+        return Err(AppError::BadRequest("文件内容为空".to_string()));
+    }
+    if part.size() > storage.max_file_size {
+        return Err(AppError::BadRequest(format!(
+            "文件大小不能超过 {}MB",
+            storage.max_file_size / 1024 / 1024
+        )));
+    }
+    let temp_path = part.path().clone();
+
+    let data = tokio::fs::read(&temp_path)
+        .await
+        .map_err(|e| AppError::InternalServerError(format!("读取上传文件失败: {}", e)))?;
+    // 图片解码和缩放比较耗时，放到阻塞线程池中执行
+    let (avatar_png, thumb_png) = tokio::task::spawn_blocking(move || render_avatar(&data))
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))??;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let existing = find_active_user(db.as_ref(), user_id).await?;
+
+    let avatar = file::store_file(
+        db.as_ref(),
+        &storage,
+        NewFile {
+            file_name: "avatar.png".to_string(),
+            extension: "png".to_string(),
+            data: &avatar_png,
+            biz_type: file::BIZ_TYPE_AVATAR.to_string(),
+        },
+        Some(user_id),
+    )
+    .await?;
+    let thumb = file::store_file(
+        db.as_ref(),
+        &storage,
+        NewFile {
+            file_name: "avatar_thumb.png".to_string(),
+            extension: "png".to_string(),
+            data: &thumb_png,
+            biz_type: file::BIZ_TYPE_AVATAR_THUMB.to_string(),
+        },
+        Some(user_id),
+    )
+    .await?;
+
+    let previous = [existing.avatar.clone(), existing.avatar_thumb.clone()];
+    let response = AvatarResponse {
+        avatar: file::avatar_url(avatar.id),
+        avatar_thumb: file::avatar_url(thumb.id),
+    };
+
+    let mut active_model: user::ActiveModel = existing.into();
+    active_model.avatar = Set(Some(response.avatar.clone()));
+    active_model.avatar_thumb = Set(Some(response.avatar_thumb.clone()));
+    active_model.updated_time = Set(Utc::now().naive_utc());
+    active_model.updated_id = Set(Some(user_id));
+    active_model
+        .update(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    // 清理旧头像，失败不影响本次上传；头像地址可能指向他人的文件，只删除本人上传的头像
+    for file_id in previous.iter().flatten().filter_map(|url| file::avatar_file_id(url)) {
+        let Ok(old) = file::find_file(db.as_ref(), file_id).await else {
+            continue;
+        };
+        if file::is_avatar_file(&old) && old.created_id == Some(user_id) {
+            if let Err(e) = file::remove_file(db.as_ref(), &storage, old, Some(user_id)).await {
+                tracing::warn!("删除旧头像失败: file={}, error={}", file_id, e);
+            }
+        }
+    }
+
+    Ok(Json(ApiResponse::success_with_message(
+        response,
+        i18n::t(depot, "上传成功"),
+    )))
+}
+
+// ========== 辅助函数 ==========
+
+fn current_user_id(depot: &Depot) -> Result<Uuid, AppError> {
+    let user_id_str = depot
+        .get::<String>("user_id")
+        .map_err(|_| AppError::Unauthorized)?;
+    Uuid::parse_str(user_id_str.as_str()).map_err(|_| AppError::Unauthorized)
+}
+
+async fn find_active_user(db: &DatabaseConnection, user_id: Uuid) -> Result<user::Model, AppError> {
+    user::Entity::find_by_id(user_id)
+        .filter(user::Column::DeletedTime.is_null())
+        .one(db)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?
+        .ok_or(AppError::NotFound("用户不存在".to_string()))
+}
+
+async fn build_user_info(
+    db: &DatabaseConnection,
+    user: user::Model,
+    current_role_id: String,
+    current_role_code: String,
+) -> Result<UserInfoResponse, AppError> {
     let user_roles = user_role::Entity::find()
-        .filter(user_role::Column::UserId.eq(user_id))
+        .filter(user_role::Column::UserId.eq(user.id))
         .find_also_related(role::Entity)
-        .all(db.as_ref())
+        .all(db)
         .await
         .map_err(|e| AppError::InternalServerError(e.to_string()))?;
 
@@ -352,34 +699,157 @@ pub async fn get_user_info(depot: &Depot) -> Result<Json<ApiResponse<UserInfoRes
         })
         .collect();
 
-    let response = UserInfoResponse {
+    Ok(UserInfoResponse {
         id: user.id.to_string(),
         user_name: user.username,
         real_name: user.real_name,
         email: user.email,
         phone: user.phone,
         avatar: user.avatar,
+        avatar_thumb: user.avatar_thumb,
+        gender: user.gender,
         status: user.status,
         roles,
         current_role_id,
         current_role_code,
-    };
+    })
+}
 
-    Ok(Json(ApiResponse::success(response)))
+/// 将上传的图片居中裁剪为正方形，生成头像和缩略图（PNG）
+fn render_avatar(data: &[u8]) -> Result<(Vec<u8>, Vec<u8>), AppError> {
+    let mut reader = ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .map_err(|_| AppError::BadRequest("无法识别的图片格式".to_string()))?;
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(AVATAR_MAX_SOURCE_DIMENSION);
+    limits.max_image_height = Some(AVATAR_MAX_SOURCE_DIMENSION);
+    reader.limits(limits);
+
+    let image = reader
+        .decode()
+        .map_err(|_| AppError::BadRequest("无法识别的图片格式".to_string()))?;
+
+    let side = image.width().min(image.height());
+    let square = image.crop_imm(
+        (image.width() - side) / 2,
+        (image.height() - side) / 2,
+        side,
+        side,
+    );
+
+    let encode = |size: u32| -> Result<Vec<u8>, AppError> {
+        let size = size.min(side);
+        let mut buffer = Vec::new();
+        square
+            .resize_exact(size, size, FilterType::Lanczos3)
+            .write_to(&mut Cursor::new(&mut buffer), ImageFormat::Png)
+            .map_err(|e| AppError::InternalServerError(format!("头像编码失败: {}", e)))?;
+        Ok(buffer)
+    };
+    Ok((encode(AVATAR_SIZE)?, encode(AVATAR_THUMB_SIZE)?))
 }
 
-/// 获取 RSA 公钥
-#[endpoint(
-    tags("认证"),
-    responses(
-        (status_code = 200, description = "成功获取公钥")
-    )
-)]
-pub async fn get_public_key() -> Result<Json<ApiResponse<serde_json::Value>>, AppError> {
-    let public_key = rsa_crypto::get_public_key()?;
-    let response = serde_json::json!({
-        "public_key": public_key
-    });
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::constants::SUPER_ADMIN_USER_ID;
+    use crate::common::jwt::Claims;
+    use crate::common::testing;
+    use image::{DynamicImage, Rgb, RgbImage};
+    use salvo::test::{ResponseExt, TestClient};
+
+    async fn change_password_request(
+        db: &testing::TestDb,
+        token: &str,
+        old_password: &str,
+        new_password: &str,
+    ) -> Response {
+        TestClient::post("http://127.0.0.1/auth/changePassword")
+            .bearer_auth(token)
+            .json(&serde_json::json!({
+                "oldPassword": old_password,
+                "newPassword": new_password,
+                "isEncrypted": false,
+            }))
+            .send(&testing::service(super::super::routes::routes(), Some(db.arc())))
+            .await
+    }
 
-    Ok(Json(ApiResponse::success(response)))
+    #[tokio::test]
+    async fn change_password_revokes_old_tokens_and_returns_new_ones() {
+        let Some(db) = testing::TestDb::migrated().await else {
+            return;
+        };
+        let admin_id = Uuid::parse_str(SUPER_ADMIN_USER_ID).unwrap();
+        let token = testing::admin_token();
+
+        let res = change_password_request(&db, &token, "wrong-password", "newPassword1").await;
+        assert_eq!(res.status_code, Some(StatusCode::BAD_REQUEST));
+
+        let mut res = change_password_request(&db, &token, "superAdmin", "newPassword1").await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        assert!(res.cookie(REFRESH_TOKEN_COOKIE).is_some());
+        let body: serde_json::Value = res.take_json().await.unwrap();
+
+        let user = find_active_user(&db, admin_id).await.unwrap();
+        assert!(crypto::verify_password("newPassword1", &user.password).unwrap());
+
+        // 修改前签发的令牌失效，返回的新令牌可以继续使用
+        let mut earlier =
+            Claims::new_access_token(admin_id, Uuid::new_v4(), "superAdmin".to_string(), 1);
+        earlier.iat -= 5;
+        assert!(is_token_revoked(&db, &earlier).await);
+
+        let jwt_service = testing::jwt_service();
+        for key in ["accessToken", "refreshToken"] {
+            let claims = jwt_service
+                .validate_token(body["data"][key].as_str().unwrap())
+                .unwrap();
+            assert!(!is_token_revoked(&db, &claims).await);
+        }
+        let res = TestClient::get("http://127.0.0.1/auth/getUserInfo")
+            .bearer_auth(body["data"]["accessToken"].as_str().unwrap())
+            .send(&testing::service(super::super::routes::routes(), Some(db.arc())))
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+    }
+
+    fn decode(data: &[u8]) -> DynamicImage {
+        image::load_from_memory(data).unwrap()
+    }
+
+    #[test]
+    fn avatar_is_center_cropped_to_square() {
+        // 300x200 的图片，左右各 50px 为红、蓝色边，中间 200px 为绿色
+        let source = RgbImage::from_fn(300, 200, |x, _| match x {
+            0..50 => Rgb([255, 0, 0]),
+            250.. => Rgb([0, 0, 255]),
+            _ => Rgb([0, 255, 0]),
+        });
+        let mut data = Vec::new();
+        DynamicImage::ImageRgb8(source)
+            .write_to(&mut Cursor::new(&mut data), ImageFormat::Png)
+            .unwrap();
+
+        let (avatar, thumb) = render_avatar(&data).unwrap();
+        let avatar = decode(&avatar).to_rgb8();
+        // 原图短边小于头像尺寸时不放大
+        assert_eq!(avatar.dimensions(), (200, 200));
+        for (x, y) in [(0, 0), (199, 0), (0, 199), (199, 199), (100, 100)] {
+            assert_eq!(avatar.get_pixel(x, y), &Rgb([0, 255, 0]));
+        }
+        let thumb = decode(&thumb);
+        assert_eq!(
+            (thumb.width(), thumb.height()),
+            (AVATAR_THUMB_SIZE, AVATAR_THUMB_SIZE)
+        );
+    }
+
+    #[test]
+    fn avatar_rejects_non_image_data() {
+        assert!(matches!(
+            render_avatar(b"not an image"),
+            Err(AppError::BadRequest(_))
+        ));
+    }
 }
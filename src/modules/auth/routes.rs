@@ -19,4 +19,11 @@ pub fn routes() -> Router {
                 .hoop(auth_middleware)
                 .get(handler::get_user_info)
         )
+        .push(
+            Router::new()
+                .hoop(auth_middleware)
+                .push(Router::with_path("updateProfile").put(handler::update_profile))
+                .push(Router::with_path("changePassword").post(handler::change_password))
+                .push(Router::with_path("uploadAvatar").post(handler::upload_avatar))
+        )
 }
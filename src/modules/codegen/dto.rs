@@ -0,0 +1,29 @@
+use serde::Serialize;
+use salvo::oapi::ToSchema;
+
+/// 数据表
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TableInfo {
+    pub table_name: String,
+    /// 表注释
+    pub comment: Option<String>,
+}
+
+/// 生成的文件
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratedFile {
+    /// 相对项目根目录的路径
+    pub path: String,
+    pub content: String,
+}
+
+/// 代码预览
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CodegenPreview {
+    /// 模块名
+    pub module: String,
+    pub files: Vec<GeneratedFile>,
+}
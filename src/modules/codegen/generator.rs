@@ -0,0 +1,1245 @@
+// CRUD 模块代码生成
+// 按 dict / notice 等模块的结构生成模型、DTO、处理器、路由和菜单 SQL
+
+use uuid::Uuid;
+
+use super::dto::GeneratedFile;
+use super::introspect::ColumnRow;
+use crate::common::constants::SUPER_ADMIN_ROLE_ID;
+use crate::common::AppError;
+
+/// 生成选项
+pub struct GenerateOptions {
+    pub table_name: String,
+    /// 模块名（snake_case），同时用作模型文件名
+    pub module: String,
+    /// 业务名称，用于接口标签、提示消息和菜单名
+    pub name: String,
+    pub parent_menu_id: Uuid,
+    /// 权限标识前缀，如 system
+    pub permission_prefix: String,
+}
+
+/// 列的 Rust 类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Uuid,
+    String,
+    I16,
+    I32,
+    I64,
+    F32,
+    F64,
+    Bool,
+    DateTime,
+    DateTimeTz,
+    Date,
+    Time,
+    Json,
+}
+
+impl Kind {
+    fn from_udt(udt_name: &str) -> Option<Self> {
+        Some(match udt_name {
+            "uuid" => Kind::Uuid,
+            "varchar" | "text" | "bpchar" | "citext" => Kind::String,
+            "int2" => Kind::I16,
+            "int4" => Kind::I32,
+            "int8" => Kind::I64,
+            "float4" => Kind::F32,
+            "float8" => Kind::F64,
+            "bool" => Kind::Bool,
+            "timestamp" => Kind::DateTime,
+            "timestamptz" => Kind::DateTimeTz,
+            "date" => Kind::Date,
+            "time" => Kind::Time,
+            "json" | "jsonb" => Kind::Json,
+            _ => return None,
+        })
+    }
+
+    /// 模型中的类型
+    fn model_type(self) -> &'static str {
+        match self {
+            Kind::Uuid => "Uuid",
+            Kind::String => "String",
+            Kind::I16 => "i16",
+            Kind::I32 => "i32",
+            Kind::I64 => "i64",
+            Kind::F32 => "f32",
+            Kind::F64 => "f64",
+            Kind::Bool => "bool",
+            Kind::DateTime => "DateTime",
+            Kind::DateTimeTz => "DateTimeWithTimeZone",
+            Kind::Date => "Date",
+            Kind::Time => "Time",
+            Kind::Json => "Json",
+        }
+    }
+
+    /// DTO 中的类型，ID 和时间以字符串传输
+    fn dto_type(self) -> &'static str {
+        match self {
+            Kind::Uuid | Kind::DateTime | Kind::DateTimeTz | Kind::Date | Kind::Time => "String",
+            Kind::Json => "serde_json::Value",
+            other => other.model_type(),
+        }
+    }
+
+    /// 字符串解析函数，不需要解析的类型返回 None
+    fn parser(self) -> Option<&'static str> {
+        match self {
+            Kind::Uuid => Some("parse_uuid"),
+            Kind::DateTime => Some("parse_datetime"),
+            Kind::DateTimeTz => Some("parse_datetime_tz"),
+            Kind::Date => Some("parse_date"),
+            Kind::Time => Some("parse_time"),
+            _ => None,
+        }
+    }
+
+    /// 模型值 `v` 转换为 DTO 值的表达式
+    fn to_dto(self, v: &str) -> String {
+        match self {
+            Kind::Uuid => format!("{}.to_string()", v),
+            Kind::DateTime => format!("{}.format(\"%Y-%m-%d %H:%M:%S\").to_string()", v),
+            Kind::DateTimeTz => format!("{}.to_rfc3339()", v),
+            Kind::Date => format!("{}.format(\"%Y-%m-%d\").to_string()", v),
+            Kind::Time => format!("{}.format(\"%H:%M:%S\").to_string()", v),
+            _ => format!("{}.clone()", v),
+        }
+    }
+
+    fn is_copy(self) -> bool {
+        matches!(
+            self,
+            Kind::I16 | Kind::I32 | Kind::I64 | Kind::F32 | Kind::F64 | Kind::Bool
+        )
+    }
+
+    fn filterable(self) -> bool {
+        matches!(
+            self,
+            Kind::Uuid | Kind::String | Kind::I16 | Kind::I32 | Kind::I64 | Kind::Bool
+        )
+    }
+}
+
+/// 业务列
+struct Field {
+    column: String,
+    /// Rust 字段名
+    ident: String,
+    /// Column 枚举成员名
+    variant: String,
+    /// JSON 字段名
+    camel: String,
+    label: String,
+    kind: Kind,
+    nullable: bool,
+    has_default: bool,
+    max_length: Option<i32>,
+}
+
+impl Field {
+    /// 是否为创建时必填
+    fn required(&self) -> bool {
+        !self.nullable && !self.has_default
+    }
+}
+
+/// 标准审计列，存在且类型匹配时自动维护
+#[derive(Default)]
+struct Audit {
+    created_time: bool,
+    created_id: bool,
+    updated_time: bool,
+    updated_id: bool,
+    deleted_time: bool,
+    deleted_id: bool,
+}
+
+struct Model<'a> {
+    options: &'a GenerateOptions,
+    /// 单行的业务名称
+    name: String,
+    pascal: String,
+    camel: String,
+    table_comment: Option<String>,
+    fields: Vec<Field>,
+    audit: Audit,
+}
+
+/// 生成模块代码
+pub fn generate(
+    options: &GenerateOptions,
+    table_comment: Option<String>,
+    columns: &[ColumnRow],
+) -> Result<Vec<GeneratedFile>, AppError> {
+    let id = columns
+        .iter()
+        .find(|c| c.column_name == "id")
+        .filter(|c| c.is_primary && c.udt_name == "uuid")
+        .ok_or_else(|| AppError::BadRequest("表必须以 UUID 类型的 id 列为主键".to_string()))?;
+    if columns.iter().filter(|c| c.is_primary).count() > 1 {
+        return Err(AppError::BadRequest("不支持联合主键".to_string()));
+    }
+
+    let mut audit = Audit::default();
+    let mut fields = Vec::new();
+    for column in columns.iter().filter(|c| c.column_name != id.column_name) {
+        let kind = Kind::from_udt(&column.udt_name).ok_or_else(|| {
+            AppError::BadRequest(format!(
+                "不支持的列类型: {} ({})",
+                column.column_name, column.udt_name
+            ))
+        })?;
+
+        let flag = match (column.column_name.as_str(), kind) {
+            ("created_time", Kind::DateTime) => Some(&mut audit.created_time),
+            ("created_id", Kind::Uuid) => Some(&mut audit.created_id),
+            ("updated_time", Kind::DateTime) => Some(&mut audit.updated_time),
+            ("updated_id", Kind::Uuid) => Some(&mut audit.updated_id),
+            ("deleted_time", Kind::DateTime) if column.nullable => Some(&mut audit.deleted_time),
+            ("deleted_id", Kind::Uuid) if column.nullable => Some(&mut audit.deleted_id),
+            _ => None,
+        };
+        if let Some(flag) = flag {
+            *flag = true;
+            continue;
+        }
+
+        let ident = rust_ident(&column.column_name);
+        fields.push(Field {
+            column: column.column_name.clone(),
+            variant: to_pascal(&ident),
+            camel: to_camel(&column.column_name),
+            label: column
+                .comment
+                .as_deref()
+                .map(single_line)
+                .filter(|c| !c.is_empty())
+                .unwrap_or_else(|| column.column_name.clone()),
+            ident,
+            kind,
+            nullable: column.nullable,
+            has_default: column.has_default,
+            max_length: column.max_length,
+        });
+    }
+
+    let model = Model {
+        options,
+        name: single_line(&options.name),
+        pascal: to_pascal(&options.module),
+        camel: to_camel(&options.module),
+        table_comment: table_comment
+            .as_deref()
+            .map(single_line)
+            .filter(|c| !c.is_empty()),
+        fields,
+        audit,
+    };
+
+    let module = &options.module;
+    let (menu_id, menu_sql) = model.menu_sql();
+    Ok(vec![
+        file(format!("src/models/{}.rs", module), model.entity()),
+        file(format!("src/modules/{}/mod.rs", module), model.module()),
+        file(format!("src/modules/{}/dto.rs", module), model.dto()),
+        file(format!("src/modules/{}/handler.rs", module), model.handler()),
+        file(format!("src/modules/{}/routes.rs", module), model.routes()),
+        file(format!("migrations/xxx_{}_menu.sql", module), menu_sql),
+        file(
+            format!("migrations/xxx_{}_menu.down.sql", module),
+            model.menu_down_sql(menu_id),
+        ),
+        file(format!("README_{}.md", module), model.readme()),
+    ])
+}
+
+fn file(path: String, content: String) -> GeneratedFile {
+    GeneratedFile { path, content }
+}
+
+impl Model<'_> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 字符串字面量中使用的业务名称
+    fn name_lit(&self) -> String {
+        lit(&self.name)
+    }
+
+    fn module_name(&self) -> &str {
+        &self.options.module
+    }
+
+    fn entity(&self) -> String {
+        let mut out = String::new();
+        out.push_str("use sea_orm::entity::prelude::*;\nuse serde::{Deserialize, Serialize};\n\n");
+        if let Some(comment) = &self.table_comment {
+            out.push_str(&format!("/// {}\n", comment));
+        }
+        out.push_str("#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]\n");
+        out.push_str(&format!(
+            "#[sea_orm(table_name = \"{}\")]\n",
+            self.options.table_name
+        ));
+        out.push_str("pub struct Model {\n    #[sea_orm(primary_key)]\n    pub id: Uuid,\n");
+        for f in &self.fields {
+            if f.label != f.column {
+                out.push_str(&format!("    /// {}\n", f.label));
+            }
+            if f.ident != f.column {
+                out.push_str(&format!("    #[sea_orm(column_name = \"{}\")]\n", f.column));
+            }
+            out.push_str(&format!("    pub {}: {},\n", f.ident, option(f.kind.model_type(), f.nullable)));
+        }
+        let audit = [
+            (self.audit.created_time, "created_time", "DateTime"),
+            (self.audit.created_id, "created_id", "Option<Uuid>"),
+            (self.audit.updated_time, "updated_time", "DateTime"),
+            (self.audit.updated_id, "updated_id", "Option<Uuid>"),
+            (self.audit.deleted_time, "deleted_time", "Option<DateTime>"),
+            (self.audit.deleted_id, "deleted_id", "Option<Uuid>"),
+        ];
+        for (present, column, ty) in audit {
+            if present {
+                out.push_str(&format!("    pub {}: {},\n", column, ty));
+            }
+        }
+        out.push_str("}\n\n");
+        out.push_str("#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]\npub enum Relation {}\n\n");
+        out.push_str("impl ActiveModelBehavior for ActiveModel {}\n");
+        out
+    }
+
+    fn module(&self) -> String {
+        format!(
+            "// {} 模块 - {}\n\nmod dto;\nmod handler;\nmod routes;\n\npub use routes::routes;\n",
+            self.module_name(),
+            self.name()
+        )
+    }
+
+    fn dto(&self) -> String {
+        let mut out = String::new();
+        out.push_str("use serde::{Deserialize, Serialize};\nuse salvo::oapi::ToSchema;\n");
+
+        let derive = "#[derive(Debug, Serialize, Deserialize, ToSchema)]\n#[serde(rename_all = \"camelCase\")]\n";
+
+        out.push_str(&format!("\n/// 创建{}请求\n{}pub struct Create{}Request {{\n", self.name(), derive, self.pascal));
+        for f in &self.fields {
+            push_dto_field(&mut out, f, option(f.kind.dto_type(), !f.required()));
+        }
+        out.push_str("}\n");
+
+        out.push_str(&format!(
+            "\n/// 更新{}请求，未传的字段保持不变\n{}pub struct Update{}Request {{\n",
+            self.name(),
+            derive,
+            self.pascal
+        ));
+        for f in &self.fields {
+            push_dto_field(&mut out, f, option(f.kind.dto_type(), true));
+        }
+        out.push_str("}\n");
+
+        out.push_str(&format!("\n/// {}响应\n{}pub struct {}Response {{\n    pub id: String,\n", self.name(), derive, self.pascal));
+        for f in &self.fields {
+            push_dto_field(&mut out, f, option(f.kind.dto_type(), f.nullable));
+        }
+        if self.audit.created_time {
+            out.push_str("    pub created_time: String,\n");
+        }
+        if self.audit.updated_time {
+            out.push_str("    pub updated_time: String,\n");
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    fn routes(&self) -> String {
+        format!(
+            r#"use salvo::prelude::*;
+use crate::common::middleware::auth_middleware;
+use super::handler;
+
+pub fn routes() -> Router {{
+    Router::with_path("{camel}")
+        .hoop(auth_middleware)
+        .push(Router::with_path("list").get(handler::get_{m}_list))
+        .push(Router::new().post(handler::create_{m}))
+        .push(
+            Router::with_path("<id>")
+                .get(handler::get_{m})
+                .put(handler::update_{m})
+                .delete(handler::delete_{m})
+        )
+}}
+"#,
+            camel = self.camel,
+            m = self.module_name()
+        )
+    }
+
+    fn handler(&self) -> String {
+        let m = self.module_name();
+        let filters: Vec<&Field> = self.fields.iter().filter(|f| f.kind.filterable()).collect();
+        let filtered = self.audit.deleted_time || !filters.is_empty();
+        let uses_not_set = self.fields.iter().any(|f| !f.nullable && f.has_default);
+        let parsers: Vec<&str> = {
+            let mut p: Vec<&str> = self.fields.iter().filter_map(|f| f.kind.parser()).collect();
+            p.sort();
+            p.dedup();
+            p
+        };
+        let uses_now = self.audit.created_time || self.audit.updated_time || self.audit.deleted_time;
+
+        let mut sea_orm = vec!["ActiveModelTrait"];
+        if filtered {
+            sea_orm.push("ColumnTrait");
+        }
+        sea_orm.extend(["DatabaseConnection", "EntityTrait"]);
+        if uses_not_set {
+            sea_orm.push("NotSet");
+        }
+        sea_orm.push("PaginatorTrait");
+        if filtered {
+            sea_orm.push("QueryFilter");
+        }
+        sea_orm.extend(["QueryOrder", "QuerySelect", "Set"]);
+
+        let mut out = String::new();
+        if uses_now {
+            out.push_str("use chrono::Utc;\n");
+        }
+        out.push_str("use salvo::oapi::extract::{JsonBody, PathParam};\nuse salvo::prelude::*;\n");
+        let sea_orm = sea_orm.join(", ");
+        if sea_orm.len() > 80 {
+            out.push_str(&format!("use sea_orm::{{\n    {}\n}};\n", wrap(&sea_orm, 96)));
+        } else {
+            out.push_str(&format!("use sea_orm::{{{}}};\n", sea_orm));
+        }
+        out.push_str("use std::sync::Arc;\nuse uuid::Uuid;\n\n");
+        out.push_str(&format!(
+            "use super::dto::{{Create{p}Request, {p}Response, Update{p}Request}};\n",
+            p = self.pascal
+        ));
+        out.push_str("use crate::common::constants::{DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE};\n");
+        out.push_str("use crate::common::{i18n, ApiResponse, AppError, PageResponse};\n");
+        out.push_str(&format!("use crate::models::{};\n", m));
+
+        out.push_str(&self.list_handler(&filters));
+        out.push_str(&self.detail_handler());
+        out.push_str(&self.create_handler());
+        out.push_str(&self.update_handler());
+        out.push_str(&self.delete_handler());
+
+        out.push_str("\n// ========== 辅助函数 ==========\n");
+        out.push_str(&self.find_helper());
+        out.push_str(&self.response_helper());
+        if self.fields.iter().any(|f| f.kind == Kind::String) {
+            out.push_str(LENGTH_HELPER);
+        }
+        for parser in parsers {
+            out.push_str(match parser {
+                "parse_uuid" => PARSE_UUID,
+                "parse_datetime" => PARSE_DATETIME,
+                "parse_datetime_tz" => PARSE_DATETIME_TZ,
+                "parse_date" => PARSE_DATE,
+                _ => PARSE_TIME,
+            });
+        }
+        out
+    }
+
+    fn db_handle() -> &'static str {
+        "    let db = depot\n        .get::<Arc<DatabaseConnection>>(\"db\")\n        .map_err(|_| AppError::InternalServerError(\"数据库服务不可用\".to_string()))?;\n"
+    }
+
+    fn user_id() -> &'static str {
+        "\n    let user_id = depot\n        .get::<String>(\"user_id\")\n        .ok()\n        .and_then(|s| Uuid::parse_str(s.as_str()).ok());\n"
+    }
+
+    fn parse_id(&self) -> String {
+        format!(
+            "    let record_id = Uuid::parse_str(&id.into_inner())\n        .map_err(|_| AppError::BadRequest(\"无效的{}ID\".to_string()))?;\n",
+            self.name_lit()
+        )
+    }
+
+    fn list_handler(&self, filters: &[&Field]) -> String {
+        let m = self.module_name();
+        let mut params = String::new();
+        let mut conditions = String::new();
+        for f in filters {
+            let (description, ty) = match f.kind {
+                Kind::String => (format!("{}（模糊搜索）", lit(&f.label)), "String"),
+                Kind::Uuid => (lit(&f.label), "String"),
+                _ => (lit(&f.label), f.kind.model_type()),
+            };
+            params.push_str(&format!(
+                "        (\"{}\" = Option<{}>, Query, description = \"{}\"),\n",
+                f.camel, ty, description
+            ));
+            let column = format!("{}::Column::{}", m, f.variant);
+            conditions.push_str(&match f.kind {
+                Kind::String => format!(
+                    "    if let Some({v}) = req.query::<String>(\"{c}\").filter(|v| !v.is_empty()) {{\n        query_builder = query_builder.filter({col}.contains(&{v}));\n    }}\n",
+                    v = f.ident, c = f.camel, col = column
+                ),
+                Kind::Uuid => format!(
+                    "    if let Some({v}) = req\n        .query::<String>(\"{c}\")\n        .and_then(|v| Uuid::parse_str(&v).ok())\n    {{\n        query_builder = query_builder.filter({col}.eq({v}));\n    }}\n",
+                    v = f.ident, c = f.camel, col = column
+                ),
+                _ => format!(
+                    "    if let Some({v}) = req.query::<{t}>(\"{c}\") {{\n        query_builder = query_builder.filter({col}.eq({v}));\n    }}\n",
+                    v = f.ident, t = f.kind.model_type(), c = f.camel, col = column
+                ),
+            });
+        }
+
+        let base_filter = if self.audit.deleted_time {
+            format!("\n        .filter({}::Column::DeletedTime.is_null())", m)
+        } else {
+            String::new()
+        };
+        let binding = if filters.is_empty() { "let" } else { "let mut" };
+        let order = if self.audit.created_time { "CreatedTime" } else { "Id" };
+
+        format!(
+            r#"
+/// 获取{name}列表（分页）
+#[endpoint(
+    tags("{name_lit}"),
+    parameters(
+{params}        ("page" = Option<u64>, Query, description = "当前页码，默认1"),
+        ("pageSize" = Option<u64>, Query, description = "每页数量，默认20，最大100"),
+    ),
+    responses(
+        (status_code = 200, description = "获取成功"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn get_{m}_list(
+    req: &mut Request,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<PageResponse<{p}Response>>>, AppError> {{
+    let page = req.query::<u64>("page").unwrap_or(1).max(1);
+    let page_size = req
+        .query::<u64>("pageSize")
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+
+{db}
+    {binding} query_builder = {m}::Entity::find(){base_filter};
+
+{conditions}
+    let total = query_builder
+        .clone()
+        .count(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let records = query_builder
+        .order_by_desc({m}::Column::{order})
+        .offset((page - 1) * page_size)
+        .limit(page_size)
+        .all(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let items = records.iter().map(to_response).collect();
+    Ok(Json(ApiResponse::success(PageResponse::new(
+        items, total, page, page_size,
+    ))))
+}}
+"#,
+            name = self.name(),
+            name_lit = self.name_lit(),
+            p = self.pascal,
+            db = Self::db_handle(),
+        )
+        .replace("\n\n\n    let total", "\n\n    let total")
+    }
+
+    fn detail_handler(&self) -> String {
+        format!(
+            r#"
+/// 获取{name}详情
+#[endpoint(
+    tags("{name_lit}"),
+    responses(
+        (status_code = 200, description = "获取成功"),
+        (status_code = 404, description = "{name_lit}不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn get_{m}(
+    id: PathParam<String>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<{p}Response>>, AppError> {{
+{parse_id}
+{db}
+    let record = find_{m}(db.as_ref(), record_id).await?;
+    Ok(Json(ApiResponse::success(to_response(&record))))
+}}
+"#,
+            name = self.name(),
+            name_lit = self.name_lit(),
+            m = self.module_name(),
+            p = self.pascal,
+            parse_id = self.parse_id(),
+            db = Self::db_handle(),
+        )
+    }
+
+    fn create_handler(&self) -> String {
+        let m = self.module_name();
+        let mut validate = String::new();
+        let mut assigns = String::from("        id: Set(Uuid::new_v4()),\n");
+        for f in &self.fields {
+            let value = format!("data.{}", f.ident);
+            let label = lit(&f.label);
+            if f.kind == Kind::String {
+                if f.required() {
+                    validate.push_str(&format!(
+                        "    check_length(&{}, {}, \"{}\")?;\n",
+                        value,
+                        length_arg(f),
+                        lit(&f.label)
+                    ));
+                } else {
+                    validate.push_str(&format!(
+                        "    if let Some(value) = &{} {{\n        check_length(value, {}, \"{}\")?;\n    }}\n",
+                        value,
+                        length_arg(f),
+                        lit(&f.label)
+                    ));
+                }
+            }
+
+            let set = match (f.kind.parser(), f.required(), f.nullable) {
+                (None, _, true) | (None, true, _) => format!("Set({})", value),
+                (None, false, false) => format!("{}.map(Set).unwrap_or(NotSet)", value),
+                (Some(parser), true, _) => {
+                    format!("Set({}(&{}, \"{}\")?)", parser, value, label)
+                }
+                (Some(parser), false, true) => format!(
+                    "Set({}\n            .map(|v| {}(&v, \"{}\"))\n            .transpose()?)",
+                    value, parser, label
+                ),
+                (Some(parser), false, false) => format!(
+                    "match {} {{\n            Some(v) => Set({}(&v, \"{}\")?),\n            None => NotSet,\n        }}",
+                    value, parser, label
+                ),
+            };
+            assigns.push_str(&format!("        {}: {},\n", f.ident, set));
+        }
+        let audit = [
+            (self.audit.created_time, "created_time: Set(now)"),
+            (self.audit.created_id, "created_id: Set(user_id)"),
+            (self.audit.updated_time, "updated_time: Set(now)"),
+            (self.audit.updated_id, "updated_id: Set(user_id)"),
+            (self.audit.deleted_time, "deleted_time: Set(None)"),
+            (self.audit.deleted_id, "deleted_id: Set(None)"),
+        ];
+        for (present, line) in audit {
+            if present {
+                assigns.push_str(&format!("        {},\n", line));
+            }
+        }
+
+        let user_id = if self.audit.created_id || self.audit.updated_id {
+            Self::user_id()
+        } else {
+            ""
+        };
+        let now = if self.audit.created_time || self.audit.updated_time {
+            "\n    let now = Utc::now().naive_utc();"
+        } else {
+            ""
+        };
+        let validate = if validate.is_empty() { validate } else { validate + "\n" };
+
+        format!(
+            r#"
+/// 创建{name}
+#[endpoint(
+    tags("{name_lit}"),
+    responses(
+        (status_code = 200, description = "创建成功"),
+        (status_code = 400, description = "参数错误"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn create_{m}(
+    req: JsonBody<Create{p}Request>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<{p}Response>>, AppError> {{
+    let data = req.into_inner();
+{validate}{db}{user_id}{now}
+    let record = {m}::ActiveModel {{
+{assigns}    }}
+    .insert(db.as_ref())
+    .await
+    .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    Ok(Json(ApiResponse::success_with_message(
+        to_response(&record),
+        i18n::t(depot, "创建成功"),
+    )))
+}}
+"#,
+            name = self.name(),
+            name_lit = self.name_lit(),
+            p = self.pascal,
+            db = Self::db_handle(),
+        )
+    }
+
+    fn update_handler(&self) -> String {
+        let m = self.module_name();
+        let mut assigns = String::new();
+        for f in &self.fields {
+            let mut body = String::new();
+            if f.kind == Kind::String {
+                body.push_str(&format!(
+                    "        check_length(&value, {}, \"{}\")?;\n",
+                    length_arg(f),
+                    lit(&f.label)
+                ));
+            }
+            let value = match f.kind.parser() {
+                Some(parser) => format!("{}(&value, \"{}\")?", parser, lit(&f.label)),
+                None => "value".to_string(),
+            };
+            let value = if f.nullable { format!("Some({})", value) } else { value };
+            body.push_str(&format!("        active_model.{} = Set({});\n", f.ident, value));
+            assigns.push_str(&format!(
+                "    if let Some(value) = data.{} {{\n{}    }}\n",
+                f.ident, body
+            ));
+        }
+        if self.audit.updated_time {
+            assigns.push_str("    active_model.updated_time = Set(Utc::now().naive_utc());\n");
+        }
+        if self.audit.updated_id {
+            assigns.push_str("    active_model.updated_id = Set(user_id);\n");
+        }
+        let user_id = if self.audit.updated_id { Self::user_id() } else { "" };
+        let binding = if assigns.is_empty() { "let" } else { "let mut" };
+
+        format!(
+            r#"
+/// 更新{name}
+#[endpoint(
+    tags("{name_lit}"),
+    responses(
+        (status_code = 200, description = "更新成功"),
+        (status_code = 400, description = "参数错误"),
+        (status_code = 404, description = "{name_lit}不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn update_{m}(
+    id: PathParam<String>,
+    req: JsonBody<Update{p}Request>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<{p}Response>>, AppError> {{
+{parse_id}    let data = req.into_inner();
+
+{db}{user_id}
+    let existing = find_{m}(db.as_ref(), record_id).await?;
+    {binding} active_model: {m}::ActiveModel = existing.into();
+
+{assigns}
+    let record = active_model
+        .update(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    Ok(Json(ApiResponse::success_with_message(
+        to_response(&record),
+        i18n::t(depot, "更新成功"),
+    )))
+}}
+"#,
+            name = self.name(),
+            name_lit = self.name_lit(),
+            p = self.pascal,
+            parse_id = self.parse_id(),
+            db = Self::db_handle(),
+        )
+    }
+
+    fn delete_handler(&self) -> String {
+        let m = self.module_name();
+        let body = if self.audit.deleted_time {
+            let mut body = format!(
+                "    let existing = find_{m}(db.as_ref(), record_id).await?;\n    let mut active_model: {m}::ActiveModel = existing.into();\n    active_model.deleted_time = Set(Some(Utc::now().naive_utc()));\n",
+                m = m
+            );
+            if self.audit.deleted_id {
+                body.push_str("    active_model.deleted_id = Set(user_id);\n");
+            }
+            body.push_str("    active_model\n        .update(db.as_ref())\n        .await\n        .map_err(|e| AppError::InternalServerError(e.to_string()))?;\n");
+            body
+        } else {
+            format!(
+                "    find_{m}(db.as_ref(), record_id).await?;\n    {m}::Entity::delete_by_id(record_id)\n        .exec(db.as_ref())\n        .await\n        .map_err(|e| AppError::InternalServerError(e.to_string()))?;\n",
+                m = m
+            )
+        };
+        let user_id = if self.audit.deleted_id { Self::user_id() } else { "" };
+
+        format!(
+            r#"
+/// 删除{name}
+#[endpoint(
+    tags("{name_lit}"),
+    responses(
+        (status_code = 200, description = "删除成功"),
+        (status_code = 404, description = "{name_lit}不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn delete_{m}(
+    id: PathParam<String>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<()>>, AppError> {{
+{parse_id}
+{db}{user_id}
+{body}
+    Ok(Json(ApiResponse::success_with_message(
+        (),
+        i18n::t(depot, "删除成功"),
+    )))
+}}
+"#,
+            name = self.name(),
+            name_lit = self.name_lit(),
+            parse_id = self.parse_id(),
+            db = Self::db_handle(),
+        )
+    }
+
+    fn find_helper(&self) -> String {
+        let m = self.module_name();
+        let filter = if self.audit.deleted_time {
+            format!("\n        .filter({}::Column::DeletedTime.is_null())", m)
+        } else {
+            String::new()
+        };
+        format!(
+            r#"
+async fn find_{m}(db: &DatabaseConnection, id: Uuid) -> Result<{m}::Model, AppError> {{
+    {m}::Entity::find_by_id(id){filter}
+        .one(db)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?
+        .ok_or(AppError::NotFound("{name_lit}不存在".to_string()))
+}}
+"#,
+            name_lit = self.name_lit(),
+        )
+    }
+
+    fn response_helper(&self) -> String {
+        let mut fields = String::from("        id: record.id.to_string(),\n");
+        for f in &self.fields {
+            let source = format!("record.{}", f.ident);
+            let value = match f.kind {
+                k if k.is_copy() => source,
+                Kind::String | Kind::Json => format!("{}.clone()", source),
+                k if f.nullable => format!("{}.as_ref().map(|v| {})", source, k.to_dto("v")),
+                k => k.to_dto(&source),
+            };
+            fields.push_str(&format!("        {}: {},\n", f.ident, value));
+        }
+        if self.audit.created_time {
+            fields.push_str("        created_time: record.created_time.format(\"%Y-%m-%d %H:%M:%S\").to_string(),\n");
+        }
+        if self.audit.updated_time {
+            fields.push_str("        updated_time: record.updated_time.format(\"%Y-%m-%d %H:%M:%S\").to_string(),\n");
+        }
+        format!(
+            "\nfn to_response(record: &{m}::Model) -> {p}Response {{\n    {p}Response {{\n{fields}    }}\n}}\n",
+            m = self.module_name(),
+            p = self.pascal,
+        )
+    }
+
+    /// 返回菜单ID和插入脚本
+    fn menu_sql(&self) -> (Uuid, String) {
+        let menu_id = Uuid::new_v4();
+        let prefix = format!("{}:{}", self.options.permission_prefix, self.camel);
+        let buttons = [("新增", "add"), ("编辑", "edit"), ("删除", "delete")]
+            .iter()
+            .map(|(label, action)| (Uuid::new_v4(), *label, *action))
+            .collect::<Vec<_>>();
+
+        let mut out = format!(
+            "-- {name}菜单和按钮权限（由代码生成器生成）\n\
+             INSERT INTO menus (id, parent_id, name, menu_type, path, component, icon, permission, sort, is_show)\n\
+             VALUES (\n    '{menu_id}'::UUID,\n    '{parent}'::UUID,\n    '{name}',\n    'menu',\n    '/{pp}/{camel}',\n    '/views/{pp}/{camel}/index',\n    NULL,\n    '{prefix}:list',\n    99,\n    TRUE\n)\nON CONFLICT DO NOTHING;\n\n",
+            name = sql_escape(self.name()),
+            parent = self.options.parent_menu_id,
+            pp = self.options.permission_prefix,
+            camel = self.camel,
+        );
+
+        out.push_str("INSERT INTO menus (id, parent_id, name, menu_type, path, component, icon, permission, sort, is_show)\nVALUES\n");
+        let rows: Vec<String> = buttons
+            .iter()
+            .enumerate()
+            .map(|(i, (id, label, action))| {
+                format!(
+                    "    ('{}'::UUID, '{}'::UUID, '{}{}', 'button', NULL, NULL, NULL, '{}:{}', {}, FALSE)",
+                    id,
+                    menu_id,
+                    label,
+                    sql_escape(self.name()),
+                    prefix,
+                    action,
+                    i + 1
+                )
+            })
+            .collect();
+        out.push_str(&rows.join(",\n"));
+        out.push_str("\nON CONFLICT DO NOTHING;\n\nINSERT INTO role_menus (role_id, menu_id)\nVALUES\n");
+
+        // 生成的菜单默认分配给超级管理员
+        let links: Vec<String> = std::iter::once(menu_id)
+            .chain(buttons.iter().map(|(id, _, _)| *id))
+            .map(|id| format!("    ('{}'::UUID, '{}'::UUID)", SUPER_ADMIN_ROLE_ID, id))
+            .collect();
+        out.push_str(&links.join(",\n"));
+        out.push_str("\nON CONFLICT DO NOTHING;\n");
+        (menu_id, out)
+    }
+
+    fn menu_down_sql(&self, menu_id: Uuid) -> String {
+        format!(
+            "-- 按钮和角色菜单关联随菜单级联删除\nDELETE FROM menus WHERE id = '{}'::UUID;\n",
+            menu_id
+        )
+    }
+
+    fn readme(&self) -> String {
+        let m = self.module_name();
+        format!(
+            r#"# {name}模块
+
+由数据表 `{table}` 生成。
+
+1. 将 `src/`、`migrations/` 下的文件复制到项目对应目录
+2. 在 `src/models/mod.rs` 中添加 `pub mod {m};`
+3. 在 `src/modules/mod.rs` 中添加 `pub mod {m};`
+4. 在 `src/routes.rs` 的 `create_router` 中添加 `.push(modules::{m}::routes())`
+5. 将 `migrations/xxx_{m}_menu.sql` 和 `xxx_{m}_menu.down.sql` 中的 `xxx` 替换为下一个迁移版本号，程序启动时自动执行
+6. 在 `src/common/i18n.rs` 的 `MESSAGES` 中补充英文翻译：
+
+```rust
+    // {name}
+    ("{name_lit}不存在", "..."),
+    ("无效的{name_lit}ID", "..."),
+```
+"#,
+            name = self.name(),
+            name_lit = self.name_lit(),
+            table = self.options.table_name,
+        )
+    }
+}
+
+fn push_dto_field(out: &mut String, f: &Field, ty: String) {
+    if f.label != f.column {
+        out.push_str(&format!("    /// {}\n", f.label));
+    }
+    if f.ident.starts_with("r#") {
+        out.push_str(&format!("    #[serde(rename = \"{}\")]\n", f.camel));
+    }
+    out.push_str(&format!("    pub {}: {},\n", f.ident, ty));
+}
+
+/// 按逗号换行，续行缩进 4 格
+fn wrap(list: &str, width: usize) -> String {
+    let mut out = String::new();
+    let mut line = 0;
+    for item in list.split(", ") {
+        if line > 0 && line + item.len() + 2 > width {
+            out.push_str(",\n    ");
+            line = 0;
+        } else if line > 0 {
+            out.push_str(", ");
+            line += 2;
+        }
+        out.push_str(item);
+        line += item.len();
+    }
+    out.push(',');
+    out
+}
+
+fn option(ty: &str, optional: bool) -> String {
+    if optional {
+        format!("Option<{}>", ty)
+    } else {
+        ty.to_string()
+    }
+}
+
+/// 长度限制参数：有最大长度时为 Some(n)，否则为 None
+fn length_arg(f: &Field) -> String {
+    match f.max_length {
+        Some(n) => format!("Some({})", n),
+        None => "None".to_string(),
+    }
+}
+
+fn sql_escape(value: &str) -> String {
+    value.replace('\'', "''")
+}
+
+/// 注释文本压成一行，换行等控制字符替换为空格，用于生成的注释
+fn single_line(text: &str) -> String {
+    text.split(char::is_control)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 转义反斜杠和双引号，用于生成的字符串字面量
+fn lit(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// 列名转为 Rust 标识符，关键字使用原始标识符
+fn rust_ident(column: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+        "mut", "pub", "ref", "return", "static", "struct", "trait", "true", "type", "unsafe",
+        "use", "where", "while", "abstract", "become", "box", "do", "final", "gen", "macro",
+        "override", "priv", "try", "typeof", "unsized", "virtual", "yield",
+    ];
+    let ident = column.to_ascii_lowercase();
+    if KEYWORDS.contains(&ident.as_str()) {
+        format!("r#{}", ident)
+    } else {
+        ident
+    }
+}
+
+pub fn to_pascal(name: &str) -> String {
+    name.trim_start_matches("r#")
+        .split('_')
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            let mut chars = s.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+pub fn to_camel(name: &str) -> String {
+    let pascal = to_pascal(name);
+    let mut chars = pascal.chars();
+    chars
+        .next()
+        .map(|c| c.to_ascii_lowercase().to_string() + chars.as_str())
+        .unwrap_or_default()
+}
+
+const LENGTH_HELPER: &str = r#"
+/// 校验必填文本非空且不超过最大长度
+fn check_length(value: &str, max: Option<usize>, label: &str) -> Result<(), AppError> {
+    if value.trim().is_empty() {
+        return Err(AppError::BadRequest(format!("{}不能为空", label)));
+    }
+    if let Some(max) = max.filter(|max| value.chars().count() > *max) {
+        return Err(AppError::BadRequest(format!("{}长度不能超过{}", label, max)));
+    }
+    Ok(())
+}
+"#;
+
+const PARSE_UUID: &str = r#"
+fn parse_uuid(value: &str, label: &str) -> Result<Uuid, AppError> {
+    Uuid::parse_str(value).map_err(|_| AppError::BadRequest(format!("无效的{}", label)))
+}
+"#;
+
+const PARSE_DATETIME: &str = r#"
+fn parse_datetime(value: &str, label: &str) -> Result<chrono::NaiveDateTime, AppError> {
+    chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+        .map_err(|_| AppError::BadRequest(format!("无效的{}", label)))
+}
+"#;
+
+const PARSE_DATETIME_TZ: &str = r#"
+fn parse_datetime_tz(
+    value: &str,
+    label: &str,
+) -> Result<chrono::DateTime<chrono::FixedOffset>, AppError> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map_err(|_| AppError::BadRequest(format!("无效的{}", label)))
+}
+"#;
+
+const PARSE_DATE: &str = r#"
+fn parse_date(value: &str, label: &str) -> Result<chrono::NaiveDate, AppError> {
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| AppError::BadRequest(format!("无效的{}", label)))
+}
+"#;
+
+const PARSE_TIME: &str = r#"
+fn parse_time(value: &str, label: &str) -> Result<chrono::NaiveTime, AppError> {
+    chrono::NaiveTime::parse_from_str(value, "%H:%M:%S")
+        .map_err(|_| AppError::BadRequest(format!("无效的{}", label)))
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(name: &str, udt_name: &str, nullable: bool) -> ColumnRow {
+        ColumnRow {
+            column_name: name.to_string(),
+            udt_name: udt_name.to_string(),
+            nullable,
+            has_default: false,
+            max_length: None,
+            comment: None,
+            is_primary: false,
+        }
+    }
+
+    fn columns() -> Vec<ColumnRow> {
+        let mut id = column("id", "uuid", false);
+        id.is_primary = true;
+        let mut title = column("title", "varchar", false);
+        title.max_length = Some(100);
+        title.comment = Some("标题\n*/ \"引号\" \\".to_string());
+        vec![
+            id,
+            title,
+            column("type", "int2", false),
+            column("content", "text", true),
+            column("created_time", "timestamp", false),
+            column("created_id", "uuid", true),
+            column("deleted_time", "timestamp", true),
+        ]
+    }
+
+    fn options(name: &str) -> GenerateOptions {
+        GenerateOptions {
+            table_name: "articles".to_string(),
+            module: "article".to_string(),
+            name: name.to_string(),
+            parent_menu_id: Uuid::from_u128(1),
+            permission_prefix: "system".to_string(),
+        }
+    }
+
+    fn content<'a>(files: &'a [GeneratedFile], path: &str) -> &'a str {
+        &files.iter().find(|f| f.path == path).expect(path).content
+    }
+
+    #[test]
+    fn generates_all_module_files() {
+        let files = generate(&options("文章"), Some("文章表".to_string()), &columns()).unwrap();
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "src/models/article.rs",
+                "src/modules/article/mod.rs",
+                "src/modules/article/dto.rs",
+                "src/modules/article/handler.rs",
+                "src/modules/article/routes.rs",
+                "migrations/xxx_article_menu.sql",
+                "migrations/xxx_article_menu.down.sql",
+                "README_article.md",
+            ]
+        );
+
+        let entity = content(&files, "src/models/article.rs");
+        assert!(entity.contains("#[sea_orm(table_name = \"articles\")]"));
+        assert!(entity.contains("pub r#type: i16,"));
+        assert!(entity.contains("pub content: Option<String>,"));
+        assert!(entity.contains("pub deleted_time: Option<DateTime>,"));
+    }
+
+    #[test]
+    fn comments_and_names_are_escaped() {
+        let files = generate(&options("文章\"); //"), None, &columns()).unwrap();
+        for file in files.iter().filter(|f| f.path.ends_with(".rs")) {
+            for line in file.content.lines() {
+                // 名称中的引号未转义时会提前结束字符串字面量（注释中不需要转义）
+                let code = line.split("//").next().unwrap_or_default();
+                assert!(!code.contains("章\""), "{}: {}", file.path, line);
+                assert!(
+                    !line.trim_start().starts_with("*/"),
+                    "{}: {}",
+                    file.path,
+                    line
+                );
+            }
+        }
+        let dto = content(&files, "src/modules/article/dto.rs");
+        assert!(dto.contains("/// 标题 */ \"引号\" \\\n"));
+        let handler = content(&files, "src/modules/article/handler.rs");
+        assert!(handler.contains("文章\\\"); //"));
+    }
+
+    #[test]
+    fn menu_sql_escapes_quotes() {
+        let files = generate(&options("O'Neil"), None, &columns()).unwrap();
+        let sql = content(&files, "migrations/xxx_article_menu.sql");
+        assert!(sql.contains("'O''Neil'"));
+        assert!(sql.contains("'新增O''Neil'"));
+        assert!(!sql.contains("O'Neil"));
+        assert!(sql.contains("'system:article:list'"));
+        assert!(sql.contains(&SUPER_ADMIN_ROLE_ID.to_string()));
+    }
+
+    #[test]
+    fn rejects_unsupported_tables() {
+        let mut no_uuid = columns();
+        no_uuid[0].udt_name = "int8".to_string();
+        assert!(generate(&options("文章"), None, &no_uuid).is_err());
+
+        let mut composite = columns();
+        composite[1].is_primary = true;
+        assert!(generate(&options("文章"), None, &composite).is_err());
+
+        let mut unsupported = columns();
+        unsupported.push(column("tags", "_text", true));
+        assert!(matches!(
+            generate(&options("文章"), None, &unsupported),
+            Err(AppError::BadRequest(message)) if message.contains("tags (_text)")
+        ));
+    }
+
+    #[test]
+    fn case_conversion() {
+        assert_eq!(to_pascal("sys_user_role"), "SysUserRole");
+        assert_eq!(to_pascal("r#type"), "Type");
+        assert_eq!(to_camel("sys_user_role"), "sysUserRole");
+        assert_eq!(to_camel("_leading__double"), "leadingDouble");
+        assert_eq!(rust_ident("Match"), "r#match");
+        assert_eq!(single_line(" a\r\n  b\tc "), "a b c");
+        assert_eq!(lit("a\"b\\c"), "a\\\"b\\\\c");
+    }
+}
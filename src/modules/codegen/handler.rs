@@ -0,0 +1,226 @@
+use salvo::http::header::{HeaderValue, CONTENT_DISPOSITION, CONTENT_TYPE};
+use salvo::prelude::*;
+use sea_orm::DatabaseConnection;
+use std::io::{Cursor, Write};
+use std::sync::Arc;
+use uuid::Uuid;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use super::dto::{CodegenPreview, GeneratedFile, TableInfo};
+use super::generator::{self, GenerateOptions};
+use super::introspect;
+use crate::common::storage::content_disposition;
+use crate::common::{ApiResponse, AppError};
+
+/// 未指定上级菜单时挂到「系统管理」下
+const DEFAULT_PARENT_MENU_ID: &str = "c0000000-0000-0000-0000-000000000100";
+/// 未指定权限前缀时使用的默认值
+const DEFAULT_PERMISSION_PREFIX: &str = "system";
+
+/// 获取可生成代码的数据表列表
+#[endpoint(
+    tags("代码生成"),
+    responses(
+        (status_code = 200, description = "获取成功"),
+        (status_code = 401, description = "未授权"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn get_table_list(depot: &Depot) -> Result<Json<ApiResponse<Vec<TableInfo>>>, AppError> {
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let tables = introspect::list_tables(db.as_ref())
+        .await?
+        .into_iter()
+        .map(|t| TableInfo {
+            table_name: t.table_name,
+            comment: t.comment,
+        })
+        .collect();
+
+    Ok(Json(ApiResponse::success(tables)))
+}
+
+/// 预览生成的代码
+///
+/// 表必须以 UUID 类型的 `id` 列为主键；存在 `created_time`、`updated_time`、`deleted_time`
+/// 等标准审计列时自动维护，有 `deleted_time` 时删除为软删除。
+#[endpoint(
+    tags("代码生成"),
+    parameters(
+        ("table" = String, Query, description = "数据表名"),
+        ("module" = Option<String>, Query, description = "模块名（snake_case），默认为表名去掉末尾的 s"),
+        ("name" = Option<String>, Query, description = "业务名称，默认为表注释"),
+        ("parentMenuId" = Option<String>, Query, description = "上级菜单ID，默认为系统管理"),
+        ("permissionPrefix" = Option<String>, Query, description = "权限标识前缀，默认 system"),
+    ),
+    responses(
+        (status_code = 200, description = "获取成功"),
+        (status_code = 400, description = "参数错误或表结构不支持"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 404, description = "表不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn preview_code(
+    req: &mut Request,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<CodegenPreview>>, AppError> {
+    let (module, files) = generate_files(req, depot).await?;
+    Ok(Json(ApiResponse::success(CodegenPreview { module, files })))
+}
+
+/// 下载生成的代码（zip）
+#[endpoint(
+    tags("代码生成"),
+    parameters(
+        ("table" = String, Query, description = "数据表名"),
+        ("module" = Option<String>, Query, description = "模块名（snake_case），默认为表名去掉末尾的 s"),
+        ("name" = Option<String>, Query, description = "业务名称，默认为表注释"),
+        ("parentMenuId" = Option<String>, Query, description = "上级菜单ID，默认为系统管理"),
+        ("permissionPrefix" = Option<String>, Query, description = "权限标识前缀，默认 system"),
+    ),
+    responses(
+        (status_code = 200, description = "zip 文件"),
+        (status_code = 400, description = "参数错误或表结构不支持"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 404, description = "表不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn download_code(
+    req: &mut Request,
+    depot: &Depot,
+    res: &mut Response,
+) -> Result<(), AppError> {
+    let (module, files) = generate_files(req, depot).await?;
+    let body = zip_files(&files)?;
+
+    res.headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/zip"));
+    if let Ok(value) = HeaderValue::from_str(&content_disposition(&format!("{}.zip", module))) {
+        res.headers_mut().insert(CONTENT_DISPOSITION, value);
+    }
+    res.write_body(body)
+        .map_err(|e| AppError::InternalServerError(e.to_string()))
+}
+
+// ========== 辅助函数 ==========
+
+/// 解析生成参数并读取表结构，预览和下载共用
+async fn generate_files(
+    req: &mut Request,
+    depot: &Depot,
+) -> Result<(String, Vec<GeneratedFile>), AppError> {
+    let table_name = req
+        .query::<String>("table")
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| AppError::BadRequest("表名不能为空".to_string()))?;
+
+    let module = req
+        .query::<String>("module")
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty())
+        .unwrap_or_else(|| default_module(&table_name));
+    if !is_valid_module(&module) {
+        return Err(AppError::BadRequest(
+            "模块名只能包含小写字母、数字和下划线，且以字母开头".to_string(),
+        ));
+    }
+
+    let parent_menu_id = match req
+        .query::<String>("parentMenuId")
+        .filter(|id| !id.is_empty())
+    {
+        Some(id) => Uuid::parse_str(&id)
+            .map_err(|_| AppError::BadRequest("无效的上级菜单ID".to_string()))?,
+        None => Uuid::parse_str(DEFAULT_PARENT_MENU_ID)
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?,
+    };
+
+    let permission_prefix = req
+        .query::<String>("permissionPrefix")
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .unwrap_or_else(|| DEFAULT_PERMISSION_PREFIX.to_string());
+    if !permission_prefix
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
+    {
+        return Err(AppError::BadRequest("无效的权限标识前缀".to_string()));
+    }
+
+    // 业务名称会写入生成的注释和迁移脚本，不允许换行等控制字符
+    let name = req
+        .query::<String>("name")
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty());
+    if name.as_deref().is_some_and(|n| n.contains(char::is_control)) {
+        return Err(AppError::BadRequest("业务名称不能包含换行等控制字符".to_string()));
+    }
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let (table_comment, columns) = introspect::load_table(db.as_ref(), &table_name).await?;
+
+    let name = name
+        .or_else(|| {
+            table_comment
+                .as_deref()
+                .map(str::trim)
+                .filter(|c| !c.is_empty())
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| generator::to_pascal(&module));
+
+    let options = GenerateOptions {
+        table_name,
+        module: module.clone(),
+        name,
+        parent_menu_id,
+        permission_prefix,
+    };
+    let files = generator::generate(&options, table_comment, &columns)?;
+    Ok((module, files))
+}
+
+/// 表名去掉复数后缀作为默认模块名，如 notices -> notice
+fn default_module(table_name: &str) -> String {
+    let name = table_name.to_ascii_lowercase();
+    match name.strip_suffix('s') {
+        Some(stem) if !stem.is_empty() && !stem.ends_with(['s', 'u']) => stem.to_string(),
+        _ => name,
+    }
+}
+
+fn is_valid_module(module: &str) -> bool {
+    module.len() <= 50
+        && module.starts_with(|c: char| c.is_ascii_lowercase())
+        && module
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+fn zip_files(files: &[GeneratedFile]) -> Result<Vec<u8>, AppError> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for file in files {
+        writer
+            .start_file(file.path.as_str(), options)
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+        writer
+            .write_all(file.content.as_bytes())
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+    }
+    writer
+        .finish()
+        .map(Cursor::into_inner)
+        .map_err(|e| AppError::InternalServerError(e.to_string()))
+}
@@ -0,0 +1,94 @@
+// 读取 PostgreSQL 表结构
+
+use sea_orm::{DatabaseConnection, DbBackend, FromQueryResult, Statement};
+
+use crate::common::AppError;
+
+/// 表信息
+#[derive(Debug, FromQueryResult)]
+pub struct TableRow {
+    pub table_name: String,
+    pub comment: Option<String>,
+}
+
+/// 列信息
+#[derive(Debug, Clone, FromQueryResult)]
+pub struct ColumnRow {
+    pub column_name: String,
+    /// 底层类型名，如 varchar、int4、timestamp
+    pub udt_name: String,
+    pub nullable: bool,
+    pub has_default: bool,
+    pub max_length: Option<i32>,
+    pub comment: Option<String>,
+    pub is_primary: bool,
+}
+
+/// 列出 public 模式下的所有表
+pub async fn list_tables(db: &DatabaseConnection) -> Result<Vec<TableRow>, AppError> {
+    let sql = r#"
+        SELECT t.table_name::TEXT AS table_name,
+               obj_description(format('%I.%I', t.table_schema, t.table_name)::regclass, 'pg_class') AS comment
+        FROM information_schema.tables t
+        WHERE t.table_schema = 'public' AND t.table_type = 'BASE TABLE'
+        ORDER BY t.table_name
+    "#;
+    TableRow::find_by_statement(Statement::from_string(DbBackend::Postgres, sql))
+        .all(db)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))
+}
+
+/// 读取表注释和列定义，表不存在时返回 NotFound
+pub async fn load_table(
+    db: &DatabaseConnection,
+    table_name: &str,
+) -> Result<(Option<String>, Vec<ColumnRow>), AppError> {
+    let table = TableRow::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        r#"
+        SELECT t.table_name::TEXT AS table_name,
+               obj_description(format('%I.%I', t.table_schema, t.table_name)::regclass, 'pg_class') AS comment
+        FROM information_schema.tables t
+        WHERE t.table_schema = 'public' AND t.table_type = 'BASE TABLE' AND t.table_name = $1
+        "#,
+        [table_name.into()],
+    ))
+    .one(db)
+    .await
+    .map_err(|e| AppError::InternalServerError(e.to_string()))?
+    .ok_or_else(|| AppError::NotFound(format!("表 {} 不存在", table_name)))?;
+
+    let columns = ColumnRow::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        r#"
+        SELECT c.column_name::TEXT AS column_name,
+               c.udt_name::TEXT AS udt_name,
+               c.is_nullable = 'YES' AS nullable,
+               c.column_default IS NOT NULL AS has_default,
+               c.character_maximum_length::INT4 AS max_length,
+               col_description(format('%I.%I', c.table_schema, c.table_name)::regclass, c.ordinal_position::INT4) AS comment,
+               EXISTS (
+                   SELECT 1
+                   FROM information_schema.table_constraints tc
+                   JOIN information_schema.key_column_usage k
+                     ON tc.constraint_name = k.constraint_name
+                    AND tc.table_schema = k.table_schema
+                    AND tc.table_name = k.table_name
+                   WHERE tc.constraint_type = 'PRIMARY KEY'
+                     AND tc.table_schema = c.table_schema
+                     AND tc.table_name = c.table_name
+                     AND k.column_name = c.column_name
+               ) AS is_primary
+        FROM information_schema.columns c
+        WHERE c.table_schema = 'public' AND c.table_name = $1
+        ORDER BY c.ordinal_position
+        "#,
+        [table_name.into()],
+    ))
+    .all(db)
+    .await
+    .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    Ok((table.comment, columns))
+}
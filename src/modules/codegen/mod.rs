@@ -0,0 +1,9 @@
+// codegen 模块 - 根据数据表生成 CRUD 模块代码
+
+mod dto;
+mod generator;
+mod handler;
+mod introspect;
+mod routes;
+
+pub use routes::routes;
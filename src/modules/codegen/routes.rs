@@ -0,0 +1,12 @@
+use salvo::prelude::*;
+use crate::common::middleware::{admin_only, auth_middleware};
+use super::handler;
+
+pub fn routes() -> Router {
+    Router::with_path("codegen")
+        .hoop(auth_middleware)
+        .hoop(admin_only)
+        .push(Router::with_path("tables").get(handler::get_table_list))
+        .push(Router::with_path("preview").get(handler::preview_code))
+        .push(Router::with_path("download").get(handler::download_code))
+}
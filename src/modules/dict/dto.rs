@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+use salvo::oapi::ToSchema;
+
+/// 字典项可用的标签样式
+pub const TAG_TYPES: [&str; 6] = ["default", "primary", "success", "info", "warning", "danger"];
+
+/// 创建字典类型请求
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[salvo(schema(example = json!({
+    "code": "sys_notice_type",
+    "name": "通知类型",
+    "description": "公告的通知类型"
+})))]
+pub struct CreateDictTypeRequest {
+    /// 字典编码
+    pub code: String,
+    /// 字典名称
+    pub name: String,
+    /// 描述
+    pub description: Option<String>,
+    /// 状态：1-正常，0-禁用
+    #[serde(default = "default_status")]
+    pub status: i16,
+}
+
+/// 更新字典类型请求
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateDictTypeRequest {
+    /// 字典编码
+    pub code: Option<String>,
+    /// 字典名称
+    pub name: Option<String>,
+    /// 描述
+    pub description: Option<String>,
+    /// 状态：1-正常，0-禁用
+    pub status: Option<i16>,
+}
+
+/// 字典类型响应
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DictTypeResponse {
+    pub id: String,
+    pub code: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub status: i16,
+    pub created_time: String,
+}
+
+/// 创建字典项请求
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[salvo(schema(example = json!({
+    "dictTypeId": "d0000000-0000-0000-0000-000000000001",
+    "label": "正常",
+    "value": "1",
+    "sort": 1,
+    "tagType": "success"
+})))]
+pub struct CreateDictItemRequest {
+    /// 字典类型ID
+    pub dict_type_id: String,
+    /// 显示文本
+    pub label: String,
+    /// 字典值
+    pub value: String,
+    /// 排序
+    #[serde(default)]
+    pub sort: i32,
+    /// 颜色，如 #52c41a
+    pub color: Option<String>,
+    /// 标签样式：default、primary、success、info、warning、danger
+    pub tag_type: Option<String>,
+    /// 是否默认值
+    #[serde(default)]
+    pub is_default: bool,
+    /// 状态：1-正常，0-禁用
+    #[serde(default = "default_status")]
+    pub status: i16,
+    /// 备注
+    pub remark: Option<String>,
+}
+
+/// 更新字典项请求
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateDictItemRequest {
+    /// 显示文本
+    pub label: Option<String>,
+    /// 字典值
+    pub value: Option<String>,
+    /// 排序
+    pub sort: Option<i32>,
+    /// 颜色，传空字符串表示清除
+    pub color: Option<String>,
+    /// 标签样式，传空字符串表示清除
+    pub tag_type: Option<String>,
+    /// 是否默认值
+    pub is_default: Option<bool>,
+    /// 状态：1-正常，0-禁用
+    pub status: Option<i16>,
+    /// 备注
+    pub remark: Option<String>,
+}
+
+/// 字典项响应
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DictItemResponse {
+    pub id: String,
+    pub dict_type_id: String,
+    pub label: String,
+    pub value: String,
+    pub sort: i32,
+    pub color: Option<String>,
+    pub tag_type: Option<String>,
+    pub is_default: bool,
+    pub status: i16,
+    pub remark: Option<String>,
+}
+
+fn default_status() -> i16 {
+    1
+}
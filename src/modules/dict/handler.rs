@@ -0,0 +1,846 @@
+use chrono::Utc;
+use regex::Regex;
+use salvo::oapi::extract::{JsonBody, PathParam, QueryParam};
+use salvo::prelude::*;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
+    sea_query::Expr,
+};
+use std::collections::BTreeMap;
+use std::sync::{Arc, OnceLock};
+use uuid::Uuid;
+
+use super::dto::{
+    CreateDictItemRequest, CreateDictTypeRequest, DictItemResponse, DictTypeResponse,
+    UpdateDictItemRequest, UpdateDictTypeRequest, TAG_TYPES,
+};
+use crate::common::constants::{DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE};
+use crate::common::{i18n, ApiResponse, AppError, PageResponse};
+use crate::models::{dict_item, dict_type};
+
+/// 单次批量获取字典的最大数量
+const MAX_DICT_CODES: usize = 50;
+
+// ========== 字典类型 ==========
+
+/// 获取字典类型列表（分页）
+#[endpoint(
+    tags("数据字典"),
+    parameters(
+        ("keyword" = Option<String>, Query, description = "编码或名称（模糊搜索）"),
+        ("status" = Option<i16>, Query, description = "状态：1-正常，0-禁用"),
+        ("page" = Option<u64>, Query, description = "当前页码，默认1"),
+        ("pageSize" = Option<u64>, Query, description = "每页数量，默认20，最大100"),
+    ),
+    responses(
+        (status_code = 200, description = "获取成功"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn get_dict_type_list(
+    req: &mut Request,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<PageResponse<DictTypeResponse>>>, AppError> {
+    let page = req.query::<u64>("page").unwrap_or(1).max(1);
+    let page_size = req
+        .query::<u64>("pageSize")
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let mut query_builder =
+        dict_type::Entity::find().filter(dict_type::Column::DeletedTime.is_null());
+
+    if let Some(keyword) = req.query::<String>("keyword").filter(|k| !k.is_empty()) {
+        query_builder = query_builder.filter(
+            Condition::any()
+                .add(dict_type::Column::Code.contains(&keyword))
+                .add(dict_type::Column::Name.contains(&keyword)),
+        );
+    }
+    if let Some(status) = req.query::<i16>("status") {
+        query_builder = query_builder.filter(dict_type::Column::Status.eq(status));
+    }
+
+    let total = query_builder
+        .clone()
+        .count(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let types = query_builder
+        .order_by_asc(dict_type::Column::Code)
+        .offset((page - 1) * page_size)
+        .limit(page_size)
+        .all(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let items = types.iter().map(type_to_response).collect();
+    Ok(Json(ApiResponse::success(PageResponse::new(
+        items, total, page, page_size,
+    ))))
+}
+
+/// 获取字典类型详情
+#[endpoint(
+    tags("数据字典"),
+    responses(
+        (status_code = 200, description = "获取成功"),
+        (status_code = 404, description = "字典类型不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn get_dict_type(
+    id: PathParam<String>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<DictTypeResponse>>, AppError> {
+    let type_id = Uuid::parse_str(&id.into_inner())
+        .map_err(|_| AppError::BadRequest("无效的字典类型ID".to_string()))?;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let dict = find_dict_type(db.as_ref(), type_id).await?;
+    Ok(Json(ApiResponse::success(type_to_response(&dict))))
+}
+
+/// 创建字典类型
+#[endpoint(
+    tags("数据字典"),
+    responses(
+        (status_code = 200, description = "创建成功"),
+        (status_code = 400, description = "参数错误"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn create_dict_type(
+    req: JsonBody<CreateDictTypeRequest>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<DictTypeResponse>>, AppError> {
+    let data = req.into_inner();
+    validate_code(&data.code)?;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let user_id = depot
+        .get::<String>("user_id")
+        .ok()
+        .and_then(|s| Uuid::parse_str(s.as_str()).ok());
+
+    ensure_code_available(db.as_ref(), &data.code, None).await?;
+
+    let now = Utc::now().naive_utc();
+    let dict = dict_type::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        code: Set(data.code),
+        name: Set(data.name),
+        description: Set(data.description),
+        status: Set(data.status),
+        created_time: Set(now),
+        created_id: Set(user_id),
+        updated_time: Set(now),
+        updated_id: Set(user_id),
+        deleted_time: Set(None),
+        deleted_id: Set(None),
+    }
+    .insert(db.as_ref())
+    .await
+    .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    Ok(Json(ApiResponse::success_with_message(
+        type_to_response(&dict),
+        i18n::t(depot, "创建成功"),
+    )))
+}
+
+/// 更新字典类型
+#[endpoint(
+    tags("数据字典"),
+    responses(
+        (status_code = 200, description = "更新成功"),
+        (status_code = 400, description = "参数错误"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 404, description = "字典类型不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn update_dict_type(
+    id: PathParam<String>,
+    req: JsonBody<UpdateDictTypeRequest>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<DictTypeResponse>>, AppError> {
+    let type_id = Uuid::parse_str(&id.into_inner())
+        .map_err(|_| AppError::BadRequest("无效的字典类型ID".to_string()))?;
+
+    let data = req.into_inner();
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let user_id = depot
+        .get::<String>("user_id")
+        .ok()
+        .and_then(|s| Uuid::parse_str(s.as_str()).ok());
+
+    let existing = find_dict_type(db.as_ref(), type_id).await?;
+    let mut active_model: dict_type::ActiveModel = existing.into();
+
+    if let Some(code) = data.code {
+        validate_code(&code)?;
+        ensure_code_available(db.as_ref(), &code, Some(type_id)).await?;
+        active_model.code = Set(code);
+    }
+    if let Some(name) = data.name {
+        active_model.name = Set(name);
+    }
+    if data.description.is_some() {
+        active_model.description = Set(data.description);
+    }
+    if let Some(status) = data.status {
+        active_model.status = Set(status);
+    }
+    active_model.updated_time = Set(Utc::now().naive_utc());
+    active_model.updated_id = Set(user_id);
+
+    let updated = active_model
+        .update(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    Ok(Json(ApiResponse::success_with_message(
+        type_to_response(&updated),
+        i18n::t(depot, "更新成功"),
+    )))
+}
+
+/// 删除字典类型（软删除，同时删除其字典项）
+#[endpoint(
+    tags("数据字典"),
+    responses(
+        (status_code = 200, description = "删除成功"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 404, description = "字典类型不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn delete_dict_type(
+    id: PathParam<String>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    let type_id = Uuid::parse_str(&id.into_inner())
+        .map_err(|_| AppError::BadRequest("无效的字典类型ID".to_string()))?;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let user_id = depot
+        .get::<String>("user_id")
+        .ok()
+        .and_then(|s| Uuid::parse_str(s.as_str()).ok());
+
+    find_dict_type(db.as_ref(), type_id).await?;
+
+    let now = Utc::now().naive_utc();
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    dict_type::Entity::update_many()
+        .col_expr(dict_type::Column::DeletedTime, Expr::value(Some(now)))
+        .col_expr(dict_type::Column::DeletedId, Expr::value(user_id))
+        .filter(dict_type::Column::Id.eq(type_id))
+        .exec(&txn)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    dict_item::Entity::update_many()
+        .col_expr(dict_item::Column::DeletedTime, Expr::value(Some(now)))
+        .col_expr(dict_item::Column::DeletedId, Expr::value(user_id))
+        .filter(dict_item::Column::DictTypeId.eq(type_id))
+        .filter(dict_item::Column::DeletedTime.is_null())
+        .exec(&txn)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    Ok(Json(ApiResponse::success_with_message(
+        (),
+        i18n::t(depot, "删除成功"),
+    )))
+}
+
+// ========== 字典项 ==========
+
+/// 获取字典项列表
+#[endpoint(
+    tags("数据字典"),
+    parameters(
+        ("typeId" = Option<String>, Query, description = "字典类型ID"),
+        ("typeCode" = Option<String>, Query, description = "字典编码，与 typeId 二选一"),
+    ),
+    responses(
+        (status_code = 200, description = "获取成功"),
+        (status_code = 400, description = "参数错误"),
+        (status_code = 404, description = "字典类型不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn get_dict_item_list(
+    req: &mut Request,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<Vec<DictItemResponse>>>, AppError> {
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let dict = if let Some(type_id) = req.query::<String>("typeId") {
+        let type_id = Uuid::parse_str(&type_id)
+            .map_err(|_| AppError::BadRequest("无效的字典类型ID".to_string()))?;
+        find_dict_type(db.as_ref(), type_id).await?
+    } else if let Some(code) = req.query::<String>("typeCode") {
+        dict_type::Entity::find()
+            .filter(dict_type::Column::Code.eq(code))
+            .filter(dict_type::Column::DeletedTime.is_null())
+            .one(db.as_ref())
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?
+            .ok_or(AppError::NotFound("字典类型不存在".to_string()))?
+    } else {
+        return Err(AppError::BadRequest("请指定字典类型".to_string()));
+    };
+
+    let items = dict_item::Entity::find()
+        .filter(dict_item::Column::DictTypeId.eq(dict.id))
+        .filter(dict_item::Column::DeletedTime.is_null())
+        .order_by_asc(dict_item::Column::Sort)
+        .all(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    Ok(Json(ApiResponse::success(
+        items.iter().map(item_to_response).collect(),
+    )))
+}
+
+/// 创建字典项
+#[endpoint(
+    tags("数据字典"),
+    responses(
+        (status_code = 200, description = "创建成功"),
+        (status_code = 400, description = "参数错误"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn create_dict_item(
+    req: JsonBody<CreateDictItemRequest>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<DictItemResponse>>, AppError> {
+    let data = req.into_inner();
+    let type_id = Uuid::parse_str(&data.dict_type_id)
+        .map_err(|_| AppError::BadRequest("无效的字典类型ID".to_string()))?;
+    validate_style(data.color.as_deref(), data.tag_type.as_deref())?;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let user_id = depot
+        .get::<String>("user_id")
+        .ok()
+        .and_then(|s| Uuid::parse_str(s.as_str()).ok());
+
+    find_dict_type(db.as_ref(), type_id).await?;
+    ensure_value_available(db.as_ref(), type_id, &data.value, None).await?;
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    if data.is_default {
+        clear_default(&txn, type_id).await?;
+    }
+
+    let now = Utc::now().naive_utc();
+    let item = dict_item::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        dict_type_id: Set(type_id),
+        label: Set(data.label),
+        value: Set(data.value),
+        sort: Set(data.sort),
+        color: Set(data.color.filter(|c| !c.is_empty())),
+        tag_type: Set(data.tag_type.filter(|t| !t.is_empty())),
+        is_default: Set(data.is_default),
+        status: Set(data.status),
+        remark: Set(data.remark),
+        created_time: Set(now),
+        created_id: Set(user_id),
+        updated_time: Set(now),
+        updated_id: Set(user_id),
+        deleted_time: Set(None),
+        deleted_id: Set(None),
+    }
+    .insert(&txn)
+    .await
+    .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    Ok(Json(ApiResponse::success_with_message(
+        item_to_response(&item),
+        i18n::t(depot, "创建成功"),
+    )))
+}
+
+/// 更新字典项
+#[endpoint(
+    tags("数据字典"),
+    responses(
+        (status_code = 200, description = "更新成功"),
+        (status_code = 400, description = "参数错误"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 404, description = "字典项不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn update_dict_item(
+    id: PathParam<String>,
+    req: JsonBody<UpdateDictItemRequest>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<DictItemResponse>>, AppError> {
+    let item_id = Uuid::parse_str(&id.into_inner())
+        .map_err(|_| AppError::BadRequest("无效的字典项ID".to_string()))?;
+
+    let data = req.into_inner();
+    validate_style(data.color.as_deref(), data.tag_type.as_deref())?;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let user_id = depot
+        .get::<String>("user_id")
+        .ok()
+        .and_then(|s| Uuid::parse_str(s.as_str()).ok());
+
+    let existing = dict_item::Entity::find_by_id(item_id)
+        .filter(dict_item::Column::DeletedTime.is_null())
+        .one(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?
+        .ok_or(AppError::NotFound("字典项不存在".to_string()))?;
+    let type_id = existing.dict_type_id;
+
+    if let Some(value) = &data.value {
+        ensure_value_available(db.as_ref(), type_id, value, Some(item_id)).await?;
+    }
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    if data.is_default == Some(true) {
+        clear_default(&txn, type_id).await?;
+    }
+
+    let mut active_model: dict_item::ActiveModel = existing.into();
+    if let Some(label) = data.label {
+        active_model.label = Set(label);
+    }
+    if let Some(value) = data.value {
+        active_model.value = Set(value);
+    }
+    if let Some(sort) = data.sort {
+        active_model.sort = Set(sort);
+    }
+    if let Some(color) = data.color {
+        active_model.color = Set(Some(color).filter(|c| !c.is_empty()));
+    }
+    if let Some(tag_type) = data.tag_type {
+        active_model.tag_type = Set(Some(tag_type).filter(|t| !t.is_empty()));
+    }
+    if let Some(is_default) = data.is_default {
+        active_model.is_default = Set(is_default);
+    }
+    if let Some(status) = data.status {
+        active_model.status = Set(status);
+    }
+    if data.remark.is_some() {
+        active_model.remark = Set(data.remark);
+    }
+    active_model.updated_time = Set(Utc::now().naive_utc());
+    active_model.updated_id = Set(user_id);
+
+    let updated = active_model
+        .update(&txn)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    Ok(Json(ApiResponse::success_with_message(
+        item_to_response(&updated),
+        i18n::t(depot, "更新成功"),
+    )))
+}
+
+/// 删除字典项（软删除）
+#[endpoint(
+    tags("数据字典"),
+    responses(
+        (status_code = 200, description = "删除成功"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 404, description = "字典项不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn delete_dict_item(
+    id: PathParam<String>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    let item_id = Uuid::parse_str(&id.into_inner())
+        .map_err(|_| AppError::BadRequest("无效的字典项ID".to_string()))?;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let user_id = depot
+        .get::<String>("user_id")
+        .ok()
+        .and_then(|s| Uuid::parse_str(s.as_str()).ok());
+
+    let existing = dict_item::Entity::find_by_id(item_id)
+        .filter(dict_item::Column::DeletedTime.is_null())
+        .one(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?
+        .ok_or(AppError::NotFound("字典项不存在".to_string()))?;
+
+    let mut active_model: dict_item::ActiveModel = existing.into();
+    active_model.deleted_time = Set(Some(Utc::now().naive_utc()));
+    active_model.deleted_id = Set(user_id);
+    active_model
+        .update(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    Ok(Json(ApiResponse::success_with_message(
+        (),
+        i18n::t(depot, "删除成功"),
+    )))
+}
+
+/// 批量获取字典数据（供前端缓存）
+///
+/// 只返回启用的字典类型和字典项，结果为 字典编码 -> 字典项列表，
+/// 不存在或已禁用的编码对应空列表。
+#[endpoint(
+    tags("数据字典"),
+    parameters(
+        ("codes" = String, Query, description = "字典编码，多个用逗号分隔"),
+    ),
+    responses(
+        (status_code = 200, description = "获取成功"),
+        (status_code = 400, description = "参数错误"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn get_dict_data(
+    codes: QueryParam<String, true>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<BTreeMap<String, Vec<DictItemResponse>>>>, AppError> {
+    let codes: Vec<String> = codes
+        .into_inner()
+        .split(',')
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+        .collect();
+    if codes.is_empty() {
+        return Err(AppError::BadRequest("请指定字典类型".to_string()));
+    }
+    if codes.len() > MAX_DICT_CODES {
+        return Err(AppError::BadRequest(format!(
+            "一次最多获取 {} 个字典",
+            MAX_DICT_CODES
+        )));
+    }
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let types = dict_type::Entity::find()
+        .filter(dict_type::Column::Code.is_in(codes.clone()))
+        .filter(dict_type::Column::DeletedTime.is_null())
+        .filter(dict_type::Column::Status.eq(1))
+        .all(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let items = dict_item::Entity::find()
+        .filter(dict_item::Column::DictTypeId.is_in(types.iter().map(|t| t.id).collect::<Vec<_>>()))
+        .filter(dict_item::Column::DeletedTime.is_null())
+        .filter(dict_item::Column::Status.eq(1))
+        .order_by_asc(dict_item::Column::Sort)
+        .all(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let mut result: BTreeMap<String, Vec<DictItemResponse>> =
+        codes.into_iter().map(|c| (c, Vec::new())).collect();
+    for t in &types {
+        result.insert(
+            t.code.clone(),
+            items
+                .iter()
+                .filter(|i| i.dict_type_id == t.id)
+                .map(item_to_response)
+                .collect(),
+        );
+    }
+
+    Ok(Json(ApiResponse::success(result)))
+}
+
+// ========== 辅助函数 ==========
+
+async fn find_dict_type(
+    db: &DatabaseConnection,
+    type_id: Uuid,
+) -> Result<dict_type::Model, AppError> {
+    dict_type::Entity::find_by_id(type_id)
+        .filter(dict_type::Column::DeletedTime.is_null())
+        .one(db)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?
+        .ok_or(AppError::NotFound("字典类型不存在".to_string()))
+}
+
+/// 校验字典编码格式：字母开头，允许字母、数字和下划线
+fn validate_code(code: &str) -> Result<(), AppError> {
+    static CODE_PATTERN: OnceLock<Regex> = OnceLock::new();
+    let pattern =
+        CODE_PATTERN.get_or_init(|| Regex::new(r"^[A-Za-z][A-Za-z0-9_]{0,99}$").unwrap());
+    if pattern.is_match(code) {
+        Ok(())
+    } else {
+        Err(AppError::BadRequest("字典编码格式无效".to_string()))
+    }
+}
+
+/// 校验颜色（#RGB / #RRGGBB）和标签样式，空字符串视为清除
+fn validate_style(color: Option<&str>, tag_type: Option<&str>) -> Result<(), AppError> {
+    static COLOR_PATTERN: OnceLock<Regex> = OnceLock::new();
+    let pattern = COLOR_PATTERN
+        .get_or_init(|| Regex::new(r"^#([0-9A-Fa-f]{3}|[0-9A-Fa-f]{6})$").unwrap());
+
+    if let Some(color) = color.filter(|c| !c.is_empty()) {
+        if !pattern.is_match(color) {
+            return Err(AppError::BadRequest("无效的颜色值".to_string()));
+        }
+    }
+    if let Some(tag_type) = tag_type.filter(|t| !t.is_empty()) {
+        if !TAG_TYPES.contains(&tag_type) {
+            return Err(AppError::BadRequest("无效的标签样式".to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// 字典编码在所有记录（含已删除）中唯一
+async fn ensure_code_available(
+    db: &DatabaseConnection,
+    code: &str,
+    exclude_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    let mut query = dict_type::Entity::find().filter(dict_type::Column::Code.eq(code));
+    if let Some(id) = exclude_id {
+        query = query.filter(dict_type::Column::Id.ne(id));
+    }
+    let exists = query
+        .count(db)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?
+        > 0;
+    if exists {
+        return Err(AppError::BadRequest(format!("字典编码 {} 已存在", code)));
+    }
+    Ok(())
+}
+
+/// 同一字典类型下字典值唯一
+async fn ensure_value_available(
+    db: &DatabaseConnection,
+    type_id: Uuid,
+    value: &str,
+    exclude_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    let mut query = dict_item::Entity::find()
+        .filter(dict_item::Column::DictTypeId.eq(type_id))
+        .filter(dict_item::Column::Value.eq(value))
+        .filter(dict_item::Column::DeletedTime.is_null());
+    if let Some(id) = exclude_id {
+        query = query.filter(dict_item::Column::Id.ne(id));
+    }
+    let exists = query
+        .count(db)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?
+        > 0;
+    if exists {
+        return Err(AppError::BadRequest(format!("字典值 {} 已存在", value)));
+    }
+    Ok(())
+}
+
+/// 取消同一字典类型下其它字典项的默认标记
+async fn clear_default<C: ConnectionTrait>(db: &C, type_id: Uuid) -> Result<(), AppError> {
+    dict_item::Entity::update_many()
+        .col_expr(dict_item::Column::IsDefault, Expr::value(false))
+        .filter(dict_item::Column::DictTypeId.eq(type_id))
+        .filter(dict_item::Column::IsDefault.eq(true))
+        .exec(db)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+    Ok(())
+}
+
+fn type_to_response(t: &dict_type::Model) -> DictTypeResponse {
+    DictTypeResponse {
+        id: t.id.to_string(),
+        code: t.code.clone(),
+        name: t.name.clone(),
+        description: t.description.clone(),
+        status: t.status,
+        created_time: t.created_time.format("%Y-%m-%d %H:%M:%S").to_string(),
+    }
+}
+
+fn item_to_response(i: &dict_item::Model) -> DictItemResponse {
+    DictItemResponse {
+        id: i.id.to_string(),
+        dict_type_id: i.dict_type_id.to_string(),
+        label: i.label.clone(),
+        value: i.value.clone(),
+        sort: i.sort,
+        color: i.color.clone(),
+        tag_type: i.tag_type.clone(),
+        is_default: i.is_default,
+        status: i.status,
+        remark: i.remark.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::testing;
+    use salvo::prelude::*;
+    use salvo::test::{ResponseExt, TestClient};
+    use sea_orm::ConnectionTrait;
+    use serde_json::{json, Value};
+    use uuid::Uuid;
+
+    const GENDER_TYPE_ID: &str = "d0000000-0000-0000-0000-000000000002";
+
+    #[tokio::test]
+    async fn bulk_fetch_returns_enabled_items_in_order() {
+        let Some(db) = testing::TestDb::migrated().await else {
+            return;
+        };
+        let app = testing::service(super::super::routes::routes(), Some(db.arc()));
+        let item = json!({
+            "dictTypeId": GENDER_TYPE_ID,
+            "label": "保密",
+            "value": "9",
+            "sort": 0,
+            "tagType": "default",
+            "isDefault": true,
+        });
+
+        let editor = testing::access_token(Uuid::new_v4(), Uuid::new_v4(), "editor");
+        let res = TestClient::post("http://127.0.0.1/dict/item")
+            .bearer_auth(&editor)
+            .json(&item)
+            .send(&app)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::FORBIDDEN));
+
+        let admin = testing::admin_token();
+        let mut invalid = item.clone();
+        invalid["color"] = json!("green");
+        let res = TestClient::post("http://127.0.0.1/dict/item")
+            .bearer_auth(&admin)
+            .json(&invalid)
+            .send(&app)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::BAD_REQUEST));
+
+        let res = TestClient::post("http://127.0.0.1/dict/item")
+            .bearer_auth(&admin)
+            .json(&item)
+            .send(&app)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+
+        db.execute_unprepared(
+            "INSERT INTO dict_items (dict_type_id, label, value, status)
+             VALUES ('d0000000-0000-0000-0000-000000000001', '锁定', '2', 0);
+             UPDATE dict_types SET status = 0 WHERE code = 'sys_menu_type';",
+        )
+        .await
+        .unwrap();
+
+        let mut res = TestClient::get(
+            "http://127.0.0.1/dict/data?codes=sys_user_gender,sys_status,sys_menu_type,missing",
+        )
+        .bearer_auth(&editor)
+        .send(&app)
+        .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        let data = res.take_json::<Value>().await.unwrap()["data"].take();
+        let values = |code: &str| -> Vec<String> {
+            data[code]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|i| i["value"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        assert_eq!(values("sys_user_gender"), ["9", "0", "1", "2"]);
+        // 新的默认项取消了原有的默认标记
+        let defaults: Vec<&Value> = data["sys_user_gender"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|i| i["isDefault"] == true)
+            .collect();
+        assert_eq!(defaults.len(), 1);
+        assert_eq!(defaults[0]["value"], "9");
+        assert_eq!(values("sys_status"), ["1", "0"]);
+        assert!(values("sys_menu_type").is_empty());
+        assert!(values("missing").is_empty());
+    }
+}
@@ -0,0 +1,7 @@
+// dict 模块 - 数据字典（字典类型与字典项）
+
+mod dto;
+mod handler;
+mod routes;
+
+pub use routes::routes;
@@ -0,0 +1,35 @@
+use salvo::prelude::*;
+use crate::common::middleware::{admin_only, auth_middleware};
+use super::handler;
+
+pub fn routes() -> Router {
+    Router::with_path("dict")
+        .hoop(auth_middleware)
+        .push(Router::with_path("data").get(handler::get_dict_data))
+        .push(Router::with_path("type/list").get(handler::get_dict_type_list))
+        .push(Router::with_path("type/<id>").get(handler::get_dict_type))
+        .push(Router::with_path("item/list").get(handler::get_dict_item_list))
+        // 字典维护仅管理员可用
+        .push(
+            Router::new()
+                .hoop(admin_only)
+                .push(
+                    Router::with_path("type")
+                        .post(handler::create_dict_type)
+                        .push(
+                            Router::with_path("<id>")
+                                .put(handler::update_dict_type)
+                                .delete(handler::delete_dict_type)
+                        )
+                )
+                .push(
+                    Router::with_path("item")
+                        .post(handler::create_dict_item)
+                        .push(
+                            Router::with_path("<id>")
+                                .put(handler::update_dict_item)
+                                .delete(handler::delete_dict_item)
+                        )
+                )
+        )
+}
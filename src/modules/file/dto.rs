@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+use salvo::oapi::ToSchema;
+
+/// 文件响应
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FileResponse {
+    pub id: String,
+    pub file_name: String,
+    pub content_type: String,
+    /// 文件大小（字节）
+    pub file_size: i64,
+    pub sha256: String,
+    pub biz_type: String,
+    pub backend: String,
+    /// 带签名的下载地址，有效期由 STORAGE_URL_EXPIRE_SECONDS 配置
+    pub url: String,
+    pub created_time: String,
+}
@@ -0,0 +1,683 @@
+use chrono::Utc;
+use salvo::http::header::{
+    HeaderValue, CACHE_CONTROL, CONTENT_DISPOSITION, CONTENT_SECURITY_POLICY, CONTENT_TYPE,
+    X_CONTENT_TYPE_OPTIONS,
+};
+use salvo::oapi::extract::PathParam;
+use salvo::prelude::*;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbBackend, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set, Statement, TransactionTrait,
+};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::dto::FileResponse;
+use crate::common::constants::{API_PREFIX, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE};
+use crate::common::storage::{self, content_disposition, StorageService};
+use crate::common::{i18n, middleware, ApiResponse, AppError, PageResponse};
+use crate::models::file;
+
+/// 未指定业务类型时使用的默认值
+const DEFAULT_BIZ_TYPE: &str = "common";
+/// 用户头像
+pub const BIZ_TYPE_AVATAR: &str = "avatar";
+/// 用户头像缩略图
+pub const BIZ_TYPE_AVATAR_THUMB: &str = "avatar_thumb";
+/// 上传接口可指定的业务类型，头像类文件只能由头像上传接口生成
+const UPLOAD_BIZ_TYPES: [&str; 3] = [DEFAULT_BIZ_TYPE, "notice", "attachment"];
+
+/// 上传文件
+///
+/// 使用 multipart/form-data，文件字段名为 `file`，可选字段 `bizType` 标记业务类型，
+/// 可选值为 common（默认）、notice、attachment。
+/// 内容相同的文件只存储一份，各自保留独立的元数据记录。
+#[endpoint(
+    tags("文件管理"),
+    responses(
+        (status_code = 200, description = "上传成功"),
+        (status_code = 400, description = "文件为空、过大、类型或业务类型不允许"),
+        (status_code = 401, description = "未授权"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn upload_file(
+    req: &mut Request,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<FileResponse>>, AppError> {
+    let storage = storage::storage()?;
+
+    let biz_type = req
+        .form::<String>("bizType")
+        .await
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| DEFAULT_BIZ_TYPE.to_string());
+    if !UPLOAD_BIZ_TYPES.contains(&biz_type.as_str()) {
+        return Err(AppError::BadRequest(format!("不支持的业务类型: {}", biz_type)));
+    }
+
+    let part = req
+        .file("file")
+        .await
+        .ok_or_else(|| AppError::BadRequest("请选择要上传的文件".to_string()))?;
+    let file_name = part
+        .name()
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty())
+        .ok_or_else(|| AppError::BadRequest("文件名不能为空".to_string()))?;
+    let temp_path = part.path().clone();
+    let file_size = part.size();
+
+    if file_size == 0 {
+        return Err(AppError::BadRequest("文件内容为空".to_string()));
+    }
+    if file_size > storage.max_file_size {
+        return Err(AppError::BadRequest(format!(
+            "文件大小不能超过 {}MB",
+            storage.max_file_size / 1024 / 1024
+        )));
+    }
+    let extension = validate_extension(&storage, &file_name)?;
+
+    let data = tokio::fs::read(&temp_path)
+        .await
+        .map_err(|e| AppError::InternalServerError(format!("读取上传文件失败: {}", e)))?;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let model = store_file(
+        db.as_ref(),
+        &storage,
+        NewFile {
+            file_name,
+            extension,
+            data: &data,
+            biz_type,
+        },
+        current_user_id(depot),
+    )
+    .await?;
+
+    Ok(Json(ApiResponse::success_with_message(
+        to_response(&storage, &model).await?,
+        i18n::t(depot, "上传成功"),
+    )))
+}
+
+/// 获取文件列表（分页）
+///
+/// 管理员可查看全部文件，其他用户只能查看自己上传的文件。
+#[endpoint(
+    tags("文件管理"),
+    parameters(
+        ("keyword" = Option<String>, Query, description = "文件名（模糊搜索）"),
+        ("bizType" = Option<String>, Query, description = "业务类型"),
+        ("page" = Option<u64>, Query, description = "当前页码，默认1"),
+        ("pageSize" = Option<u64>, Query, description = "每页数量，默认20，最大100"),
+    ),
+    responses(
+        (status_code = 200, description = "获取成功"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn get_file_list(
+    req: &mut Request,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<PageResponse<FileResponse>>>, AppError> {
+    let page = req.query::<u64>("page").unwrap_or(1).max(1);
+    let page_size = req
+        .query::<u64>("pageSize")
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+
+    let storage = storage::storage()?;
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let mut query_builder = file::Entity::find().filter(file::Column::DeletedTime.is_null());
+    if !middleware::is_admin(depot) {
+        query_builder = query_builder.filter(file::Column::CreatedId.eq(current_user_id(depot)));
+    }
+
+    if let Some(keyword) = req.query::<String>("keyword").filter(|k| !k.is_empty()) {
+        query_builder = query_builder.filter(file::Column::FileName.contains(&keyword));
+    }
+    if let Some(biz_type) = req.query::<String>("bizType").filter(|t| !t.is_empty()) {
+        query_builder = query_builder.filter(file::Column::BizType.eq(biz_type));
+    }
+
+    let total = query_builder
+        .clone()
+        .count(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let files = query_builder
+        .order_by_desc(file::Column::CreatedTime)
+        .offset((page - 1) * page_size)
+        .limit(page_size)
+        .all(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let mut items = Vec::with_capacity(files.len());
+    for model in &files {
+        items.push(to_response(&storage, model).await?);
+    }
+    Ok(Json(ApiResponse::success(PageResponse::new(
+        items, total, page, page_size,
+    ))))
+}
+
+/// 获取文件详情（含下载地址）
+#[endpoint(
+    tags("文件管理"),
+    responses(
+        (status_code = 200, description = "获取成功"),
+        (status_code = 404, description = "文件不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn get_file(
+    id: PathParam<String>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<FileResponse>>, AppError> {
+    let file_id = parse_file_id(&id.into_inner())?;
+
+    let storage = storage::storage()?;
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let model = find_owned_file(db.as_ref(), depot, file_id).await?;
+    Ok(Json(ApiResponse::success(
+        to_response(&storage, &model).await?,
+    )))
+}
+
+/// 删除文件
+///
+/// 删除元数据记录，存储对象在没有其他文件引用时一并删除。管理员可删除任意文件，其他用户只能删除自己上传的文件。
+#[endpoint(
+    tags("文件管理"),
+    responses(
+        (status_code = 200, description = "删除成功"),
+        (status_code = 404, description = "文件不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn delete_file(
+    id: PathParam<String>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    let file_id = parse_file_id(&id.into_inner())?;
+
+    let storage = storage::storage()?;
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let existing = find_owned_file(db.as_ref(), depot, file_id).await?;
+    remove_file(db.as_ref(), &storage, existing, current_user_id(depot)).await?;
+
+    Ok(Json(ApiResponse::success_with_message(
+        (),
+        i18n::t(depot, "删除成功"),
+    )))
+}
+
+/// 下载文件
+///
+/// 通过文件详情返回的签名地址访问，无需登录。
+#[endpoint(
+    tags("文件管理"),
+    parameters(
+        ("expires" = i64, Query, description = "过期时间（Unix 时间戳，秒）"),
+        ("signature" = String, Query, description = "下载签名"),
+    ),
+    responses(
+        (status_code = 200, description = "文件内容"),
+        (status_code = 403, description = "签名无效或已过期"),
+        (status_code = 404, description = "文件不存在")
+    )
+)]
+pub async fn download_file(
+    id: PathParam<String>,
+    req: &mut Request,
+    depot: &Depot,
+    res: &mut Response,
+) -> Result<(), AppError> {
+    let file_id = parse_file_id(&id.into_inner())?;
+
+    let storage = storage::storage()?;
+    let expires = req.query::<i64>("expires").unwrap_or_default();
+    let signature = req.query::<String>("signature").unwrap_or_default();
+    if !storage.verify(file_id, expires, &signature) {
+        return Err(AppError::Forbidden("下载链接无效或已过期".to_string()));
+    }
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let model = find_file(db.as_ref(), file_id).await?;
+    let data = storage.backend().get(&model.storage_key).await?;
+
+    if let Ok(value) = HeaderValue::from_str(&content_disposition(&model.file_name)) {
+        res.headers_mut().insert(CONTENT_DISPOSITION, value);
+    }
+    write_file(res, &model, data)
+}
+
+/// 获取用户头像
+///
+/// 头像需要直接用于 img 标签，因此无需登录和签名，只能访问头像上传接口生成的 PNG 图片。
+#[endpoint(
+    tags("文件管理"),
+    responses(
+        (status_code = 200, description = "图片内容"),
+        (status_code = 404, description = "头像不存在")
+    )
+)]
+pub async fn get_avatar(
+    id: PathParam<String>,
+    depot: &Depot,
+    res: &mut Response,
+) -> Result<(), AppError> {
+    let file_id = parse_file_id(&id.into_inner())?;
+
+    let storage = storage::storage()?;
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let model = find_file(db.as_ref(), file_id).await?;
+    if !is_avatar_file(&model) {
+        return Err(AppError::NotFound("文件不存在".to_string()));
+    }
+    let data = storage.backend().get(&model.storage_key).await?;
+
+    // 头像每次更换都会生成新的文件ID，同一地址的内容不会变化
+    res.headers_mut().insert(
+        CACHE_CONTROL,
+        HeaderValue::from_static("public, max-age=86400"),
+    );
+    write_file(res, &model, data)
+}
+
+// ========== 辅助函数 ==========
+
+/// 待保存的文件内容
+pub struct NewFile<'a> {
+    pub file_name: String,
+    /// 小写扩展名，用于推断 MIME 类型和生成存储键
+    pub extension: String,
+    pub data: &'a [u8],
+    pub biz_type: String,
+}
+
+/// 写入存储并保存元数据，已存在相同内容的文件时复用存储对象
+///
+/// 上传在加锁之前完成，内容锁只覆盖查找可复用对象和插入记录，与 `remove_file` 的引用检查和删除互斥。
+/// 新对象使用独立的存储键，其他请求不会引用或删除它；并发上传相同内容时先提交的请求胜出，
+/// 其余请求改为复用胜出者的对象并删除自己上传的对象。
+pub async fn store_file(
+    db: &DatabaseConnection,
+    storage: &StorageService,
+    file: NewFile<'_>,
+    user_id: Option<Uuid>,
+) -> Result<file::Model, AppError> {
+    let sha256 = hex::encode(Sha256::digest(file.data));
+    // 以文件扩展名推断类型，不信任客户端声明的 Content-Type
+    let content_type = mime_guess::from_ext(&file.extension)
+        .first_or_octet_stream()
+        .to_string();
+    let backend = storage.backend().name();
+
+    // 已有相同内容时不再上传；锁内会再次确认，期间对象被删除时补传
+    let uploaded = match find_content(db, backend, &sha256).await? {
+        Some(_) => None,
+        None => Some(upload(storage, &sha256, &file, &content_type).await?),
+    };
+
+    let result = insert_file(
+        db,
+        storage,
+        &file,
+        &content_type,
+        &sha256,
+        uploaded.as_deref(),
+        user_id,
+    )
+    .await;
+
+    // 未被记录引用的上传对象（复用了并发请求的对象，或写入记录失败）随即删除
+    if let Some(key) = uploaded {
+        if !result.as_ref().is_ok_and(|model| model.storage_key == key) {
+            if let Err(e) = storage.backend().delete(&key).await {
+                tracing::warn!("删除未引用的存储对象失败: key={}, error={}", key, e);
+            }
+        }
+    }
+    result
+}
+
+/// 持有内容锁写入文件记录，优先复用已有对象，其次使用本次上传的对象
+async fn insert_file(
+    db: &DatabaseConnection,
+    storage: &StorageService,
+    file: &NewFile<'_>,
+    content_type: &str,
+    sha256: &str,
+    uploaded: Option<&str>,
+    user_id: Option<Uuid>,
+) -> Result<file::Model, AppError> {
+    let backend = storage.backend().name();
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+    lock_content(&txn, backend, sha256).await?;
+
+    let storage_key = match (find_content(&txn, backend, sha256).await?, uploaded) {
+        (Some(existing), _) => existing.storage_key,
+        (None, Some(key)) => key.to_string(),
+        // 加锁前找到的对象已随最后一个引用删除，在锁内补传
+        (None, None) => upload(storage, sha256, file, content_type).await?,
+    };
+
+    let now = Utc::now().naive_utc();
+    let model = file::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        file_name: Set(file.file_name.clone()),
+        storage_key: Set(storage_key),
+        backend: Set(backend.to_string()),
+        content_type: Set(content_type.to_string()),
+        file_size: Set(file.data.len() as i64),
+        sha256: Set(sha256.to_string()),
+        biz_type: Set(file.biz_type.clone()),
+        created_time: Set(now),
+        created_id: Set(user_id),
+        updated_time: Set(now),
+        updated_id: Set(user_id),
+        deleted_time: Set(None),
+        deleted_id: Set(None),
+    }
+    .insert(&txn)
+    .await
+    .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+    Ok(model)
+}
+
+/// 查找当前后端上内容相同的有效文件
+async fn find_content<C: ConnectionTrait>(
+    conn: &C,
+    backend: &str,
+    sha256: &str,
+) -> Result<Option<file::Model>, AppError> {
+    file::Entity::find()
+        .filter(file::Column::Sha256.eq(sha256))
+        .filter(file::Column::Backend.eq(backend))
+        .filter(file::Column::DeletedTime.is_null())
+        .one(conn)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))
+}
+
+/// 以独立的存储键上传对象，返回存储键
+async fn upload(
+    storage: &StorageService,
+    sha256: &str,
+    file: &NewFile<'_>,
+    content_type: &str,
+) -> Result<String, AppError> {
+    let key = format!(
+        "files/{}/{}-{}.{}",
+        &sha256[..2],
+        sha256,
+        Uuid::new_v4().simple(),
+        file.extension
+    );
+    storage.backend().put(&key, file.data, content_type).await?;
+    Ok(key)
+}
+
+/// 删除文件元数据，存储对象在没有其他文件引用时一并删除
+pub async fn remove_file(
+    db: &DatabaseConnection,
+    storage: &StorageService,
+    existing: file::Model,
+    user_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    let storage_key = existing.storage_key.clone();
+    let backend = existing.backend.clone();
+
+    // 持有内容锁直到删除存储对象之后，期间上传相同内容的请求不会复用即将删除的对象
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+    lock_content(&txn, &backend, &existing.sha256).await?;
+
+    let mut active_model: file::ActiveModel = existing.into();
+    active_model.deleted_time = Set(Some(Utc::now().naive_utc()));
+    active_model.deleted_id = Set(user_id);
+    active_model
+        .update(&txn)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let references = file::Entity::find()
+        .filter(file::Column::StorageKey.eq(&storage_key))
+        .filter(file::Column::Backend.eq(&backend))
+        .filter(file::Column::DeletedTime.is_null())
+        .count(&txn)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    // 切换存储后端前上传的文件不在当前后端上，只删除元数据
+    if references == 0 && backend == storage.backend().name() {
+        if let Err(e) = storage.backend().delete(&storage_key).await {
+            tracing::warn!("删除存储对象失败: key={}, error={}", storage_key, e);
+        }
+    }
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))
+}
+
+/// 对同一后端上的相同内容加事务级咨询锁，事务结束时自动释放
+async fn lock_content<C: ConnectionTrait>(
+    conn: &C,
+    backend: &str,
+    sha256: &str,
+) -> Result<(), AppError> {
+    conn.execute(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "SELECT pg_advisory_xact_lock(hashtext($1))",
+        [format!("file:{}:{}", backend, sha256).into()],
+    ))
+    .await
+    .map(|_| ())
+    .map_err(|e| AppError::InternalServerError(e.to_string()))
+}
+
+/// 头像访问地址
+pub fn avatar_url(file_id: Uuid) -> String {
+    format!("/{}/file/avatar/{}", API_PREFIX, file_id)
+}
+
+/// 从头像地址中解析文件ID，外部头像地址返回 None
+pub fn avatar_file_id(url: &str) -> Option<Uuid> {
+    url.strip_prefix('/')
+        .and_then(|url| url.strip_prefix(API_PREFIX))
+        .and_then(|url| url.strip_prefix("/file/avatar/"))
+        .and_then(|id| Uuid::parse_str(id).ok())
+}
+
+/// 是否为头像上传接口生成的头像或缩略图（均为重新编码的 PNG）
+pub fn is_avatar_file(model: &file::Model) -> bool {
+    (model.biz_type == BIZ_TYPE_AVATAR || model.biz_type == BIZ_TYPE_AVATAR_THUMB)
+        && model.content_type == "image/png"
+}
+
+/// 写入文件内容，禁止浏览器嗅探类型和执行内容中的脚本，避免上传的文件在本站点下被当作页面渲染
+fn write_file(res: &mut Response, model: &file::Model, data: Vec<u8>) -> Result<(), AppError> {
+    if let Ok(value) = HeaderValue::from_str(&model.content_type) {
+        res.headers_mut().insert(CONTENT_TYPE, value);
+    }
+    res.headers_mut()
+        .insert(X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"));
+    res.headers_mut().insert(
+        CONTENT_SECURITY_POLICY,
+        HeaderValue::from_static("default-src 'none'"),
+    );
+    res.write_body(data)
+        .map_err(|e| AppError::InternalServerError(e.to_string()))
+}
+
+fn parse_file_id(id: &str) -> Result<Uuid, AppError> {
+    Uuid::parse_str(id).map_err(|_| AppError::BadRequest("无效的文件ID".to_string()))
+}
+
+/// 查找当前用户可管理的文件，非管理员访问他人的文件时视为不存在
+async fn find_owned_file(
+    db: &DatabaseConnection,
+    depot: &Depot,
+    file_id: Uuid,
+) -> Result<file::Model, AppError> {
+    let model = find_file(db, file_id).await?;
+    if !middleware::is_admin(depot) && model.created_id != current_user_id(depot) {
+        return Err(AppError::NotFound("文件不存在".to_string()));
+    }
+    Ok(model)
+}
+
+fn current_user_id(depot: &Depot) -> Option<Uuid> {
+    depot
+        .get::<String>("user_id")
+        .ok()
+        .and_then(|s| Uuid::parse_str(s.as_str()).ok())
+}
+
+pub async fn find_file(db: &DatabaseConnection, file_id: Uuid) -> Result<file::Model, AppError> {
+    file::Entity::find_by_id(file_id)
+        .filter(file::Column::DeletedTime.is_null())
+        .one(db)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?
+        .ok_or(AppError::NotFound("文件不存在".to_string()))
+}
+
+/// 校验扩展名是否在允许列表中，返回小写扩展名
+fn validate_extension(storage: &StorageService, file_name: &str) -> Result<String, AppError> {
+    let extension = Path::new(file_name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+        .ok_or_else(|| AppError::BadRequest("无法识别文件类型".to_string()))?;
+
+    if !storage.allowed_extensions.contains(&extension) {
+        return Err(AppError::BadRequest(format!(
+            "不允许上传 {} 类型的文件",
+            extension
+        )));
+    }
+    Ok(extension)
+}
+
+async fn to_response(
+    storage: &StorageService,
+    model: &file::Model,
+) -> Result<FileResponse, AppError> {
+    let url = storage
+        .download_url(model.id, &model.storage_key, &model.file_name)
+        .await?;
+    Ok(FileResponse {
+        id: model.id.to_string(),
+        file_name: model.file_name.clone(),
+        content_type: model.content_type.clone(),
+        file_size: model.file_size,
+        sha256: model.sha256.clone(),
+        biz_type: model.biz_type.clone(),
+        backend: model.backend.clone(),
+        url,
+        created_time: model.created_time.format("%Y-%m-%d %H:%M:%S").to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::testing::TestDb;
+
+    fn new_file(data: &[u8]) -> NewFile<'_> {
+        NewFile {
+            file_name: "a.txt".to_string(),
+            extension: "txt".to_string(),
+            data,
+            biz_type: DEFAULT_BIZ_TYPE.to_string(),
+        }
+    }
+
+    /// 存储目录下的全部对象
+    fn stored_objects(root: &Path) -> Vec<String> {
+        let mut objects = Vec::new();
+        let mut dirs = vec![root.to_path_buf()];
+        while let Some(dir) = dirs.pop() {
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    dirs.push(path);
+                } else {
+                    objects.push(path.strip_prefix(root).unwrap().display().to_string());
+                }
+            }
+        }
+        objects
+    }
+
+    #[tokio::test]
+    async fn concurrent_uploads_share_one_object() {
+        let Some(db) = TestDb::migrated().await else {
+            return;
+        };
+        let root = std::env::temp_dir().join(format!("maple_test_{}", Uuid::new_v4().simple()));
+        let storage = StorageService::local(&root);
+
+        let data = b"same content";
+        let results = futures_util::future::join_all(
+            (0..4).map(|_| store_file(&db, &storage, new_file(data), None)),
+        )
+        .await;
+        let models: Vec<file::Model> = results.into_iter().map(Result::unwrap).collect();
+
+        // 落败的请求复用胜出者的对象，自己上传的对象被删除
+        let objects = stored_objects(&root);
+        assert_eq!(objects.len(), 1, "{:?}", objects);
+        assert!(models.iter().all(|m| m.storage_key == objects[0]));
+
+        // 已有相同内容时不再上传
+        let model = store_file(&db, &storage, new_file(data), None).await.unwrap();
+        assert_eq!(model.storage_key, objects[0]);
+        assert_eq!(stored_objects(&root).len(), 1);
+
+        // 最后一个引用删除后对象一并删除
+        for model in models.into_iter().chain([model]) {
+            remove_file(&db, &storage, model, None).await.unwrap();
+        }
+        assert!(stored_objects(&root).is_empty());
+        std::fs::remove_dir_all(&root).ok();
+    }
+}
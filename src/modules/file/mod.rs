@@ -0,0 +1,11 @@
+// file 模块 - 文件上传、下载与元数据管理
+
+mod dto;
+mod handler;
+mod routes;
+
+pub use handler::{
+    avatar_file_id, avatar_url, find_file, is_avatar_file, remove_file, store_file, NewFile,
+    BIZ_TYPE_AVATAR, BIZ_TYPE_AVATAR_THUMB,
+};
+pub use routes::routes;
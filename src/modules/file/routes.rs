@@ -0,0 +1,21 @@
+use salvo::prelude::*;
+use crate::common::middleware::auth_middleware;
+use super::handler;
+
+pub fn routes() -> Router {
+    Router::with_path("file")
+        // 下载接口通过签名校验、头像公开访问，不需要登录
+        .push(Router::with_path("download/<id>").get(handler::download_file))
+        .push(Router::with_path("avatar/<id>").get(handler::get_avatar))
+        .push(
+            Router::new()
+                .hoop(auth_middleware)
+                .push(Router::with_path("upload").post(handler::upload_file))
+                .push(Router::with_path("list").get(handler::get_file_list))
+                .push(
+                    Router::with_path("<id>")
+                        .get(handler::get_file)
+                        .delete(handler::delete_file)
+                )
+        )
+}
@@ -0,0 +1,38 @@
+use salvo::oapi::ToSchema;
+use serde::Serialize;
+
+/// 存活状态
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LivenessStatus {
+    /// 固定为 up
+    pub status: String,
+    /// 版本号
+    pub version: String,
+}
+
+/// 就绪状态
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadinessStatus {
+    /// 所有组件可用时为 up，否则为 down
+    pub status: String,
+    /// 版本号
+    pub version: String,
+    pub components: Vec<ComponentStatus>,
+}
+
+/// 依赖组件状态
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentStatus {
+    /// 组件名称：database、rsa、storage
+    pub name: String,
+    /// up 或 down
+    pub status: String,
+    /// 检查耗时（毫秒）
+    pub latency_ms: f64,
+    /// 不可用原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
@@ -1,36 +1,174 @@
 use salvo::prelude::*;
-use salvo::oapi::ToSchema;
-use serde::{Deserialize, Serialize};
+use sea_orm::DatabaseConnection;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use crate::common::{ApiResponse};
-
-/// 健康检查响应
-#[derive(Serialize, Deserialize, ToSchema)]
-pub struct HealthStatus {
-    /// 服务状态
-    pub status: String,
-    /// 数据库连接状态
-    pub database: String,
-    /// 版本号
-    pub version: String,
+use super::dto::{ComponentStatus, LivenessStatus, ReadinessStatus};
+use crate::common::{i18n, rsa_crypto, shutdown, storage, ApiResponse, AppError};
+
+/// 数据库 ping 超时，超时视为不可用
+const PING_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// 存活检查
+///
+/// 只表示进程能够处理请求，不检查依赖，供容器编排判断是否需要重启。
+#[endpoint(
+    tags("系统"),
+    responses(
+        (status_code = 200, description = "服务存活")
+    )
+)]
+pub async fn liveness() -> Json<ApiResponse<LivenessStatus>> {
+    Json(ApiResponse::success(LivenessStatus {
+        status: "up".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+    }))
 }
 
-/// 健康检查
-#[endpoint(tags("系统"))]
-pub async fn health_check(depot: &Depot) -> Json<ApiResponse<HealthStatus>> {
-    // 检查数据库连接
-    let db_status = if depot.get::<Arc<sea_orm::DatabaseConnection>>("db").is_ok() {
-        "connected"
-    } else {
-        "disconnected"
-    };
+/// 就绪检查
+///
+/// 实际 ping 数据库并检查 RSA 密钥和文件存储，任一组件不可用或服务正在停机时返回 503，
+/// 供负载均衡判断是否转发流量。进程内缓存（系统参数）随数据库加载，不单独检查。
+#[endpoint(
+    tags("系统"),
+    responses(
+        (status_code = 200, description = "服务就绪"),
+        (status_code = 503, description = "存在不可用的组件")
+    )
+)]
+pub async fn readiness(depot: &Depot, res: &mut Response) {
+    let db = depot.get::<Arc<DatabaseConnection>>("db").ok().cloned();
+    let components = vec![
+        check("database", ping_database(db)).await,
+        check("rsa", async { rsa_crypto::get_public_key().map(|_| ()).map_err(reason) }).await,
+        check("storage", async { storage::storage().map(|_| ()).map_err(reason) }).await,
+    ];
 
-    let health = HealthStatus {
-        status: "healthy".to_string(),
-        database: db_status.to_string(),
+    let stopping = shutdown::is_shutting_down();
+    let ready = !stopping && components.iter().all(|c| c.status == "up");
+    let report = ReadinessStatus {
+        status: if ready { "up" } else { "down" }.to_string(),
         version: env!("CARGO_PKG_VERSION").to_string(),
+        components: components
+            .into_iter()
+            .map(|c| ComponentStatus {
+                error: c.error.map(|e| i18n::t(depot, &e)),
+                ..c
+            })
+            .collect(),
     };
 
-    Json(ApiResponse::success(health))
+    if ready {
+        res.render(Json(ApiResponse::success(report)));
+    } else {
+        res.status_code(StatusCode::SERVICE_UNAVAILABLE);
+        res.render(Json(ApiResponse {
+            code: StatusCode::SERVICE_UNAVAILABLE.as_u16(),
+            message: i18n::t(depot, if stopping { "服务正在停止" } else { "服务未就绪" }),
+            data: Some(report),
+        }));
+    }
+}
+
+// ========== 辅助函数 ==========
+
+/// 执行一项检查并记录耗时
+async fn check<F>(name: &str, probe: F) -> ComponentStatus
+where
+    F: Future<Output = Result<(), String>>,
+{
+    let started = Instant::now();
+    let result = probe.await;
+    ComponentStatus {
+        name: name.to_string(),
+        status: if result.is_ok() { "up" } else { "down" }.to_string(),
+        latency_ms: started.elapsed().as_micros() as f64 / 1000.0,
+        error: result.err(),
+    }
+}
+
+async fn ping_database(db: Option<Arc<DatabaseConnection>>) -> Result<(), String> {
+    let db = db.ok_or_else(|| "数据库未连接".to_string())?;
+    tokio::time::timeout(PING_TIMEOUT, db.ping())
+        .await
+        .map_err(|_| "数据库响应超时".to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// 取出错误信息，不带错误类别前缀
+fn reason(error: AppError) -> String {
+    match error {
+        AppError::InternalServerError(msg) => msg,
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use salvo::test::{ResponseExt, TestClient};
+    use serde_json::Value;
+
+    use crate::common::testing::{self, TestDb};
+
+    fn component<'a>(body: &'a Value, name: &str) -> &'a Value {
+        body["data"]["components"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|c| c["name"] == name)
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn readiness_fails_without_database() {
+        let service = testing::service(super::super::routes::routes(), None);
+
+        let mut res = TestClient::get("http://127.0.0.1/health/ready")
+            .send(&service)
+            .await;
+        assert_eq!(
+            res.status_code,
+            Some(salvo::http::StatusCode::SERVICE_UNAVAILABLE)
+        );
+        let body = res.take_json::<Value>().await.unwrap();
+        assert_eq!(body["data"]["status"], "down");
+        assert_eq!(component(&body, "database")["status"], "down");
+        assert_eq!(component(&body, "database")["error"], "数据库未连接");
+
+        // 存活检查不依赖数据库
+        let res = TestClient::get("http://127.0.0.1/health/live")
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(salvo::http::StatusCode::OK));
+    }
+
+    #[tokio::test]
+    async fn readiness_pings_database() {
+        let Some(db) = TestDb::empty().await else {
+            return;
+        };
+        let conn = db.arc();
+        let service = testing::service(super::super::routes::routes(), Some(conn));
+
+        let mut res = TestClient::get("http://127.0.0.1/health/ready")
+            .send(&service)
+            .await;
+        let body = res.take_json::<Value>().await.unwrap();
+        assert_eq!(component(&body, "database")["status"], "up");
+
+        // 删除数据库后连接仍在，但 ping 失败
+        drop(db);
+        let mut res = TestClient::get("http://127.0.0.1/health/ready")
+            .send(&service)
+            .await;
+        assert_eq!(
+            res.status_code,
+            Some(salvo::http::StatusCode::SERVICE_UNAVAILABLE)
+        );
+        let body = res.take_json::<Value>().await.unwrap();
+        assert_eq!(body["data"]["status"], "down");
+        assert_eq!(component(&body, "database")["status"], "down");
+        assert!(component(&body, "database")["error"].is_string());
+    }
 }
@@ -3,5 +3,8 @@ use super::handler;
 
 pub fn routes() -> Router {
     Router::with_path("health")
-        .get(handler::health_check)
+        // 兼容原有地址，等同于就绪检查
+        .get(handler::readiness)
+        .push(Router::with_path("live").get(handler::liveness))
+        .push(Router::with_path("ready").get(handler::readiness))
 }
@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+use salvo::oapi::ToSchema;
+
+/// 更新定时任务请求
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[salvo(schema(example = json!({
+    "cronExpression": "0 0 3 * * *",
+    "concurrencyPolicy": "forbid"
+})))]
+pub struct UpdateJobRequest {
+    /// cron 表达式（秒 分 时 日 月 周，也支持五段式），按服务器本地时区执行
+    pub cron_expression: Option<String>,
+    /// 并发策略：forbid-上次未结束则跳过，allow-允许并发
+    pub concurrency_policy: Option<String>,
+}
+
+/// 定时任务响应
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct JobResponse {
+    pub id: String,
+    pub job_key: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub cron_expression: String,
+    pub enabled: bool,
+    pub concurrency_policy: String,
+    pub next_run_time: Option<String>,
+    pub last_run_time: Option<String>,
+    /// 是否正在执行（持有未过期的执行租约）
+    pub running: bool,
+    /// 持有执行租约的实例
+    pub lease_owner: Option<String>,
+    /// 当前版本代码中是否注册了该任务
+    pub registered: bool,
+}
+
+/// 任务执行记录响应
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct JobRunResponse {
+    pub id: String,
+    pub job_id: String,
+    /// 触发方式：schedule-定时，manual-手动
+    pub trigger_type: String,
+    pub instance_id: String,
+    /// 状态：running、success、failed、skipped
+    pub status: String,
+    pub output: Option<String>,
+    pub error: Option<String>,
+    pub started_time: String,
+    pub finished_time: Option<String>,
+    pub duration_ms: Option<i64>,
+}
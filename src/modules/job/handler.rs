@@ -0,0 +1,361 @@
+use chrono::{NaiveDateTime, Utc};
+use salvo::oapi::extract::{JsonBody, PathParam};
+use salvo::prelude::*;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::dto::{JobResponse, JobRunResponse, UpdateJobRequest};
+use crate::common::constants::{DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE};
+use crate::common::scheduler::{self, ConcurrencyPolicy, TriggerType};
+use crate::common::{i18n, ApiResponse, AppError, PageResponse};
+use crate::models::{job_run, scheduled_job};
+
+/// 获取定时任务列表
+#[endpoint(
+    tags("定时任务"),
+    responses(
+        (status_code = 200, description = "获取成功"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn get_job_list(depot: &Depot) -> Result<Json<ApiResponse<Vec<JobResponse>>>, AppError> {
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let jobs = scheduled_job::Entity::find()
+        .order_by_asc(scheduled_job::Column::JobKey)
+        .all(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    Ok(Json(ApiResponse::success(
+        jobs.iter().map(job_to_response).collect(),
+    )))
+}
+
+/// 获取定时任务详情
+#[endpoint(
+    tags("定时任务"),
+    responses(
+        (status_code = 200, description = "获取成功"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 404, description = "任务不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn get_job(
+    id: PathParam<String>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<JobResponse>>, AppError> {
+    let job_id = parse_job_id(&id.into_inner())?;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let job = find_job(db.as_ref(), job_id).await?;
+    Ok(Json(ApiResponse::success(job_to_response(&job))))
+}
+
+/// 更新定时任务的 cron 表达式和并发策略
+#[endpoint(
+    tags("定时任务"),
+    responses(
+        (status_code = 200, description = "更新成功"),
+        (status_code = 400, description = "参数错误"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 404, description = "任务不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn update_job(
+    id: PathParam<String>,
+    req: JsonBody<UpdateJobRequest>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<JobResponse>>, AppError> {
+    let job_id = parse_job_id(&id.into_inner())?;
+    let data = req.into_inner();
+
+    let schedule = data
+        .cron_expression
+        .as_deref()
+        .map(scheduler::parse_cron)
+        .transpose()?;
+    if let Some(policy) = &data.concurrency_policy {
+        ConcurrencyPolicy::from_str(policy)
+            .ok_or(AppError::BadRequest("无效的并发策略".to_string()))?;
+    }
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let user_id = depot
+        .get::<String>("user_id")
+        .ok()
+        .and_then(|s| Uuid::parse_str(s.as_str()).ok());
+
+    let existing = find_job(db.as_ref(), job_id).await?;
+    let enabled = existing.enabled;
+    let now = Utc::now().naive_utc();
+
+    let mut active_model: scheduled_job::ActiveModel = existing.into();
+    if let (Some(expression), Some(schedule)) = (data.cron_expression, schedule) {
+        active_model.cron_expression = Set(expression.trim().to_string());
+        if enabled {
+            active_model.next_run_time = Set(scheduler::next_run_time(&schedule, now));
+        }
+    }
+    if let Some(policy) = data.concurrency_policy {
+        active_model.concurrency_policy = Set(policy);
+    }
+    active_model.updated_time = Set(now);
+    active_model.updated_id = Set(user_id);
+
+    let updated = active_model
+        .update(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    Ok(Json(ApiResponse::success_with_message(
+        job_to_response(&updated),
+        i18n::t(depot, "更新成功"),
+    )))
+}
+
+/// 暂停定时任务（不影响正在执行的实例，也不影响手动触发）
+#[endpoint(
+    tags("定时任务"),
+    responses(
+        (status_code = 200, description = "暂停成功"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 404, description = "任务不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn pause_job(
+    id: PathParam<String>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<JobResponse>>, AppError> {
+    set_enabled(id, depot, false).await
+}
+
+/// 恢复定时任务，从当前时间起重新计算下次执行时间
+#[endpoint(
+    tags("定时任务"),
+    responses(
+        (status_code = 200, description = "恢复成功"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 404, description = "任务不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn resume_job(
+    id: PathParam<String>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<JobResponse>>, AppError> {
+    set_enabled(id, depot, true).await
+}
+
+/// 手动触发一次任务
+///
+/// 任务在后台执行，立即返回执行记录，可通过执行记录接口查询结果。
+#[endpoint(
+    tags("定时任务"),
+    responses(
+        (status_code = 200, description = "触发成功"),
+        (status_code = 400, description = "任务未注册或正在执行"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 404, description = "任务不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn trigger_job(
+    id: PathParam<String>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<JobRunResponse>>, AppError> {
+    let job_id = parse_job_id(&id.into_inner())?;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let user_id = depot
+        .get::<String>("user_id")
+        .ok()
+        .and_then(|s| Uuid::parse_str(s.as_str()).ok());
+
+    let job = find_job(db.as_ref(), job_id).await?;
+    let run = scheduler::trigger(db.clone(), &job, TriggerType::Manual, user_id).await?;
+
+    let mut active_model: scheduled_job::ActiveModel = job.into();
+    active_model.last_run_time = Set(Some(run.started_time));
+    active_model
+        .update(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    Ok(Json(ApiResponse::success_with_message(
+        run_to_response(&run),
+        i18n::t(depot, "触发成功"),
+    )))
+}
+
+/// 获取任务执行记录（分页，按开始时间倒序）
+#[endpoint(
+    tags("定时任务"),
+    parameters(
+        ("status" = Option<String>, Query, description = "状态：running、success、failed、skipped"),
+        ("page" = Option<u64>, Query, description = "当前页码，默认1"),
+        ("pageSize" = Option<u64>, Query, description = "每页数量，默认20，最大100"),
+    ),
+    responses(
+        (status_code = 200, description = "获取成功"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 404, description = "任务不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn get_job_runs(
+    id: PathParam<String>,
+    req: &mut Request,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<PageResponse<JobRunResponse>>>, AppError> {
+    let job_id = parse_job_id(&id.into_inner())?;
+    let page = req.query::<u64>("page").unwrap_or(1).max(1);
+    let page_size = req
+        .query::<u64>("pageSize")
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    find_job(db.as_ref(), job_id).await?;
+
+    let mut query_builder = job_run::Entity::find().filter(job_run::Column::JobId.eq(job_id));
+    if let Some(status) = req.query::<String>("status").filter(|s| !s.is_empty()) {
+        query_builder = query_builder.filter(job_run::Column::Status.eq(status));
+    }
+
+    let total = query_builder
+        .clone()
+        .count(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let runs = query_builder
+        .order_by_desc(job_run::Column::StartedTime)
+        .offset((page - 1) * page_size)
+        .limit(page_size)
+        .all(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let items = runs.iter().map(run_to_response).collect();
+    Ok(Json(ApiResponse::success(PageResponse::new(
+        items, total, page, page_size,
+    ))))
+}
+
+// ========== 辅助函数 ==========
+
+fn parse_job_id(id: &str) -> Result<Uuid, AppError> {
+    Uuid::parse_str(id).map_err(|_| AppError::BadRequest("无效的任务ID".to_string()))
+}
+
+async fn find_job(db: &DatabaseConnection, job_id: Uuid) -> Result<scheduled_job::Model, AppError> {
+    scheduled_job::Entity::find_by_id(job_id)
+        .one(db)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?
+        .ok_or(AppError::NotFound("任务不存在".to_string()))
+}
+
+async fn set_enabled(
+    id: PathParam<String>,
+    depot: &Depot,
+    enabled: bool,
+) -> Result<Json<ApiResponse<JobResponse>>, AppError> {
+    let job_id = parse_job_id(&id.into_inner())?;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let user_id = depot
+        .get::<String>("user_id")
+        .ok()
+        .and_then(|s| Uuid::parse_str(s.as_str()).ok());
+
+    let existing = find_job(db.as_ref(), job_id).await?;
+    let now = Utc::now().naive_utc();
+    let next_run_time = if enabled {
+        let schedule = scheduler::parse_cron(&existing.cron_expression)?;
+        scheduler::next_run_time(&schedule, now)
+    } else {
+        None
+    };
+
+    let mut active_model: scheduled_job::ActiveModel = existing.into();
+    active_model.enabled = Set(enabled);
+    active_model.next_run_time = Set(next_run_time);
+    active_model.updated_time = Set(now);
+    active_model.updated_id = Set(user_id);
+    let updated = active_model
+        .update(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let message = if enabled { "恢复成功" } else { "暂停成功" };
+    Ok(Json(ApiResponse::success_with_message(
+        job_to_response(&updated),
+        i18n::t(depot, message),
+    )))
+}
+
+fn format_time(time: Option<NaiveDateTime>) -> Option<String> {
+    time.map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+}
+
+fn job_to_response(job: &scheduled_job::Model) -> JobResponse {
+    let running = job
+        .lease_until
+        .is_some_and(|until| until > Utc::now().naive_utc());
+    JobResponse {
+        id: job.id.to_string(),
+        job_key: job.job_key.clone(),
+        name: job.name.clone(),
+        description: job.description.clone(),
+        cron_expression: job.cron_expression.clone(),
+        enabled: job.enabled,
+        concurrency_policy: job.concurrency_policy.clone(),
+        next_run_time: format_time(job.next_run_time),
+        last_run_time: format_time(job.last_run_time),
+        running,
+        lease_owner: job.lease_owner.clone().filter(|_| running),
+        registered: scheduler::definition(&job.job_key).is_some(),
+    }
+}
+
+fn run_to_response(run: &job_run::Model) -> JobRunResponse {
+    JobRunResponse {
+        id: run.id.to_string(),
+        job_id: run.job_id.to_string(),
+        trigger_type: run.trigger_type.clone(),
+        instance_id: run.instance_id.clone(),
+        status: run.status.clone(),
+        output: run.output.clone(),
+        error: run.error.clone(),
+        started_time: run.started_time.format("%Y-%m-%d %H:%M:%S").to_string(),
+        finished_time: format_time(run.finished_time),
+        duration_ms: run.duration_ms,
+    }
+}
@@ -0,0 +1,60 @@
+// 内置定时任务
+
+use chrono::{Duration, Utc};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+use crate::common::constants::JOB_RUN_RETENTION_DAYS;
+use crate::common::scheduler::{self, ConcurrencyPolicy, JobContext, JobDefinition, JobFuture};
+use crate::models::job_run;
+use crate::modules::recycle_bin;
+use crate::modules::system::service as system_params;
+
+/// 注册内置任务，需在调度器启动前调用
+pub fn register_builtin_jobs() {
+    scheduler::register(JobDefinition {
+        key: "recycle_bin.purge_expired",
+        name: "回收站清理",
+        description: "彻底删除超过保留天数的回收站记录",
+        cron: "0 0 3 * * *",
+        concurrency: ConcurrencyPolicy::Forbid,
+        handler: purge_recycle_bin,
+    });
+    scheduler::register(JobDefinition {
+        key: "job.purge_runs",
+        name: "任务执行记录清理",
+        description: "删除超过保留天数的任务执行记录",
+        cron: "0 30 3 * * *",
+        concurrency: ConcurrencyPolicy::Forbid,
+        handler: purge_job_runs,
+    });
+}
+
+fn purge_recycle_bin(ctx: JobContext) -> JobFuture {
+    Box::pin(async move {
+        let days = recycle_bin::retention_days(&ctx.db).await;
+        let result = recycle_bin::purge_expired_records(&ctx.db, days)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(format!(
+            "保留 {} 天，清理用户 {} 个、角色 {} 个、菜单 {} 个",
+            result.retention_days, result.users, result.roles, result.menus
+        ))
+    })
+}
+
+fn purge_job_runs(ctx: JobContext) -> JobFuture {
+    Box::pin(async move {
+        let days = system_params::get::<i64>(&ctx.db, "sys.job.run_retention_days")
+            .await
+            .unwrap_or(JOB_RUN_RETENTION_DAYS);
+        let cutoff = Utc::now().naive_utc() - Duration::days(days);
+
+        let result = job_run::Entity::delete_many()
+            .filter(job_run::Column::StartedTime.lt(cutoff))
+            .filter(job_run::Column::Status.ne("running"))
+            .exec(ctx.db.as_ref())
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(format!("保留 {} 天，删除执行记录 {} 条", days, result.rows_affected))
+    })
+}
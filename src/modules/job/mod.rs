@@ -0,0 +1,9 @@
+// job 模块 - 定时任务管理与内置任务
+
+mod dto;
+mod handler;
+mod jobs;
+mod routes;
+
+pub use jobs::register_builtin_jobs;
+pub use routes::routes;
@@ -0,0 +1,19 @@
+use salvo::prelude::*;
+use crate::common::middleware::{admin_only, auth_middleware};
+use super::handler;
+
+pub fn routes() -> Router {
+    Router::with_path("job")
+        .hoop(auth_middleware)
+        .hoop(admin_only)
+        .push(Router::with_path("list").get(handler::get_job_list))
+        .push(
+            Router::with_path("<id>")
+                .get(handler::get_job)
+                .put(handler::update_job)
+                .push(Router::with_path("pause").post(handler::pause_job))
+                .push(Router::with_path("resume").post(handler::resume_job))
+                .push(Router::with_path("trigger").post(handler::trigger_job))
+                .push(Router::with_path("runs").get(handler::get_job_runs))
+        )
+}
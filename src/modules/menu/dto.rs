@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use salvo::oapi::ToSchema;
+use std::collections::HashMap;
 
 /// 菜单类型
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq)]
@@ -40,6 +41,8 @@ pub struct CreateMenuRequest {
     pub parent_id: Option<String>,
     /// 菜单名称
     pub name: String,
+    /// 其它语言的菜单名称，如 {"en-US": "Home"}
+    pub name_i18n: Option<HashMap<String, String>>,
     /// 菜单类型
     pub menu_type: String,
     /// 路由路径
@@ -72,6 +75,8 @@ pub struct UpdateMenuRequest {
     pub parent_id: Option<String>,
     /// 菜单名称
     pub name: Option<String>,
+    /// 其它语言的菜单名称，传空对象表示清除
+    pub name_i18n: Option<HashMap<String, String>>,
     /// 菜单类型
     pub menu_type: Option<String>,
     /// 路由路径
@@ -94,6 +99,119 @@ pub struct UpdateMenuRequest {
     pub status: Option<i16>,
 }
 
+/// 菜单移动项
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MenuMoveItem {
+    /// 菜单ID
+    pub id: String,
+    /// 目标父菜单ID，为空表示移动到顶级
+    pub parent_id: Option<String>,
+    /// 目标排序值
+    pub sort: i32,
+}
+
+/// 批量移动/排序菜单请求
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[salvo(schema(example = json!({
+    "moves": [
+        { "id": "c0000000-0000-0000-0000-000000000101", "parentId": "c0000000-0000-0000-0000-000000000100", "sort": 2 },
+        { "id": "c0000000-0000-0000-0000-000000000102", "parentId": "c0000000-0000-0000-0000-000000000100", "sort": 1 }
+    ]
+})))]
+pub struct BatchMoveMenuRequest {
+    /// 移动列表
+    pub moves: Vec<MenuMoveItem>,
+}
+
+/// 菜单导出文档
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MenuExportDocument {
+    /// 文档格式版本
+    pub version: u32,
+    /// 导出时间
+    #[serde(default)]
+    pub exported_time: Option<String>,
+    /// 菜单树
+    pub menus: Vec<MenuExportNode>,
+}
+
+/// 导出的菜单节点，通过权限标识或路由路径匹配目标环境中的菜单
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MenuExportNode {
+    /// 菜单名称
+    pub name: String,
+    /// 其它语言的菜单名称
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name_i18n: Option<HashMap<String, String>>,
+    /// 菜单类型
+    pub menu_type: String,
+    /// 路由路径
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// 组件路径
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub component: Option<String>,
+    /// 图标
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// 权限标识
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permission: Option<String>,
+    /// 排序
+    #[serde(default)]
+    pub sort: i32,
+    /// 是否显示
+    #[serde(default = "default_true")]
+    pub is_show: bool,
+    /// 是否缓存
+    #[serde(default)]
+    pub is_cache: bool,
+    /// 是否外链
+    #[serde(default)]
+    pub is_external: bool,
+    /// 状态
+    #[serde(default = "default_status")]
+    pub status: i16,
+    /// 拥有该菜单的角色代码，为空表示不处理角色分配
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub roles: Option<Vec<String>>,
+    /// 子菜单
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<MenuExportNode>,
+}
+
+/// 菜单导入差异项
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MenuImportDiffItem {
+    /// 匹配键（permission:xxx / path:xxx / name:xxx）
+    pub key: String,
+    /// 菜单名称
+    pub name: String,
+    /// 变更的字段
+    pub changes: Vec<String>,
+}
+
+/// 菜单导入结果
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MenuImportResult {
+    /// 是否为试运行（未写入数据库）
+    pub dry_run: bool,
+    /// 新增的菜单
+    pub created: Vec<MenuImportDiffItem>,
+    /// 更新的菜单
+    pub updated: Vec<MenuImportDiffItem>,
+    /// 未变化的菜单数量
+    pub unchanged: usize,
+    /// 新增的角色菜单关联数量
+    pub role_links_added: usize,
+}
+
 /// 菜单响应
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -101,6 +219,9 @@ pub struct MenuResponse {
     pub id: String,
     pub parent_id: Option<String>,
     pub name: String,
+    /// 其它语言的菜单名称
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name_i18n: Option<HashMap<String, String>>,
     pub menu_type: String,
     pub path: Option<String>,
     pub component: Option<String>,
@@ -116,6 +237,62 @@ pub struct MenuResponse {
     pub children: Option<Vec<MenuResponse>>,
 }
 
+/// 菜单搜索结果中的祖先节点
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MenuBreadcrumbItem {
+    pub id: String,
+    pub name: String,
+}
+
+/// 菜单搜索结果中关联的角色
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MenuRoleItem {
+    pub role_id: String,
+    pub role_code: String,
+    pub role_name: String,
+}
+
+/// 菜单搜索结果
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MenuSearchResult {
+    /// 匹配的菜单
+    pub menu: MenuResponse,
+    /// 从顶级到父菜单的祖先路径
+    pub breadcrumb: Vec<MenuBreadcrumbItem>,
+    /// 包含该菜单的角色
+    pub roles: Vec<MenuRoleItem>,
+    /// 命中的字段
+    pub matched_fields: Vec<String>,
+}
+
+/// 路由元信息
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteMeta {
+    /// 标题
+    pub title: String,
+    /// 图标
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// 是否缓存页面（对应 is_cache）
+    pub keep_alive: bool,
+    /// 是否在菜单中隐藏（对应 is_show 取反）
+    pub hidden: bool,
+    /// 外链地址（is_external 为 true 时取 path）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    /// 页面下按钮的权限标识
+    pub permissions: Vec<String>,
+    /// 隐藏页面激活时高亮的菜单路径
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active_menu: Option<String>,
+    /// 排序
+    pub order: i32,
+}
+
 /// 菜单树响应（用于前端路由）
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -127,10 +304,61 @@ pub struct MenuTreeResponse {
     pub component: Option<String>,
     pub icon: Option<String>,
     pub sort: i32,
+    /// 路由元信息，仅 format=meta、vue、react 时输出
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<RouteMeta>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub children: Option<Vec<MenuTreeResponse>>,
 }
 
+/// vue-router 路由记录
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct VueRouteRecord {
+    /// 路由名称（取菜单ID）
+    pub name: String,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component: Option<String>,
+    /// 目录重定向到第一个子路由
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redirect: Option<String>,
+    pub meta: RouteMeta,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<VueRouteRecord>,
+}
+
+/// react-router 路由对象
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReactRouteObject {
+    pub id: String,
+    pub path: String,
+    /// 组件路径，由前端映射为 element
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component: Option<String>,
+    /// 路由元信息，对应 react-router 的 handle
+    pub handle: RouteMeta,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<ReactRouteObject>,
+}
+
+/// 当前用户路由，结构由 format 参数决定
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum UserRoutesResponse {
+    /// 默认结构及带元信息的结构
+    Default(Vec<MenuTreeResponse>),
+    /// vue-router 结构
+    Vue(Vec<VueRouteRecord>),
+    /// react-router 结构
+    React(Vec<ReactRouteObject>),
+}
+
 fn default_true() -> bool {
     true
 }
+
+fn default_status() -> i16 {
+    1
+}
@@ -1,16 +1,27 @@
 use chrono::Utc;
-use salvo::oapi::extract::{JsonBody, PathParam};
+use salvo::oapi::extract::{JsonBody, PathParam, QueryParam};
 use salvo::prelude::*;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set, TransactionTrait, sea_query::Expr,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use uuid::Uuid;
 
-use super::dto::{CreateMenuRequest, MenuResponse, MenuTreeResponse, UpdateMenuRequest};
+use super::dto::{
+    BatchMoveMenuRequest, CreateMenuRequest, MenuBreadcrumbItem, MenuExportDocument,
+    MenuExportNode, MenuImportDiffItem, MenuImportResult, MenuResponse, MenuRoleItem,
+    MenuSearchResult, MenuTreeResponse, MenuType,
+    ReactRouteObject, RouteMeta, UpdateMenuRequest, UserRoutesResponse, VueRouteRecord,
+};
+use crate::common::i18n::{self, Locale};
+use crate::common::push::{self, PushEvent};
 use crate::common::{ApiResponse, AppError};
-use crate::models::{menu, role_menu};
+use crate::models::{menu, role, role_menu};
+
+/// 菜单导出文档的当前格式版本
+const MENU_EXPORT_VERSION: u32 = 1;
 
 /// 获取菜单列表（树形结构）
 #[endpoint(
@@ -66,8 +77,15 @@ pub async fn get_menu_list(
 }
 
 /// 获取当前用户的菜单（根据角色权限）
+///
+/// 默认结构与旧版一致：不含隐藏页面，按钮作为子节点输出。
+/// format 为 meta、vue、react 时返回的路由包含隐藏页面（`meta.hidden`），按钮不作为路由输出，
+/// 其权限标识汇总到所属页面的 `meta.permissions`。
 #[endpoint(
     tags("菜单管理"),
+    parameters(
+        ("format" = Option<String>, Query, description = "输出结构：default（默认）、meta、vue 或 react"),
+    ),
     responses(
         (status_code = 200, description = "获取成功"),
         (status_code = 401, description = "未授权"),
@@ -75,8 +93,9 @@ pub async fn get_menu_list(
     )
 )]
 pub async fn get_user_menus(
+    format: QueryParam<String, false>,
     depot: &Depot,
-) -> Result<Json<ApiResponse<Vec<MenuTreeResponse>>>, AppError> {
+) -> Result<Json<ApiResponse<UserRoutesResponse>>, AppError> {
     let role_id_str = depot
         .get::<String>("role_id")
         .map_err(|_| AppError::Unauthorized)?;
@@ -90,20 +109,43 @@ pub async fn get_user_menus(
     // 查询角色关联的菜单
     let role_menus = role_menu::Entity::find()
         .filter(role_menu::Column::RoleId.eq(role_id))
+        .filter(role_menu::Column::DeletedTime.is_null())
         .find_also_related(menu::Entity)
         .all(db.as_ref())
         .await
         .map_err(|e| AppError::InternalServerError(e.to_string()))?;
 
-    // 过滤出有效的菜单（已启用、显示）
+    // 过滤出有效的菜单（已启用）
     let menus: Vec<menu::Model> = role_menus
         .into_iter()
         .filter_map(|(_, m)| m)
-        .filter(|m| m.deleted_time.is_none() && m.status == 1 && m.is_show)
+        .filter(|m| m.deleted_time.is_none() && m.status == 1)
         .collect();
 
-    let tree = build_menu_tree_for_route(&menus, None);
-    Ok(Json(ApiResponse::success(tree)))
+    let locale = Locale::from_depot(depot);
+    let routes = match format.into_inner().as_deref() {
+        None | Some("") | Some("default") => {
+            let visible: Vec<menu::Model> = menus.into_iter().filter(|m| m.is_show).collect();
+            UserRoutesResponse::Default(build_menu_tree_for_route(&visible, None, locale, false))
+        }
+        Some("meta") => {
+            UserRoutesResponse::Default(build_menu_tree_for_route(&menus, None, locale, true))
+        }
+        Some("vue") => UserRoutesResponse::Vue(
+            build_menu_tree_for_route(&menus, None, locale, true)
+                .into_iter()
+                .map(to_vue_route)
+                .collect(),
+        ),
+        Some("react") => UserRoutesResponse::React(
+            build_menu_tree_for_route(&menus, None, locale, true)
+                .into_iter()
+                .map(to_react_route)
+                .collect(),
+        ),
+        Some(_) => return Err(AppError::BadRequest("不支持的路由格式".to_string())),
+    };
+    Ok(Json(ApiResponse::success(routes)))
 }
 
 /// 获取当前用户的权限列表
@@ -130,6 +172,7 @@ pub async fn get_user_permissions(
 
     let role_menus = role_menu::Entity::find()
         .filter(role_menu::Column::RoleId.eq(role_id))
+        .filter(role_menu::Column::DeletedTime.is_null())
         .find_also_related(menu::Entity)
         .all(db.as_ref())
         .await
@@ -145,6 +188,132 @@ pub async fn get_user_permissions(
     Ok(Json(ApiResponse::success(permissions)))
 }
 
+/// 搜索菜单
+///
+/// 按名称（含译名）、路由路径、组件路径或权限标识模糊匹配，忽略大小写。
+/// 结果附带祖先路径和包含该菜单的角色，便于定位授予某权限的页面。
+#[endpoint(
+    tags("菜单管理"),
+    parameters(
+        ("keyword" = String, Query, description = "搜索关键字"),
+        ("field" = Option<String>, Query, description = "限定字段：name、path、component、permission，默认全部"),
+    ),
+    responses(
+        (status_code = 200, description = "搜索成功"),
+        (status_code = 400, description = "参数错误"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn search_menus(
+    keyword: QueryParam<String, true>,
+    field: QueryParam<String, false>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<Vec<MenuSearchResult>>>, AppError> {
+    let keyword = keyword.into_inner().trim().to_lowercase();
+    if keyword.is_empty() {
+        return Err(AppError::BadRequest("搜索关键字不能为空".to_string()));
+    }
+    let field = field.into_inner();
+    if let Some(f) = field.as_deref() {
+        if !["name", "path", "component", "permission"].contains(&f) {
+            return Err(AppError::BadRequest("无效的搜索字段".to_string()));
+        }
+    }
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let menus = menu::Entity::find()
+        .filter(menu::Column::DeletedTime.is_null())
+        .order_by_asc(menu::Column::Sort)
+        .all(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let contains = |value: Option<&str>| {
+        value.is_some_and(|v| v.to_lowercase().contains(&keyword))
+    };
+    let wants = |name: &str| field.as_deref().is_none_or(|f| f == name);
+
+    let matches: Vec<(&menu::Model, Vec<String>)> = menus
+        .iter()
+        .filter_map(|m| {
+            let mut matched = Vec::new();
+            let name_matched = contains(Some(&m.name))
+                || name_i18n_of(m).is_some_and(|names| names.values().any(|n| contains(Some(n))));
+            if wants("name") && name_matched {
+                matched.push("name".to_string());
+            }
+            if wants("path") && contains(m.path.as_deref()) {
+                matched.push("path".to_string());
+            }
+            if wants("component") && contains(m.component.as_deref()) {
+                matched.push("component".to_string());
+            }
+            if wants("permission") && contains(m.permission.as_deref()) {
+                matched.push("permission".to_string());
+            }
+            (!matched.is_empty()).then_some((m, matched))
+        })
+        .collect();
+
+    if matches.is_empty() {
+        return Ok(Json(ApiResponse::success(Vec::new())));
+    }
+
+    // 查询命中菜单关联的角色
+    let matched_ids: Vec<Uuid> = matches.iter().map(|(m, _)| m.id).collect();
+    let links = role_menu::Entity::find()
+        .filter(role_menu::Column::MenuId.is_in(matched_ids))
+        .filter(role_menu::Column::DeletedTime.is_null())
+        .find_also_related(role::Entity)
+        .all(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+    let mut roles_by_menu: HashMap<Uuid, Vec<MenuRoleItem>> = HashMap::new();
+    for (link, r) in links {
+        if let Some(r) = r.filter(|r| r.deleted_time.is_none()) {
+            roles_by_menu.entry(link.menu_id).or_default().push(MenuRoleItem {
+                role_id: r.id.to_string(),
+                role_code: r.code,
+                role_name: r.name,
+            });
+        }
+    }
+
+    let locale = Locale::from_depot(depot);
+    let menu_map: HashMap<Uuid, &menu::Model> = menus.iter().map(|m| (m.id, m)).collect();
+    let results = matches
+        .into_iter()
+        .map(|(m, matched_fields)| {
+            // 自下而上收集祖先，长度上限防御脏数据中的环
+            let mut breadcrumb = Vec::new();
+            let mut current = m.parent_id.and_then(|pid| menu_map.get(&pid));
+            while let Some(parent) = current {
+                if breadcrumb.len() > menus.len() {
+                    break;
+                }
+                breadcrumb.push(MenuBreadcrumbItem {
+                    id: parent.id.to_string(),
+                    name: localized_name(parent, locale),
+                });
+                current = parent.parent_id.and_then(|pid| menu_map.get(&pid));
+            }
+            breadcrumb.reverse();
+
+            MenuSearchResult {
+                menu: model_to_response(m),
+                breadcrumb,
+                roles: roles_by_menu.remove(&m.id).unwrap_or_default(),
+                matched_fields,
+            }
+        })
+        .collect();
+
+    Ok(Json(ApiResponse::success(results)))
+}
+
 /// 获取单个菜单详情
 #[endpoint(
     tags("菜单管理"),
@@ -191,7 +360,7 @@ pub async fn create_menu(
     let data = req.into_inner();
 
     // 验证菜单类型
-    if !["catalog", "menu", "button"].contains(&data.menu_type.as_str()) {
+    if MenuType::from_str(&data.menu_type).is_none() {
         return Err(AppError::BadRequest("无效的菜单类型".to_string()));
     }
 
@@ -211,11 +380,22 @@ pub async fn create_menu(
         .transpose()
         .map_err(|_| AppError::BadRequest("无效的父菜单ID".to_string()))?;
 
+    // 锁住父菜单直到插入完成，避免并发删除父菜单后留下孤立的子菜单
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    if let Some(pid) = parent_id {
+        validate_parent(&txn, pid).await?;
+    }
+
     let now = Utc::now().naive_utc();
     let new_menu = menu::ActiveModel {
         id: Set(Uuid::new_v4()),
         parent_id: Set(parent_id),
         name: Set(data.name),
+        name_i18n: Set(name_i18n_to_json(data.name_i18n)),
         menu_type: Set(data.menu_type),
         path: Set(data.path),
         component: Set(data.component),
@@ -235,13 +415,17 @@ pub async fn create_menu(
     };
 
     let menu = new_menu
-        .insert(db.as_ref())
+        .insert(&txn)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    txn.commit()
         .await
         .map_err(|e| AppError::InternalServerError(e.to_string()))?;
 
     Ok(Json(ApiResponse::success_with_message(
         model_to_response(&menu),
-        "创建成功".to_string(),
+        i18n::t(depot, "创建成功"),
     )))
 }
 
@@ -273,13 +457,26 @@ pub async fn update_menu(
         .ok()
         .and_then(|s| Uuid::parse_str(s.as_str()).ok());
 
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
     let existing = menu::Entity::find_by_id(menu_id)
         .filter(menu::Column::DeletedTime.is_null())
-        .one(db.as_ref())
+        .one(&txn)
         .await
         .map_err(|e| AppError::InternalServerError(e.to_string()))?
         .ok_or(AppError::NotFound("菜单不存在".to_string()))?;
 
+    // 父级变更或改为按钮时需要校验树结构，在事务内加锁加载全部有效菜单
+    let changes_type_to_button = data.menu_type.as_deref() == Some(MenuType::Button.as_str());
+    let all_menus = if data.parent_id.is_some() || changes_type_to_button {
+        lock_active_menus(&txn).await?
+    } else {
+        Vec::new()
+    };
+
     let mut active_model: menu::ActiveModel = existing.into();
 
     if let Some(parent_id) = data.parent_id {
@@ -291,15 +488,30 @@ pub async fn update_menu(
                     .map_err(|_| AppError::BadRequest("无效的父菜单ID".to_string()))?,
             )
         };
+        if let Some(pid) = pid {
+            if pid == menu_id {
+                return Err(AppError::BadRequest("父菜单不能是菜单自身".to_string()));
+            }
+            if collect_descendant_ids(&all_menus, menu_id).contains(&pid) {
+                return Err(AppError::BadRequest("父菜单不能是当前菜单的子菜单".to_string()));
+            }
+            validate_parent(&txn, pid).await?;
+        }
         active_model.parent_id = Set(pid);
     }
     if let Some(name) = data.name {
         active_model.name = Set(name);
     }
+    if data.name_i18n.is_some() {
+        active_model.name_i18n = Set(name_i18n_to_json(data.name_i18n));
+    }
     if let Some(menu_type) = data.menu_type {
-        if !["catalog", "menu", "button"].contains(&menu_type.as_str()) {
+        if MenuType::from_str(&menu_type).is_none() {
             return Err(AppError::BadRequest("无效的菜单类型".to_string()));
         }
+        if changes_type_to_button && all_menus.iter().any(|m| m.parent_id == Some(menu_id)) {
+            return Err(AppError::BadRequest("存在子菜单的菜单不能改为按钮".to_string()));
+        }
         active_model.menu_type = Set(menu_type);
     }
     if data.path.is_some() {
@@ -334,31 +546,47 @@ pub async fn update_menu(
     active_model.updated_id = Set(user_id);
 
     let updated = active_model
-        .update(db.as_ref())
+        .update(&txn)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    txn.commit()
         .await
         .map_err(|e| AppError::InternalServerError(e.to_string()))?;
 
+    // 菜单和权限标识变化后通知在线用户刷新路由与权限
+    push::broadcast(PushEvent::PermissionsChanged);
+
     Ok(Json(ApiResponse::success_with_message(
         model_to_response(&updated),
-        "更新成功".to_string(),
+        i18n::t(depot, "更新成功"),
     )))
 }
 
 /// 删除菜单（软删除）
+///
+/// 存在子菜单时默认拒绝删除；传入 `cascade=true` 时级联软删除整棵子树及其角色关联，
+/// 从回收站恢复菜单时角色关联随之恢复。
 #[endpoint(
     tags("菜单管理"),
+    parameters(
+        ("cascade" = Option<bool>, Query, description = "是否级联删除子菜单，默认false"),
+    ),
     responses(
         (status_code = 200, description = "删除成功"),
+        (status_code = 400, description = "存在子菜单"),
         (status_code = 404, description = "菜单不存在"),
         (status_code = 500, description = "服务器错误")
     )
 )]
 pub async fn delete_menu(
     id: PathParam<String>,
+    cascade: QueryParam<bool, false>,
     depot: &Depot,
 ) -> Result<Json<ApiResponse<()>>, AppError> {
     let menu_id = Uuid::parse_str(&id.into_inner())
         .map_err(|_| AppError::BadRequest("无效的菜单ID".to_string()))?;
+    let cascade = cascade.into_inner().unwrap_or(false);
 
     let db = depot
         .get::<Arc<DatabaseConnection>>("db")
@@ -369,35 +597,802 @@ pub async fn delete_menu(
         .ok()
         .and_then(|s| Uuid::parse_str(s.as_str()).ok());
 
-    let existing = menu::Entity::find_by_id(menu_id)
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    // 加锁后重新读取：加锁时等待的并发创建已提交，新增的子菜单在新的快照中可见；
+    // 之后的创建会等待父菜单的锁，并在删除提交后因父菜单已删除而失败
+    lock_active_menus(&txn).await?;
+    let all_menus = menu::Entity::find()
         .filter(menu::Column::DeletedTime.is_null())
-        .one(db.as_ref())
+        .all(&txn)
         .await
-        .map_err(|e| AppError::InternalServerError(e.to_string()))?
-        .ok_or(AppError::NotFound("菜单不存在".to_string()))?;
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+    if !all_menus.iter().any(|m| m.id == menu_id) {
+        return Err(AppError::NotFound("菜单不存在".to_string()));
+    }
 
-    let mut active_model: menu::ActiveModel = existing.into();
-    active_model.deleted_time = Set(Some(Utc::now().naive_utc()));
-    active_model.deleted_id = Set(user_id);
+    let descendant_ids = collect_descendant_ids(&all_menus, menu_id);
+    if !descendant_ids.is_empty() && !cascade {
+        return Err(AppError::BadRequest(
+            "该菜单存在子菜单，请先删除子菜单或使用级联删除".to_string(),
+        ));
+    }
 
-    active_model
-        .update(db.as_ref())
+    let mut ids = descendant_ids;
+    ids.push(menu_id);
+
+    let now = Utc::now().naive_utc();
+    menu::Entity::update_many()
+        .col_expr(menu::Column::DeletedTime, Expr::value(Some(now)))
+        .col_expr(menu::Column::DeletedId, Expr::value(user_id))
+        .filter(menu::Column::Id.is_in(ids.clone()))
+        .exec(&txn)
         .await
         .map_err(|e| AppError::InternalServerError(e.to_string()))?;
 
+    // 角色关联软删除，从回收站恢复菜单时一并恢复；彻底删除菜单时由外键级联删除
+    role_menu::Entity::update_many()
+        .col_expr(role_menu::Column::DeletedTime, Expr::value(Some(now)))
+        .col_expr(role_menu::Column::DeletedId, Expr::value(user_id))
+        .filter(role_menu::Column::MenuId.is_in(ids))
+        .filter(role_menu::Column::DeletedTime.is_null())
+        .exec(&txn)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    // 菜单和权限标识变化后通知在线用户刷新路由与权限
+    push::broadcast(PushEvent::PermissionsChanged);
+
     Ok(Json(ApiResponse::success_with_message(
         (),
-        "删除成功".to_string(),
+        i18n::t(depot, "删除成功"),
+    )))
+}
+
+/// 批量移动/排序菜单（用于拖拽编辑）
+///
+/// 所有移动项应用后的整棵树会先整体校验，通过后在同一事务中提交。
+#[endpoint(
+    tags("菜单管理"),
+    responses(
+        (status_code = 200, description = "移动成功"),
+        (status_code = 400, description = "参数错误或树结构无效"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 404, description = "菜单不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn batch_move_menus(
+    req: JsonBody<BatchMoveMenuRequest>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    let data = req.into_inner();
+    if data.moves.is_empty() {
+        return Err(AppError::BadRequest("移动列表不能为空".to_string()));
+    }
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let user_id = depot
+        .get::<String>("user_id")
+        .ok()
+        .and_then(|s| Uuid::parse_str(s.as_str()).ok());
+
+    // 解析移动项
+    let mut moves: Vec<(Uuid, Option<Uuid>, i32)> = Vec::with_capacity(data.moves.len());
+    for item in &data.moves {
+        let id = Uuid::parse_str(&item.id)
+            .map_err(|_| AppError::BadRequest(format!("无效的菜单ID: {}", item.id)))?;
+        let parent_id = match item.parent_id.as_deref() {
+            None | Some("") => None,
+            Some(pid) => Some(
+                Uuid::parse_str(pid)
+                    .map_err(|_| AppError::BadRequest(format!("无效的父菜单ID: {}", pid)))?,
+            ),
+        };
+        if moves.iter().any(|(existing, _, _)| *existing == id) {
+            return Err(AppError::BadRequest(format!("菜单 {} 重复出现", id)));
+        }
+        moves.push((id, parent_id, item.sort));
+    }
+
+    // 校验和写入在同一事务内进行，加锁读取避免并发移动各自通过校验后组合成循环
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let all_menus = lock_active_menus(&txn).await?;
+
+    // 在内存中应用移动后的父级关系，并整体校验
+    let menu_map: HashMap<Uuid, &menu::Model> = all_menus.iter().map(|m| (m.id, m)).collect();
+    let mut parent_map: HashMap<Uuid, Option<Uuid>> =
+        all_menus.iter().map(|m| (m.id, m.parent_id)).collect();
+
+    for (id, parent_id, _) in &moves {
+        if !menu_map.contains_key(id) {
+            return Err(AppError::NotFound(format!("菜单 {} 不存在", id)));
+        }
+        if let Some(pid) = parent_id {
+            let parent = menu_map
+                .get(pid)
+                .ok_or(AppError::BadRequest(format!("父菜单 {} 不存在", pid)))?;
+            if parent.menu_type == MenuType::Button.as_str() {
+                return Err(AppError::BadRequest(format!(
+                    "按钮 {} 不能包含子菜单",
+                    parent.name
+                )));
+            }
+        }
+        parent_map.insert(*id, *parent_id);
+    }
+
+    if let Some(id) = find_cycle(&parent_map) {
+        return Err(AppError::BadRequest(format!(
+            "移动后菜单 {} 形成循环引用",
+            menu_map.get(&id).map(|m| m.name.as_str()).unwrap_or_default()
+        )));
+    }
+
+    let now = Utc::now().naive_utc();
+    for (id, parent_id, sort) in moves {
+        menu::Entity::update_many()
+            .col_expr(menu::Column::ParentId, Expr::value(parent_id))
+            .col_expr(menu::Column::Sort, Expr::value(sort))
+            .col_expr(menu::Column::UpdatedTime, Expr::value(now))
+            .col_expr(menu::Column::UpdatedId, Expr::value(user_id))
+            .filter(menu::Column::Id.eq(id))
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+    }
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    // 菜单和权限标识变化后通知在线用户刷新路由与权限
+    push::broadcast(PushEvent::PermissionsChanged);
+
+    Ok(Json(ApiResponse::success_with_message(
+        (),
+        i18n::t(depot, "移动成功"),
+    )))
+}
+
+/// 导出菜单树
+///
+/// 导出的文档不包含数据库ID，导入时按权限标识、路由路径或名称匹配菜单，
+/// 便于在不同环境之间迁移。
+#[endpoint(
+    tags("菜单管理"),
+    parameters(
+        ("format" = Option<String>, Query, description = "导出格式：json（默认）或 yaml"),
+        ("includeRoles" = Option<bool>, Query, description = "是否导出角色分配，默认false"),
+    ),
+    responses(
+        (status_code = 200, description = "导出成功"),
+        (status_code = 400, description = "参数错误"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn export_menus(
+    req: &mut Request,
+    depot: &Depot,
+    res: &mut Response,
+) -> Result<(), AppError> {
+    let format = req.query::<String>("format").unwrap_or_else(|| "json".to_string());
+    let include_roles = req.query::<bool>("includeRoles").unwrap_or(false);
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let menus = menu::Entity::find()
+        .filter(menu::Column::DeletedTime.is_null())
+        .order_by_asc(menu::Column::Sort)
+        .all(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    // 菜单ID -> 角色代码列表
+    let mut role_codes: HashMap<Uuid, Vec<String>> = HashMap::new();
+    if include_roles {
+        let links = role_menu::Entity::find()
+            .filter(role_menu::Column::DeletedTime.is_null())
+            .find_also_related(role::Entity)
+            .all(db.as_ref())
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+        for (link, r) in links {
+            if let Some(r) = r.filter(|r| r.deleted_time.is_none()) {
+                role_codes.entry(link.menu_id).or_default().push(r.code);
+            }
+        }
+        for codes in role_codes.values_mut() {
+            codes.sort();
+        }
+    }
+
+    let document = MenuExportDocument {
+        version: MENU_EXPORT_VERSION,
+        exported_time: Some(Utc::now().format("%Y-%m-%d %H:%M:%S").to_string()),
+        menus: build_export_tree(&menus, None, include_roles.then_some(&role_codes)),
+    };
+
+    match format.as_str() {
+        "json" => {
+            res.add_header(
+                "Content-Disposition",
+                "attachment; filename=\"menus.json\"",
+                true,
+            )
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+            res.render(Json(document));
+        }
+        "yaml" | "yml" => {
+            let body = serde_yaml::to_string(&document)
+                .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+            res.add_header(
+                "Content-Disposition",
+                "attachment; filename=\"menus.yaml\"",
+                true,
+            )
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+            res.add_header("Content-Type", "application/yaml; charset=utf-8", true)
+                .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+            res.write_body(body)
+                .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+        }
+        _ => return Err(AppError::BadRequest("不支持的导出格式".to_string())),
+    }
+
+    Ok(())
+}
+
+/// 导入菜单树
+///
+/// 请求体为导出的 JSON 或 YAML 文档（按 Content-Type 判断）。菜单按
+/// 权限标识、路由路径、父级+名称依次匹配：已存在的更新，不存在的新增，
+/// 文档中未出现的菜单保持不变。`dryRun=true` 时只返回差异不写入。
+#[endpoint(
+    tags("菜单管理"),
+    parameters(
+        ("dryRun" = Option<bool>, Query, description = "是否仅预览差异，默认false"),
+    ),
+    responses(
+        (status_code = 200, description = "导入成功"),
+        (status_code = 400, description = "文档格式错误"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn import_menus(
+    req: &mut Request,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<MenuImportResult>>, AppError> {
+    let dry_run = req.query::<bool>("dryRun").unwrap_or(false);
+    let is_yaml = req
+        .content_type()
+        .map(|ct| ct.essence_str().contains("yaml"))
+        .unwrap_or(false);
+
+    let payload = req
+        .payload()
+        .await
+        .map_err(|e| AppError::BadRequest(format!("读取请求体失败: {}", e)))?;
+
+    let document: MenuExportDocument = if is_yaml {
+        serde_yaml::from_slice(payload)
+            .map_err(|e| AppError::BadRequest(format!("YAML 解析失败: {}", e)))?
+    } else {
+        serde_json::from_slice(payload)
+            .map_err(|e| AppError::BadRequest(format!("JSON 解析失败: {}", e)))?
+    };
+
+    if document.version != MENU_EXPORT_VERSION {
+        return Err(AppError::BadRequest(format!(
+            "不支持的文档版本: {}",
+            document.version
+        )));
+    }
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let user_id = depot
+        .get::<String>("user_id")
+        .ok()
+        .and_then(|s| Uuid::parse_str(s.as_str()).ok());
+
+    // 比对和写入在同一事务内进行，加锁读取现有菜单，避免并发修改后按过期的快照覆盖
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let existing = lock_active_menus(&txn).await?;
+
+    let roles = role::Entity::find()
+        .filter(role::Column::DeletedTime.is_null())
+        .all(&txn)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+    let role_ids: HashMap<String, Uuid> = roles.into_iter().map(|r| (r.code, r.id)).collect();
+
+    // 已软删除的关联不计入现有关联，导入需要时恢复原记录（(role_id, menu_id) 唯一）
+    let (live_links, deleted_links): (Vec<_>, Vec<_>) = role_menu::Entity::find()
+        .all(&txn)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?
+        .into_iter()
+        .partition(|l| l.deleted_time.is_none());
+    let mut deleted_links: HashMap<(Uuid, Uuid), role_menu::Model> = deleted_links
+        .into_iter()
+        .map(|l| ((l.role_id, l.menu_id), l))
+        .collect();
+
+    let mut planner = ImportPlanner {
+        existing_keys: build_existing_key_index(&existing)?,
+        role_ids,
+        linked: live_links.into_iter().map(|l| (l.role_id, l.menu_id)).collect(),
+        seen_keys: Vec::new(),
+        plan: ImportPlan::default(),
+    };
+    planner.plan_nodes(&document.menus, None)?;
+    let plan = planner.plan;
+
+    let result = MenuImportResult {
+        dry_run,
+        created: plan.created.iter().map(|(p, _)| p.diff.clone()).collect(),
+        updated: plan.updated.iter().map(|(p, _)| p.diff.clone()).collect(),
+        unchanged: plan.unchanged,
+        role_links_added: plan.role_links.len(),
+    };
+
+    if dry_run {
+        return Ok(Json(ApiResponse::success(result)));
+    }
+
+    let now = Utc::now().naive_utc();
+    for (planned, model) in plan.created {
+        menu::ActiveModel {
+            id: Set(planned.id),
+            parent_id: Set(planned.parent_id),
+            name: Set(model.name),
+            name_i18n: Set(name_i18n_to_json(model.name_i18n)),
+            menu_type: Set(model.menu_type),
+            path: Set(model.path),
+            component: Set(model.component),
+            icon: Set(model.icon),
+            permission: Set(model.permission),
+            sort: Set(model.sort),
+            is_show: Set(model.is_show),
+            is_cache: Set(model.is_cache),
+            is_external: Set(model.is_external),
+            status: Set(model.status),
+            created_time: Set(now),
+            created_id: Set(user_id),
+            updated_time: Set(now),
+            updated_id: Set(user_id),
+            deleted_time: Set(None),
+            deleted_id: Set(None),
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+    }
+
+    for (planned, model) in plan.updated {
+        let current = existing
+            .iter()
+            .find(|m| m.id == planned.id)
+            .cloned()
+            .ok_or(AppError::InternalServerError("菜单不存在".to_string()))?;
+        let mut active_model: menu::ActiveModel = current.into();
+        active_model.parent_id = Set(planned.parent_id);
+        active_model.name = Set(model.name);
+        active_model.name_i18n = Set(name_i18n_to_json(model.name_i18n));
+        active_model.menu_type = Set(model.menu_type);
+        active_model.path = Set(model.path);
+        active_model.component = Set(model.component);
+        active_model.icon = Set(model.icon);
+        active_model.permission = Set(model.permission);
+        active_model.sort = Set(model.sort);
+        active_model.is_show = Set(model.is_show);
+        active_model.is_cache = Set(model.is_cache);
+        active_model.is_external = Set(model.is_external);
+        active_model.status = Set(model.status);
+        active_model.updated_time = Set(now);
+        active_model.updated_id = Set(user_id);
+        active_model
+            .update(&txn)
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+    }
+
+    for (role_id, menu_id) in plan.role_links {
+        if let Some(link) = deleted_links.remove(&(role_id, menu_id)) {
+            let mut active_model: role_menu::ActiveModel = link.into();
+            active_model.deleted_time = Set(None);
+            active_model.deleted_id = Set(None);
+            active_model
+                .update(&txn)
+                .await
+                .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+            continue;
+        }
+        role_menu::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            role_id: Set(role_id),
+            menu_id: Set(menu_id),
+            created_time: Set(now),
+            created_id: Set(user_id),
+            deleted_time: Set(None),
+            deleted_id: Set(None),
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+    }
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    // 菜单和权限标识变化后通知在线用户刷新路由与权限
+    push::broadcast(PushEvent::PermissionsChanged);
+
+    Ok(Json(ApiResponse::success_with_message(
+        result,
+        i18n::t(depot, "导入成功"),
     )))
 }
 
 // ========== 辅助函数 ==========
 
+/// 加锁读取全部有效菜单（SELECT ... FOR UPDATE）
+///
+/// 修改父级关系的操作在同一事务内基于加锁结果校验树结构，并发修改依次执行，
+/// 不会各自通过校验后共同形成循环引用。
+async fn lock_active_menus<C: ConnectionTrait>(db: &C) -> Result<Vec<menu::Model>, AppError> {
+    menu::Entity::find()
+        .filter(menu::Column::DeletedTime.is_null())
+        .lock_exclusive()
+        .all(db)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))
+}
+
+/// 加锁校验父菜单：必须存在、未被删除，且不能是按钮
+async fn validate_parent<C: ConnectionTrait>(db: &C, parent_id: Uuid) -> Result<menu::Model, AppError> {
+    let parent = menu::Entity::find_by_id(parent_id)
+        .filter(menu::Column::DeletedTime.is_null())
+        .lock_exclusive()
+        .one(db)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?
+        .ok_or(AppError::BadRequest("父菜单不存在".to_string()))?;
+
+    if parent.menu_type == MenuType::Button.as_str() {
+        return Err(AppError::BadRequest("按钮不能包含子菜单".to_string()));
+    }
+
+    Ok(parent)
+}
+
+/// 将多语言名称转换为 JSONB 值，空映射视为未设置
+fn name_i18n_to_json(name_i18n: Option<HashMap<String, String>>) -> Option<serde_json::Value> {
+    name_i18n
+        .filter(|m| !m.is_empty())
+        .map(|m| serde_json::json!(m))
+}
+
+/// 解析菜单的多语言名称
+fn name_i18n_of(m: &menu::Model) -> Option<HashMap<String, String>> {
+    m.name_i18n
+        .as_ref()
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+}
+
+/// 按语言获取菜单名称，没有对应译名时使用默认名称
+fn localized_name(m: &menu::Model, locale: Locale) -> String {
+    if locale == Locale::ZhCn {
+        return m.name.clone();
+    }
+    name_i18n_of(m)
+        .as_ref()
+        .and_then(|names| i18n::pick_translation(names, locale).map(str::to_string))
+        .unwrap_or_else(|| m.name.clone())
+}
+
+/// 按父级递归构建导出节点
+fn build_export_tree(
+    menus: &[menu::Model],
+    parent_id: Option<Uuid>,
+    role_codes: Option<&HashMap<Uuid, Vec<String>>>,
+) -> Vec<MenuExportNode> {
+    let mut result: Vec<(i32, MenuExportNode)> = menus
+        .iter()
+        .filter(|m| m.parent_id == parent_id)
+        .map(|m| {
+            let node = MenuExportNode {
+                name: m.name.clone(),
+                name_i18n: name_i18n_of(m),
+                menu_type: m.menu_type.clone(),
+                path: m.path.clone(),
+                component: m.component.clone(),
+                icon: m.icon.clone(),
+                permission: m.permission.clone(),
+                sort: m.sort,
+                is_show: m.is_show,
+                is_cache: m.is_cache,
+                is_external: m.is_external,
+                status: m.status,
+                roles: role_codes.map(|codes| codes.get(&m.id).cloned().unwrap_or_default()),
+                children: build_export_tree(menus, Some(m.id), role_codes),
+            };
+            (m.sort, node)
+        })
+        .collect();
+
+    result.sort_by_key(|(sort, _)| *sort);
+    result.into_iter().map(|(_, node)| node).collect()
+}
+
+/// 计算菜单的匹配键：优先权限标识，其次路由路径，最后使用父级键+名称
+fn menu_key(permission: Option<&str>, path: Option<&str>, parent_key: &str, name: &str) -> String {
+    if let Some(permission) = permission.filter(|p| !p.is_empty()) {
+        return format!("permission:{}", permission);
+    }
+    if let Some(path) = path.filter(|p| !p.is_empty()) {
+        return format!("path:{}", path);
+    }
+    format!("name:{}/{}", parent_key, name)
+}
+
+/// 为现有菜单树建立 匹配键 -> 菜单 的索引
+///
+/// 多个菜单匹配键相同时无法确定导入节点对应哪一个，返回错误，需先修正现有菜单。
+fn build_existing_key_index(
+    menus: &[menu::Model],
+) -> Result<HashMap<String, &menu::Model>, AppError> {
+    let mut index: HashMap<String, &menu::Model> = HashMap::new();
+    let mut queue: Vec<(Option<Uuid>, String)> = vec![(None, String::new())];
+    while let Some((parent_id, parent_key)) = queue.pop() {
+        for m in menus.iter().filter(|m| m.parent_id == parent_id) {
+            let key = menu_key(m.permission.as_deref(), m.path.as_deref(), &parent_key, &m.name);
+            if let Some(other) = index.insert(key.clone(), m) {
+                return Err(AppError::BadRequest(format!(
+                    "现有菜单 {} 和 {} 的匹配键重复: {}",
+                    other.name, m.name, key
+                )));
+            }
+            queue.push((Some(m.id), key));
+        }
+    }
+    Ok(index)
+}
+
+/// 导入计划中的单个菜单
+struct PlannedMenu {
+    id: Uuid,
+    parent_id: Option<Uuid>,
+    diff: MenuImportDiffItem,
+}
+
+/// 菜单导入计划
+#[derive(Default)]
+struct ImportPlan {
+    created: Vec<(PlannedMenu, MenuExportNode)>,
+    updated: Vec<(PlannedMenu, MenuExportNode)>,
+    unchanged: usize,
+    role_links: Vec<(Uuid, Uuid)>,
+}
+
+/// 将导入文档与现有菜单比对，生成导入计划
+struct ImportPlanner<'a> {
+    existing_keys: HashMap<String, &'a menu::Model>,
+    role_ids: HashMap<String, Uuid>,
+    linked: Vec<(Uuid, Uuid)>,
+    seen_keys: Vec<String>,
+    plan: ImportPlan,
+}
+
+impl ImportPlanner<'_> {
+    /// 递归处理一组兄弟节点，`parent` 为 (父菜单ID, 父级匹配键, 父级是否按钮)
+    fn plan_nodes(
+        &mut self,
+        nodes: &[MenuExportNode],
+        parent: Option<(Uuid, &str, bool)>,
+    ) -> Result<(), AppError> {
+        let parent_id = parent.map(|(id, _, _)| id);
+        let parent_key = parent.map(|(_, key, _)| key).unwrap_or_default();
+
+        for node in nodes {
+            if MenuType::from_str(&node.menu_type).is_none() {
+                return Err(AppError::BadRequest(format!(
+                    "菜单 {} 的类型无效: {}",
+                    node.name, node.menu_type
+                )));
+            }
+            if parent.is_some_and(|(_, _, is_button)| is_button) {
+                return Err(AppError::BadRequest(format!(
+                    "按钮不能包含子菜单: {}",
+                    node.name
+                )));
+            }
+
+            let key = menu_key(
+                node.permission.as_deref(),
+                node.path.as_deref(),
+                parent_key,
+                &node.name,
+            );
+            if self.seen_keys.contains(&key) {
+                return Err(AppError::BadRequest(format!("文档中存在重复的菜单: {}", key)));
+            }
+            self.seen_keys.push(key.clone());
+
+            let mut flat = node.clone();
+            flat.children = Vec::new();
+            flat.roles = None;
+
+            let id = match self.existing_keys.get(&key) {
+                Some(existing) => {
+                    let changes = diff_menu_fields(existing, node, parent_id);
+                    if changes.is_empty() {
+                        self.plan.unchanged += 1;
+                    } else {
+                        self.plan.updated.push((
+                            PlannedMenu {
+                                id: existing.id,
+                                parent_id,
+                                diff: MenuImportDiffItem {
+                                    key: key.clone(),
+                                    name: node.name.clone(),
+                                    changes,
+                                },
+                            },
+                            flat,
+                        ));
+                    }
+                    existing.id
+                }
+                None => {
+                    let id = Uuid::new_v4();
+                    self.plan.created.push((
+                        PlannedMenu {
+                            id,
+                            parent_id,
+                            diff: MenuImportDiffItem {
+                                key: key.clone(),
+                                name: node.name.clone(),
+                                changes: Vec::new(),
+                            },
+                        },
+                        flat,
+                    ));
+                    id
+                }
+            };
+
+            if let Some(codes) = &node.roles {
+                for code in codes {
+                    let role_id = *self
+                        .role_ids
+                        .get(code)
+                        .ok_or(AppError::BadRequest(format!("角色不存在: {}", code)))?;
+                    if !self.linked.contains(&(role_id, id)) {
+                        self.linked.push((role_id, id));
+                        self.plan.role_links.push((role_id, id));
+                    }
+                }
+            }
+
+            let is_button = node.menu_type == MenuType::Button.as_str();
+            self.plan_nodes(&node.children, Some((id, &key, is_button)))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// 比较现有菜单与导入节点，返回发生变化的字段名
+fn diff_menu_fields(
+    existing: &menu::Model,
+    node: &MenuExportNode,
+    parent_id: Option<Uuid>,
+) -> Vec<String> {
+    let mut changes = Vec::new();
+    let mut check = |field: &str, changed: bool| {
+        if changed {
+            changes.push(field.to_string());
+        }
+    };
+    check("parentId", existing.parent_id != parent_id);
+    check("name", existing.name != node.name);
+    check(
+        "nameI18n",
+        name_i18n_of(existing) != node.name_i18n.clone().filter(|m| !m.is_empty()),
+    );
+    check("menuType", existing.menu_type != node.menu_type);
+    check("path", existing.path != node.path);
+    check("component", existing.component != node.component);
+    check("icon", existing.icon != node.icon);
+    check("permission", existing.permission != node.permission);
+    check("sort", existing.sort != node.sort);
+    check("isShow", existing.is_show != node.is_show);
+    check("isCache", existing.is_cache != node.is_cache);
+    check("isExternal", existing.is_external != node.is_external);
+    check("status", existing.status != node.status);
+    changes
+}
+
+/// 检查父级关系中是否存在环，存在时返回环上的任一菜单ID
+///
+/// 已确认能走到根的菜单不再重复遍历，整体为线性复杂度。
+fn find_cycle(parent_map: &HashMap<Uuid, Option<Uuid>>) -> Option<Uuid> {
+    let mut acyclic: HashSet<Uuid> = HashSet::new();
+    for start in parent_map.keys() {
+        let mut path: HashSet<Uuid> = HashSet::new();
+        let mut current = Some(*start);
+        while let Some(id) = current {
+            if acyclic.contains(&id) {
+                break;
+            }
+            if !path.insert(id) {
+                return Some(id);
+            }
+            current = parent_map.get(&id).copied().flatten();
+        }
+        acyclic.extend(path);
+    }
+    None
+}
+
+/// 收集指定菜单的全部后代菜单ID（不含自身）
+fn collect_descendant_ids(menus: &[menu::Model], root_id: Uuid) -> Vec<Uuid> {
+    let mut children_map: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    for m in menus {
+        if let Some(pid) = m.parent_id {
+            children_map.entry(pid).or_default().push(m.id);
+        }
+    }
+
+    let mut result = Vec::new();
+    let mut seen: HashSet<Uuid> = HashSet::new();
+    let mut stack = vec![root_id];
+    while let Some(id) = stack.pop() {
+        if let Some(children) = children_map.get(&id) {
+            for child in children {
+                // 防御历史脏数据中已存在的环
+                if *child != root_id && seen.insert(*child) {
+                    result.push(*child);
+                    stack.push(*child);
+                }
+            }
+        }
+    }
+    result
+}
+
 fn model_to_response(m: &menu::Model) -> MenuResponse {
     MenuResponse {
         id: m.id.to_string(),
         parent_id: m.parent_id.map(|id| id.to_string()),
         name: m.name.clone(),
+        name_i18n: name_i18n_of(m),
         menu_type: m.menu_type.clone(),
         path: m.path.clone(),
         component: m.component.clone(),
@@ -433,15 +1428,51 @@ fn build_menu_tree(menus: &[menu::Model], parent_id: Option<Uuid>) -> Vec<MenuRe
 fn build_menu_tree_for_route(
     menus: &[menu::Model],
     parent_id: Option<Uuid>,
+    locale: Locale,
+    with_meta: bool,
 ) -> Vec<MenuTreeResponse> {
-    // 构建父子关系映射
+    let button = MenuType::Button.as_str();
+    let menu_map: HashMap<Uuid, &menu::Model> = menus.iter().map(|m| (m.id, m)).collect();
+
+    // 构建父子关系映射（输出元信息时按钮不作为路由，汇总为所属页面的权限）
     let mut children_map: HashMap<Option<Uuid>, Vec<&menu::Model>> = HashMap::new();
+    let mut button_permissions: HashMap<Uuid, Vec<String>> = HashMap::new();
     for m in menus {
-        children_map.entry(m.parent_id).or_default().push(m);
+        if with_meta && m.menu_type == button {
+            if let (Some(pid), Some(permission)) = (m.parent_id, &m.permission) {
+                button_permissions.entry(pid).or_default().push(permission.clone());
+            }
+        } else {
+            children_map.entry(m.parent_id).or_default().push(m);
+        }
     }
 
+    // 隐藏页面激活时高亮最近的可见上级菜单
+    let active_menu = |m: &menu::Model| -> Option<String> {
+        if m.is_show {
+            return None;
+        }
+        let mut current = m.parent_id.and_then(|pid| menu_map.get(&pid));
+        let mut depth = 0;
+        while let Some(parent) = current {
+            if parent.is_show && parent.path.is_some() {
+                return parent.path.clone();
+            }
+            depth += 1;
+            if depth > menus.len() {
+                break;
+            }
+            current = parent.parent_id.and_then(|pid| menu_map.get(&pid));
+        }
+        None
+    };
+
     fn build_recursive(
         children_map: &HashMap<Option<Uuid>, Vec<&menu::Model>>,
+        button_permissions: &HashMap<Uuid, Vec<String>>,
+        active_menu: &dyn Fn(&menu::Model) -> Option<String>,
+        with_meta: bool,
+        locale: Locale,
         parent_id: Option<Uuid>,
     ) -> Vec<MenuTreeResponse> {
         let mut result: Vec<MenuTreeResponse> = children_map
@@ -450,15 +1481,36 @@ fn build_menu_tree_for_route(
                 children
                     .iter()
                     .map(|m| {
-                        let sub_children = build_recursive(children_map, Some(m.id));
+                        let sub_children = build_recursive(
+                            children_map,
+                            button_permissions,
+                            active_menu,
+                            with_meta,
+                            locale,
+                            Some(m.id),
+                        );
+                        let title = localized_name(m, locale);
                         MenuTreeResponse {
                             id: m.id.to_string(),
-                            name: m.name.clone(),
+                            name: title.clone(),
                             menu_type: m.menu_type.clone(),
                             path: m.path.clone(),
                             component: m.component.clone(),
                             icon: m.icon.clone(),
                             sort: m.sort,
+                            meta: with_meta.then(|| RouteMeta {
+                                title,
+                                icon: m.icon.clone(),
+                                keep_alive: m.is_cache,
+                                hidden: !m.is_show,
+                                link: if m.is_external { m.path.clone() } else { None },
+                                permissions: button_permissions
+                                    .get(&m.id)
+                                    .cloned()
+                                    .unwrap_or_default(),
+                                active_menu: active_menu(m),
+                                order: m.sort,
+                            }),
                             children: if sub_children.is_empty() {
                                 None
                             } else {
@@ -474,5 +1526,579 @@ fn build_menu_tree_for_route(
         result
     }
 
-    build_recursive(&children_map, parent_id)
+    build_recursive(
+        &children_map,
+        &button_permissions,
+        &active_menu,
+        with_meta,
+        locale,
+        parent_id,
+    )
+}
+
+/// 将路由节点转换为 vue-router 路由记录
+fn to_vue_route(node: MenuTreeResponse) -> VueRouteRecord {
+    let path = node.path.unwrap_or_default();
+    let children: Vec<VueRouteRecord> = node
+        .children
+        .unwrap_or_default()
+        .into_iter()
+        .map(to_vue_route)
+        .collect();
+    let redirect = if node.menu_type == MenuType::Catalog.as_str() {
+        children.first().map(|c| c.path.clone())
+    } else {
+        None
+    };
+
+    VueRouteRecord {
+        // 路径可能互相冲突（如 /system/user 与 /system-user），取菜单ID保证唯一
+        name: node.id,
+        path,
+        component: node.component,
+        redirect,
+        meta: node.meta.unwrap_or_default(),
+        children,
+    }
+}
+
+/// 将路由节点转换为 react-router 路由对象
+fn to_react_route(node: MenuTreeResponse) -> ReactRouteObject {
+    ReactRouteObject {
+        id: node.id,
+        path: node.path.unwrap_or_default(),
+        component: node.component,
+        handle: node.meta.unwrap_or_default(),
+        children: node
+            .children
+            .unwrap_or_default()
+            .into_iter()
+            .map(to_react_route)
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::middleware::auth_middleware;
+    use crate::common::testing;
+    use salvo::test::{ResponseExt, TestClient};
+    use sea_orm::PaginatorTrait;
+
+    fn menu(id: u128, parent_id: Option<u128>, name: &str) -> menu::Model {
+        let now = Utc::now().naive_utc();
+        menu::Model {
+            id: Uuid::from_u128(id),
+            parent_id: parent_id.map(Uuid::from_u128),
+            name: name.to_string(),
+            name_i18n: None,
+            menu_type: MenuType::Menu.as_str().to_string(),
+            path: None,
+            component: None,
+            icon: None,
+            permission: None,
+            sort: 0,
+            is_show: true,
+            is_cache: false,
+            is_external: false,
+            status: 1,
+            created_time: now,
+            created_id: None,
+            updated_time: now,
+            updated_id: None,
+            deleted_time: None,
+            deleted_id: None,
+        }
+    }
+
+    fn node(name: &str, permission: Option<&str>, children: Vec<MenuExportNode>) -> MenuExportNode {
+        MenuExportNode {
+            name: name.to_string(),
+            name_i18n: None,
+            menu_type: MenuType::Menu.as_str().to_string(),
+            path: None,
+            component: None,
+            icon: None,
+            permission: permission.map(str::to_string),
+            sort: 0,
+            is_show: true,
+            is_cache: false,
+            is_external: false,
+            status: 1,
+            roles: None,
+            children,
+        }
+    }
+
+    fn planner<'a>(existing: &'a [menu::Model], roles: &[(&str, u128)]) -> ImportPlanner<'a> {
+        ImportPlanner {
+            existing_keys: build_existing_key_index(existing).unwrap(),
+            role_ids: roles
+                .iter()
+                .map(|(code, id)| (code.to_string(), Uuid::from_u128(*id)))
+                .collect(),
+            linked: Vec::new(),
+            seen_keys: Vec::new(),
+            plan: ImportPlan::default(),
+        }
+    }
+
+    fn parent_map(edges: &[(u128, Option<u128>)]) -> HashMap<Uuid, Option<Uuid>> {
+        edges
+            .iter()
+            .map(|(id, parent)| (Uuid::from_u128(*id), parent.map(Uuid::from_u128)))
+            .collect()
+    }
+
+    #[test]
+    fn find_cycle_accepts_forest() {
+        let map = parent_map(&[
+            (1, None),
+            (2, Some(1)),
+            (3, Some(2)),
+            (4, Some(1)),
+            (5, None),
+        ]);
+        assert_eq!(find_cycle(&map), None);
+    }
+
+    #[test]
+    fn find_cycle_reports_member_of_cycle() {
+        let map = parent_map(&[
+            (1, None),
+            (2, Some(4)),
+            (3, Some(2)),
+            (4, Some(3)),
+            (5, Some(3)),
+        ]);
+        let id = find_cycle(&map).expect("cycle");
+        assert!([2, 3, 4].map(Uuid::from_u128).contains(&id));
+    }
+
+    #[test]
+    fn find_cycle_reports_self_parent() {
+        let map = parent_map(&[(1, Some(1))]);
+        assert_eq!(find_cycle(&map), Some(Uuid::from_u128(1)));
+    }
+
+    #[test]
+    fn collect_descendant_ids_walks_whole_subtree() {
+        let menus = vec![
+            menu(1, None, "root"),
+            menu(2, Some(1), "a"),
+            menu(3, Some(2), "b"),
+            menu(4, Some(1), "c"),
+            menu(5, None, "other"),
+            menu(6, Some(5), "d"),
+        ];
+        let mut ids = collect_descendant_ids(&menus, Uuid::from_u128(1));
+        ids.sort();
+        assert_eq!(ids, [2, 3, 4].map(Uuid::from_u128));
+        assert!(collect_descendant_ids(&menus, Uuid::from_u128(3)).is_empty());
+    }
+
+    #[test]
+    fn collect_descendant_ids_stops_on_existing_cycle() {
+        let menus = vec![
+            menu(1, Some(3), "a"),
+            menu(2, Some(1), "b"),
+            menu(3, Some(2), "c"),
+        ];
+        let mut ids = collect_descendant_ids(&menus, Uuid::from_u128(1));
+        ids.sort();
+        assert_eq!(ids, [2, 3].map(Uuid::from_u128));
+    }
+
+    #[test]
+    fn menu_key_prefers_permission_then_path_then_name() {
+        assert_eq!(
+            menu_key(Some("system:user:list"), Some("/system/user"), "", "用户"),
+            "permission:system:user:list"
+        );
+        assert_eq!(
+            menu_key(Some(""), Some("/system/user"), "", "用户"),
+            "path:/system/user"
+        );
+        assert_eq!(
+            menu_key(None, None, "path:/system", "新增"),
+            "name:path:/system/新增"
+        );
+    }
+
+    #[test]
+    fn existing_key_index_rejects_duplicate_keys() {
+        let mut a = menu(1, None, "a");
+        a.permission = Some("dup".to_string());
+        let mut b = menu(2, None, "b");
+        b.permission = Some("dup".to_string());
+        assert!(build_existing_key_index(&[a.clone()]).is_ok());
+        assert!(matches!(
+            build_existing_key_index(&[a, b]),
+            Err(AppError::BadRequest(_))
+        ));
+    }
+
+    #[test]
+    fn import_planner_splits_created_updated_and_unchanged() {
+        let mut system = menu(1, None, "系统管理");
+        system.permission = Some("system".to_string());
+        let mut user = menu(2, Some(1), "用户管理");
+        user.permission = Some("system:user".to_string());
+        let existing = vec![system, user];
+
+        let mut renamed = node("用户", Some("system:user"), Vec::new());
+        renamed.roles = Some(vec!["admin".to_string()]);
+        let document = vec![node(
+            "系统管理",
+            Some("system"),
+            vec![renamed, node("角色管理", Some("system:role"), Vec::new())],
+        )];
+
+        let mut planner = planner(&existing, &[("admin", 9)]);
+        planner.plan_nodes(&document, None).unwrap();
+        let plan = planner.plan;
+
+        assert_eq!(plan.unchanged, 1);
+        assert_eq!(plan.updated.len(), 1);
+        assert_eq!(plan.updated[0].0.id, Uuid::from_u128(2));
+        assert_eq!(plan.updated[0].0.diff.changes, ["name"]);
+        assert_eq!(plan.created.len(), 1);
+        assert_eq!(plan.created[0].0.parent_id, Some(Uuid::from_u128(1)));
+        assert_eq!(plan.role_links, [(Uuid::from_u128(9), Uuid::from_u128(2))]);
+    }
+
+    #[test]
+    fn import_planner_rejects_invalid_documents() {
+        let duplicated = vec![
+            node("a", Some("p"), Vec::new()),
+            node("b", Some("p"), Vec::new()),
+        ];
+        assert!(planner(&[], &[]).plan_nodes(&duplicated, None).is_err());
+
+        let mut unknown_role = node("a", Some("p"), Vec::new());
+        unknown_role.roles = Some(vec!["missing".to_string()]);
+        assert!(planner(&[], &[]).plan_nodes(&[unknown_role], None).is_err());
+
+        let mut button = node(
+            "按钮",
+            Some("p:add"),
+            vec![node("子菜单", None, Vec::new())],
+        );
+        button.menu_type = MenuType::Button.as_str().to_string();
+        assert!(planner(&[], &[]).plan_nodes(&[button], None).is_err());
+    }
+
+    fn route_menus() -> Vec<menu::Model> {
+        let mut system = menu(1, None, "系统管理");
+        system.menu_type = MenuType::Catalog.as_str().to_string();
+        system.path = Some("/system".to_string());
+        let mut user = menu(2, Some(1), "用户管理");
+        user.path = Some("/system/user".to_string());
+        user.component = Some("/views/system/user/index".to_string());
+        user.is_cache = true;
+        user.sort = 1;
+        let mut add = menu(3, Some(2), "新增用户");
+        add.menu_type = MenuType::Button.as_str().to_string();
+        add.permission = Some("system:user:add".to_string());
+        let mut detail = menu(4, Some(2), "用户详情");
+        detail.path = Some("/system/user/detail".to_string());
+        detail.is_show = false;
+        let mut docs = menu(5, Some(1), "文档");
+        docs.path = Some("https://example.com".to_string());
+        docs.is_external = true;
+        docs.sort = 2;
+        vec![system, user, add, detail, docs]
+    }
+
+    #[test]
+    fn route_tree_folds_buttons_into_meta() {
+        let tree = build_menu_tree_for_route(&route_menus(), None, Locale::ZhCn, true);
+        assert_eq!(tree.len(), 1);
+        let children = tree[0].children.as_ref().unwrap();
+        let user = &children[0];
+        let meta = user.meta.as_ref().unwrap();
+        assert!(meta.keep_alive);
+        assert_eq!(meta.permissions, ["system:user:add"]);
+
+        let detail = &user.children.as_ref().unwrap()[0];
+        let detail_meta = detail.meta.as_ref().unwrap();
+        assert!(detail_meta.hidden);
+        assert_eq!(detail_meta.active_menu.as_deref(), Some("/system/user"));
+
+        let docs = children[1].meta.as_ref().unwrap();
+        assert_eq!(docs.link.as_deref(), Some("https://example.com"));
+    }
+
+    #[test]
+    fn route_tree_keeps_buttons_without_meta() {
+        let tree = build_menu_tree_for_route(&route_menus(), None, Locale::ZhCn, false);
+        let user = &tree[0].children.as_ref().unwrap()[0];
+        assert!(user.meta.is_none());
+        assert_eq!(user.children.as_ref().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn vue_route_redirects_catalog_to_first_child() {
+        let tree = build_menu_tree_for_route(&route_menus(), None, Locale::ZhCn, true);
+        let route = to_vue_route(tree.into_iter().next().unwrap());
+        assert_eq!(route.name, Uuid::from_u128(1).to_string());
+        assert_eq!(route.redirect.as_deref(), Some("/system/user"));
+        assert_eq!(route.children[0].redirect, None);
+        assert_eq!(
+            route.children[0].component.as_deref(),
+            Some("/views/system/user/index")
+        );
+    }
+
+    #[test]
+    fn react_route_carries_meta_in_handle() {
+        let tree = build_menu_tree_for_route(&route_menus(), None, Locale::ZhCn, true);
+        let route = to_react_route(tree.into_iter().next().unwrap());
+        assert_eq!(route.id, Uuid::from_u128(1).to_string());
+        assert_eq!(route.path, "/system");
+        assert_eq!(route.children[0].handle.title, "用户管理");
+        assert_eq!(route.children[0].handle.permissions, ["system:user:add"]);
+        assert_eq!(route.children[0].children[0].path, "/system/user/detail");
+    }
+
+    async fn import(
+        db: Option<Arc<DatabaseConnection>>,
+        token: &str,
+        body: &str,
+    ) -> Response {
+        TestClient::post("http://127.0.0.1/menu/import")
+            .bearer_auth(token)
+            .raw_json(body.to_string())
+            .send(&testing::service(super::super::routes::routes(), db))
+            .await
+    }
+
+    #[tokio::test]
+    async fn batch_move_requires_admin() {
+        let token = testing::access_token(Uuid::new_v4(), Uuid::new_v4(), "editor");
+        let res = TestClient::put("http://127.0.0.1/menu/batchMove")
+            .bearer_auth(token)
+            .raw_json("[]")
+            .send(&testing::service(super::super::routes::routes(), None))
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::FORBIDDEN));
+    }
+
+    #[tokio::test]
+    async fn import_requires_admin() {
+        let token = testing::access_token(Uuid::new_v4(), Uuid::new_v4(), "editor");
+        let mut res = import(None, &token, "{}").await;
+        assert_eq!(res.status_code, Some(StatusCode::FORBIDDEN));
+        assert!(res.take_string().await.unwrap().contains("403"));
+    }
+
+    #[tokio::test]
+    async fn import_restores_soft_deleted_role_link() {
+        let Some(db) = testing::TestDb::migrated().await else {
+            return;
+        };
+        let role_id = Uuid::new_v4();
+        let menu_id = Uuid::new_v4();
+        db.execute_unprepared(&format!(
+            "INSERT INTO roles (id, code, name) VALUES ('{role_id}', 'editor', '编辑');
+             INSERT INTO menus (id, name, menu_type, permission) VALUES ('{menu_id}', '文章', 'menu', 'cms:article');
+             INSERT INTO role_menus (role_id, menu_id, deleted_time) VALUES ('{role_id}', '{menu_id}', now());"
+        ))
+        .await
+        .unwrap();
+
+        let mut article = node("文章", Some("cms:article"), Vec::new());
+        article.roles = Some(vec!["editor".to_string()]);
+        let document = MenuExportDocument {
+            version: MENU_EXPORT_VERSION,
+            exported_time: None,
+            menus: vec![article],
+        };
+        let mut res = import(
+            Some(db.arc()),
+            &testing::admin_token(),
+            &serde_json::to_string(&document).unwrap(),
+        )
+        .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        let body: serde_json::Value = res.take_json().await.unwrap();
+        assert_eq!(body["data"]["roleLinksAdded"], 1);
+
+        let links = role_menu::Entity::find()
+            .filter(role_menu::Column::RoleId.eq(role_id))
+            .all(&*db)
+            .await
+            .unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].menu_id, menu_id);
+        assert!(links[0].deleted_time.is_none());
+    }
+
+    /// 在 `menu/{id}` 上挂载菜单修改和删除接口
+    fn menu_service(db: &testing::TestDb) -> Service {
+        let router = Router::with_path("menu/{id}")
+            .hoop(auth_middleware)
+            .put(update_menu)
+            .delete(delete_menu);
+        testing::service(router, Some(db.arc()))
+    }
+
+    /// 插入 根 → 子 → 孙 三级菜单，并把三个菜单都授权给一个角色，返回 (角色, [根, 子, 孙])
+    async fn insert_menu_chain(db: &testing::TestDb) -> (Uuid, [Uuid; 3]) {
+        let role_id = Uuid::new_v4();
+        let ids = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+        db.execute_unprepared(&format!(
+            "INSERT INTO roles (id, code, name) VALUES ('{role_id}', 'editor', '编辑');
+             INSERT INTO menus (id, parent_id, name, menu_type) VALUES
+                 ('{a}', NULL, '根', 'catalog'),
+                 ('{b}', '{a}', '子', 'menu'),
+                 ('{c}', '{b}', '孙', 'menu');
+             INSERT INTO role_menus (role_id, menu_id) VALUES
+                 ('{role_id}', '{a}'), ('{role_id}', '{b}'), ('{role_id}', '{c}');",
+            a = ids[0],
+            b = ids[1],
+            c = ids[2],
+        ))
+        .await
+        .unwrap();
+        (role_id, ids)
+    }
+
+    #[tokio::test]
+    async fn delete_refuses_children_unless_cascade() {
+        let Some(db) = testing::TestDb::migrated().await else {
+            return;
+        };
+        let (role_id, [root, child, grandchild]) = insert_menu_chain(&db).await;
+        let service = menu_service(&db);
+
+        let res = TestClient::delete(format!("http://127.0.0.1/menu/{}", root))
+            .bearer_auth(testing::admin_token())
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::BAD_REQUEST));
+        let deleted = menu::Entity::find()
+            .filter(menu::Column::DeletedTime.is_not_null())
+            .count(&*db)
+            .await
+            .unwrap();
+        assert_eq!(deleted, 0);
+
+        let res = TestClient::delete(format!("http://127.0.0.1/menu/{}?cascade=true", root))
+            .bearer_auth(testing::admin_token())
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+
+        // 整棵子树及其角色关联一并软删除
+        let deleted: Vec<Uuid> = menu::Entity::find()
+            .filter(menu::Column::DeletedTime.is_not_null())
+            .all(&*db)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| m.id)
+            .collect();
+        assert_eq!(deleted.len(), 3);
+        assert!([root, child, grandchild].iter().all(|id| deleted.contains(id)));
+        let links = role_menu::Entity::find()
+            .filter(role_menu::Column::RoleId.eq(role_id))
+            .all(&*db)
+            .await
+            .unwrap();
+        assert_eq!(links.len(), 3);
+        assert!(links.iter().all(|l| l.deleted_time.is_some()));
+    }
+
+    #[tokio::test]
+    async fn update_rejects_self_or_descendant_as_parent() {
+        let Some(db) = testing::TestDb::migrated().await else {
+            return;
+        };
+        let (_, [root, child, grandchild]) = insert_menu_chain(&db).await;
+        let service = menu_service(&db);
+
+        for parent in [root, grandchild] {
+            let res = TestClient::put(format!("http://127.0.0.1/menu/{}", root))
+                .bearer_auth(testing::admin_token())
+                .json(&serde_json::json!({ "parentId": parent.to_string() }))
+                .send(&service)
+                .await;
+            assert_eq!(res.status_code, Some(StatusCode::BAD_REQUEST));
+        }
+        let root_menu = menu::Entity::find_by_id(root).one(&*db).await.unwrap().unwrap();
+        assert_eq!(root_menu.parent_id, None);
+
+        // 移到非子孙菜单下是允许的
+        let res = TestClient::put(format!("http://127.0.0.1/menu/{}", grandchild))
+            .bearer_auth(testing::admin_token())
+            .json(&serde_json::json!({ "parentId": root.to_string() }))
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        let moved = menu::Entity::find_by_id(grandchild).one(&*db).await.unwrap().unwrap();
+        assert_eq!(moved.parent_id, Some(root));
+        assert_ne!(moved.parent_id, Some(child));
+    }
+
+    #[tokio::test]
+    async fn search_returns_breadcrumb_and_live_roles() {
+        let Some(db) = testing::TestDb::migrated().await else {
+            return;
+        };
+        let (_, [_, _, grandchild]) = insert_menu_chain(&db).await;
+        let removed_role = Uuid::new_v4();
+        db.execute_unprepared(&format!(
+            "UPDATE menus SET permission = 'cms:article:edit' WHERE id = '{grandchild}';
+             INSERT INTO roles (id, code, name) VALUES ('{removed_role}', 'viewer', '访客');
+             INSERT INTO role_menus (role_id, menu_id, deleted_time)
+                 VALUES ('{removed_role}', '{grandchild}', now());"
+        ))
+        .await
+        .unwrap();
+        let service = testing::service(super::super::routes::routes(), Some(db.arc()));
+
+        let url = "http://127.0.0.1/menu/search?keyword=ARTICLE:EDIT&field=permission";
+        let mut res = TestClient::get(url)
+            .bearer_auth(testing::admin_token())
+            .send(&service)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        let body: serde_json::Value = res.take_json().await.unwrap();
+        let results = body["data"].as_array().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["menu"]["id"], grandchild.to_string());
+        assert_eq!(results[0]["matchedFields"], serde_json::json!(["permission"]));
+        let breadcrumb: Vec<&str> = results[0]["breadcrumb"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|b| b["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(breadcrumb, ["根", "子"]);
+        let roles: Vec<&str> = results[0]["roles"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["roleCode"].as_str().unwrap())
+            .collect();
+        assert_eq!(roles, ["editor"]);
+
+        // 限定其它字段时不命中，未知字段报错
+        for (query, status) in [
+            ("keyword=article&field=name", StatusCode::OK),
+            ("keyword=article&field=other", StatusCode::BAD_REQUEST),
+        ] {
+            let mut res = TestClient::get(format!("http://127.0.0.1/menu/search?{}", query))
+                .bearer_auth(testing::admin_token())
+                .send(&service)
+                .await;
+            assert_eq!(res.status_code, Some(status));
+            if status == StatusCode::OK {
+                let body: serde_json::Value = res.take_json().await.unwrap();
+                assert_eq!(body["data"], serde_json::json!([]));
+            }
+        }
+    }
 }
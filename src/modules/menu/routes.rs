@@ -1,5 +1,5 @@
 use salvo::prelude::*;
-use crate::common::middleware::auth_middleware;
+use crate::common::middleware::{admin_only, auth_middleware};
 use super::handler;
 
 pub fn routes() -> Router {
@@ -9,6 +9,15 @@ pub fn routes() -> Router {
         .push(Router::with_path("tree").get(handler::get_menu_tree))
         .push(Router::with_path("getUserRoutes").get(handler::get_user_menus))
         .push(Router::with_path("permissions").get(handler::get_user_permissions))
+        .push(Router::with_path("search").get(handler::search_menus))
+        .push(Router::with_path("export").get(handler::export_menus))
+        // 批量移动会调整整棵菜单树，导入会新增菜单并给角色授权，仅管理员可用
+        .push(
+            Router::new()
+                .hoop(admin_only)
+                .push(Router::with_path("batchMove").put(handler::batch_move_menus))
+                .push(Router::with_path("import").post(handler::import_menus))
+        )
         .push(Router::new().post(handler::create_menu))
         .push(
             Router::with_path("<id>")
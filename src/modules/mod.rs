@@ -6,3 +6,11 @@ pub mod permission;
 pub mod menu;
 pub mod audit_log;
 pub mod system;
+pub mod recycle_bin;
+pub mod dict;
+pub mod notice;
+pub mod push;
+pub mod job;
+pub mod file;
+pub mod monitor;
+pub mod codegen;
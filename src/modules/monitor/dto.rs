@@ -0,0 +1,135 @@
+use serde::Serialize;
+use salvo::oapi::ToSchema;
+
+/// 服务器监控信息
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerInfoResponse {
+    /// 服务器当前时间
+    pub server_time: String,
+    pub process: ProcessInfo,
+    pub host: HostInfo,
+    pub memory: MemoryInfo,
+    pub disks: Vec<DiskInfo>,
+    pub runtime: RuntimeInfo,
+    pub database: DatabaseInfo,
+    pub build: BuildInfo,
+}
+
+/// 当前进程
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessInfo {
+    pub pid: u32,
+    /// 启动时间
+    pub started_time: String,
+    /// 运行时长（秒）
+    pub uptime_seconds: u64,
+    /// CPU 使用率（%），以单核为 100%，多核满载时可超过 100
+    pub cpu_usage: f32,
+    /// 常驻内存（字节）
+    pub memory_bytes: u64,
+    /// 虚拟内存（字节）
+    pub virtual_memory_bytes: u64,
+}
+
+/// 主机
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HostInfo {
+    pub host_name: Option<String>,
+    pub os_name: Option<String>,
+    pub os_version: Option<String>,
+    pub kernel_version: Option<String>,
+    pub arch: String,
+    pub cpu_brand: Option<String>,
+    /// 逻辑核数
+    pub cpu_cores: usize,
+    /// 物理核数
+    pub physical_cores: Option<usize>,
+    /// 整机 CPU 使用率（%）
+    pub cpu_usage: f32,
+    /// 开机时长（秒）
+    pub uptime_seconds: u64,
+    pub load_average: LoadAverage,
+}
+
+/// 系统负载（Windows 上始终为 0）
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadAverage {
+    pub one: f64,
+    pub five: f64,
+    pub fifteen: f64,
+}
+
+/// 内存（字节）
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryInfo {
+    pub total: u64,
+    pub used: u64,
+    pub available: u64,
+    /// 使用率（%）
+    pub usage: f64,
+    pub swap_total: u64,
+    pub swap_used: u64,
+}
+
+/// 磁盘（字节）
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskInfo {
+    pub name: String,
+    pub mount_point: String,
+    pub file_system: String,
+    pub total: u64,
+    pub used: u64,
+    pub available: u64,
+    /// 使用率（%）
+    pub usage: f64,
+}
+
+/// Tokio 运行时
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeInfo {
+    /// 工作线程数
+    pub workers: usize,
+    /// 存活任务数
+    pub alive_tasks: usize,
+    /// 全局队列中等待调度的任务数
+    pub global_queue_depth: usize,
+    /// 所有工作线程累计忙碌时长（毫秒）
+    pub busy_duration_ms: u64,
+    /// 所有工作线程累计休眠次数
+    pub park_count: u64,
+}
+
+/// 数据库连接池
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseInfo {
+    /// connected 或 disconnected
+    pub status: String,
+    /// 当前连接数
+    pub size: u32,
+    /// 空闲连接数
+    pub idle: u32,
+    /// 使用中的连接数
+    pub in_use: u32,
+    /// 最大连接数
+    pub max_connections: u32,
+}
+
+/// 构建信息
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildInfo {
+    pub version: String,
+    pub git_commit: String,
+    pub build_time: String,
+    pub rustc_version: String,
+    /// debug 或 release
+    pub profile: String,
+}
@@ -0,0 +1,285 @@
+use chrono::{DateTime, Local, TimeZone};
+use salvo::prelude::*;
+use sea_orm::DatabaseConnection;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use sysinfo::{
+    Disks, Pid, ProcessRefreshKind, ProcessesToUpdate, System, MINIMUM_CPU_UPDATE_INTERVAL,
+};
+
+use super::dto::{
+    BuildInfo, DatabaseInfo, DiskInfo, HostInfo, LoadAverage, MemoryInfo, ProcessInfo,
+    RuntimeInfo, ServerInfoResponse,
+};
+use crate::common::{ApiResponse, AppError};
+
+/// 进程启动时刻
+static STARTED: LazyLock<(Instant, DateTime<Local>)> =
+    LazyLock::new(|| (Instant::now(), Local::now()));
+
+/// 上次采样在该时间内时，直接作为本次 CPU 采样的起点
+const SAMPLE_REUSE_WINDOW: Duration = Duration::from_secs(1);
+
+// CPU 使用率需要两次采样的差值，复用同一个 System 保留上次采样；
+// 锁只在采样和读取时持有，等待采样间隔时不持有，并发请求互不阻塞
+static SAMPLER: LazyLock<Mutex<Sampler>> = LazyLock::new(|| {
+    Mutex::new(Sampler {
+        sys: System::new(),
+        refreshed_at: None,
+    })
+});
+
+struct Sampler {
+    sys: System,
+    refreshed_at: Option<Instant>,
+}
+
+/// 记录进程启动时刻，启动时调用
+pub fn init() {
+    LazyLock::force(&STARTED);
+}
+
+/// 获取服务器监控信息
+///
+/// 包含进程、主机、内存、磁盘、Tokio 运行时、数据库连接池和构建信息。
+/// CPU 使用率需要间隔采样，接口耗时最多约 200ms，并发请求共用同一轮采样。仅管理员可用。
+#[endpoint(
+    tags("系统监控"),
+    responses(
+        (status_code = 200, description = "获取成功"),
+        (status_code = 401, description = "未授权"),
+        (status_code = 403, description = "需要管理员权限")
+    )
+)]
+pub async fn get_server_info(
+    depot: &Depot,
+) -> Result<Json<ApiResponse<ServerInfoResponse>>, AppError> {
+    let pid = sysinfo::get_current_pid().ok();
+
+    // 最近一次采样不够新时先采样一次作为起点，再等待到满足最小采样间隔
+    let wait = {
+        let mut sampler = lock_sampler();
+        match sampler.refreshed_at {
+            Some(at) if at.elapsed() < SAMPLE_REUSE_WINDOW => {
+                MINIMUM_CPU_UPDATE_INTERVAL.saturating_sub(at.elapsed())
+            }
+            _ => {
+                sampler.refresh(pid);
+                MINIMUM_CPU_UPDATE_INTERVAL
+            }
+        }
+    };
+    tokio::time::sleep(wait).await;
+
+    let mut sampler = lock_sampler();
+    // 并发请求已完成本轮采样时直接复用
+    if sampler
+        .refreshed_at
+        .is_none_or(|at| at.elapsed() >= MINIMUM_CPU_UPDATE_INTERVAL)
+    {
+        sampler.refresh(pid);
+    }
+    let sys = &sampler.sys;
+
+    let (started_instant, started_time) = *STARTED;
+    let process = pid.and_then(|pid| sys.process(pid));
+    let process = ProcessInfo {
+        pid: pid.map(|p| p.as_u32()).unwrap_or_default(),
+        started_time: started_time.format("%Y-%m-%d %H:%M:%S").to_string(),
+        uptime_seconds: started_instant.elapsed().as_secs(),
+        cpu_usage: process.map(|p| p.cpu_usage()).unwrap_or_default(),
+        memory_bytes: process.map(|p| p.memory()).unwrap_or_default(),
+        virtual_memory_bytes: process.map(|p| p.virtual_memory()).unwrap_or_default(),
+    };
+
+    let load = System::load_average();
+    let host = HostInfo {
+        host_name: System::host_name(),
+        os_name: System::name(),
+        os_version: System::os_version(),
+        kernel_version: System::kernel_version(),
+        arch: System::cpu_arch(),
+        cpu_brand: sys.cpus().first().map(|c| c.brand().trim().to_string()),
+        cpu_cores: sys.cpus().len(),
+        physical_cores: System::physical_core_count(),
+        cpu_usage: sys.global_cpu_usage(),
+        uptime_seconds: System::uptime(),
+        load_average: LoadAverage {
+            one: load.one,
+            five: load.five,
+            fifteen: load.fifteen,
+        },
+    };
+
+    let memory = MemoryInfo {
+        total: sys.total_memory(),
+        used: sys.used_memory(),
+        available: sys.available_memory(),
+        usage: percent(sys.used_memory(), sys.total_memory()),
+        swap_total: sys.total_swap(),
+        swap_used: sys.used_swap(),
+    };
+    drop(sampler);
+
+    let disks = Disks::new_with_refreshed_list()
+        .iter()
+        .map(|disk| {
+            let used = disk.total_space().saturating_sub(disk.available_space());
+            DiskInfo {
+                name: disk.name().to_string_lossy().to_string(),
+                mount_point: disk.mount_point().to_string_lossy().to_string(),
+                file_system: disk.file_system().to_string_lossy().to_string(),
+                total: disk.total_space(),
+                used,
+                available: disk.available_space(),
+                usage: percent(used, disk.total_space()),
+            }
+        })
+        .collect();
+
+    let response = ServerInfoResponse {
+        server_time: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        process,
+        host,
+        memory,
+        disks,
+        runtime: runtime_info(),
+        database: database_info(depot),
+        build: build_info(),
+    };
+
+    Ok(Json(ApiResponse::success(response)))
+}
+
+// ========== 辅助函数 ==========
+
+impl Sampler {
+    fn refresh(&mut self, pid: Option<Pid>) {
+        self.sys.refresh_cpu_all();
+        self.sys.refresh_memory();
+        if let Some(pid) = pid {
+            self.sys.refresh_processes_specifics(
+                ProcessesToUpdate::Some(&[pid]),
+                true,
+                ProcessRefreshKind::nothing().with_cpu().with_memory(),
+            );
+        }
+        self.refreshed_at = Some(Instant::now());
+    }
+}
+
+/// 采样过程不会 panic，锁中毒时沿用其中的数据
+fn lock_sampler() -> std::sync::MutexGuard<'static, Sampler> {
+    SAMPLER.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn runtime_info() -> RuntimeInfo {
+    let metrics = tokio::runtime::Handle::current().metrics();
+    let workers = metrics.num_workers();
+    RuntimeInfo {
+        workers,
+        alive_tasks: metrics.num_alive_tasks(),
+        global_queue_depth: metrics.global_queue_depth(),
+        busy_duration_ms: (0..workers)
+            .map(|w| metrics.worker_total_busy_duration(w))
+            .sum::<Duration>()
+            .as_millis() as u64,
+        park_count: (0..workers).map(|w| metrics.worker_park_count(w)).sum(),
+    }
+}
+
+fn database_info(depot: &Depot) -> DatabaseInfo {
+    match depot.get::<Arc<DatabaseConnection>>("db") {
+        Ok(db) => {
+            let pool = db.get_postgres_connection_pool();
+            let size = pool.size();
+            let idle = pool.num_idle() as u32;
+            DatabaseInfo {
+                status: "connected".to_string(),
+                size,
+                idle,
+                in_use: size.saturating_sub(idle),
+                max_connections: pool.options().get_max_connections(),
+            }
+        }
+        Err(_) => DatabaseInfo {
+            status: "disconnected".to_string(),
+            size: 0,
+            idle: 0,
+            in_use: 0,
+            max_connections: 0,
+        },
+    }
+}
+
+fn build_info() -> BuildInfo {
+    let build_time = env!("BUILD_TIMESTAMP")
+        .parse::<i64>()
+        .ok()
+        .and_then(|ts| Local.timestamp_opt(ts, 0).single())
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default();
+
+    BuildInfo {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        git_commit: env!("BUILD_GIT_COMMIT").to_string(),
+        build_time,
+        rustc_version: env!("BUILD_RUSTC_VERSION").to_string(),
+        profile: if cfg!(debug_assertions) { "debug" } else { "release" }.to_string(),
+    }
+}
+
+fn percent(used: u64, total: u64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    (used as f64 / total as f64 * 10000.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::testing;
+    use salvo::test::{ResponseExt, TestClient};
+    use serde_json::Value;
+    use uuid::Uuid;
+
+    async fn server_info(app: &Service, token: &str) -> Response {
+        TestClient::get("http://127.0.0.1/monitor/server")
+            .bearer_auth(token)
+            .send(app)
+            .await
+    }
+
+    #[tokio::test]
+    async fn server_info_requires_admin() {
+        let app = testing::service(super::super::routes::routes(), None);
+        let token = testing::access_token(Uuid::new_v4(), Uuid::new_v4(), "editor");
+        let res = server_info(&app, &token).await;
+        assert_eq!(res.status_code, Some(StatusCode::FORBIDDEN));
+    }
+
+    #[tokio::test]
+    async fn server_info_reports_process_and_pool() {
+        let Some(db) = testing::TestDb::migrated().await else {
+            return;
+        };
+        let app = testing::service(super::super::routes::routes(), Some(db.arc()));
+        let mut res = server_info(&app, &testing::admin_token()).await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        let info = res.take_json::<Value>().await.unwrap()["data"].take();
+
+        assert_eq!(info["process"]["pid"], std::process::id());
+        assert!(info["memory"]["total"].as_u64().unwrap() > 0);
+        assert!(info["runtime"]["workers"].as_u64().unwrap() > 0);
+        assert_eq!(info["database"]["status"], "connected");
+        assert!(info["database"]["maxConnections"].as_u64().unwrap() > 0);
+        assert_eq!(info["build"]["version"], env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn percent_rounds_to_two_decimals() {
+        assert_eq!(percent(1, 3), 33.33);
+        assert_eq!(percent(5, 0), 0.0);
+    }
+}
@@ -0,0 +1,8 @@
+// monitor 模块 - 服务器与运行时监控
+
+mod dto;
+mod handler;
+mod routes;
+
+pub use handler::init;
+pub use routes::routes;
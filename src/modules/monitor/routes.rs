@@ -0,0 +1,10 @@
+use salvo::prelude::*;
+use crate::common::middleware::{admin_only, auth_middleware};
+use super::handler;
+
+pub fn routes() -> Router {
+    Router::with_path("monitor")
+        .hoop(auth_middleware)
+        .hoop(admin_only)
+        .push(Router::with_path("server").get(handler::get_server_info))
+}
@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+use salvo::oapi::ToSchema;
+
+/// 创建通知公告请求
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[salvo(schema(example = json!({
+    "title": "系统维护通知",
+    "content": "系统将于本周六 22:00 至 24:00 进行维护，期间暂停服务。",
+    "isPinned": true,
+    "startTime": "2026-01-01 00:00:00",
+    "endTime": "2026-01-10 00:00:00",
+    "roleIds": []
+})))]
+pub struct CreateNoticeRequest {
+    /// 标题
+    pub title: String,
+    /// 内容
+    pub content: String,
+    /// 是否置顶
+    #[serde(default)]
+    pub is_pinned: bool,
+    /// 生效开始时间，格式 yyyy-MM-dd HH:mm:ss，为空表示发布即生效
+    pub start_time: Option<String>,
+    /// 生效结束时间，格式 yyyy-MM-dd HH:mm:ss，为空表示长期有效
+    pub end_time: Option<String>,
+    /// 目标角色ID列表，为空表示所有用户
+    #[serde(default)]
+    pub role_ids: Vec<String>,
+}
+
+/// 更新通知公告请求（已发布的公告需先撤回）
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateNoticeRequest {
+    /// 标题
+    pub title: Option<String>,
+    /// 内容
+    pub content: Option<String>,
+    /// 是否置顶
+    pub is_pinned: Option<bool>,
+    /// 生效开始时间，传空字符串表示清除
+    pub start_time: Option<String>,
+    /// 生效结束时间，传空字符串表示清除
+    pub end_time: Option<String>,
+    /// 目标角色ID列表，传空数组表示所有用户
+    pub role_ids: Option<Vec<String>>,
+}
+
+/// 通知公告响应（管理端）
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NoticeResponse {
+    pub id: String,
+    pub title: String,
+    pub content: String,
+    /// 状态：0-草稿，1-已发布，2-已撤回
+    pub status: i16,
+    pub is_pinned: bool,
+    pub start_time: Option<String>,
+    pub end_time: Option<String>,
+    pub published_time: Option<String>,
+    /// 目标角色ID列表，为空表示所有用户
+    pub role_ids: Vec<String>,
+    /// 已读人数
+    pub read_count: u64,
+    pub created_time: String,
+}
+
+/// 当前用户的通知公告
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MyNoticeItem {
+    pub id: String,
+    pub title: String,
+    pub content: String,
+    pub is_pinned: bool,
+    pub published_time: Option<String>,
+    pub end_time: Option<String>,
+    /// 是否已读
+    pub is_read: bool,
+    /// 阅读时间
+    pub read_time: Option<String>,
+}
+
+/// 通知数量响应
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NoticeCountResponse {
+    pub count: u64,
+}
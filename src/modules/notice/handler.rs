@@ -0,0 +1,1025 @@
+use chrono::{NaiveDateTime, Utc};
+use salvo::oapi::extract::{JsonBody, PathParam};
+use salvo::prelude::*;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
+    sea_query::{OnConflict, Query},
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::dto::{
+    CreateNoticeRequest, MyNoticeItem, NoticeCountResponse, NoticeResponse, UpdateNoticeRequest,
+};
+use crate::common::constants::{
+    DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE, NOTICE_STATUS_DRAFT, NOTICE_STATUS_PUBLISHED,
+    NOTICE_STATUS_WITHDRAWN,
+};
+use crate::common::push::{self, PushEvent};
+use crate::common::{i18n, ApiResponse, AppError, PageResponse};
+use crate::models::{notice, notice_read, notice_role, role, user_role};
+
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+// ========== 管理端 ==========
+
+/// 获取通知公告列表（分页）
+#[endpoint(
+    tags("通知公告"),
+    parameters(
+        ("keyword" = Option<String>, Query, description = "标题（模糊搜索）"),
+        ("status" = Option<i16>, Query, description = "状态：0-草稿，1-已发布，2-已撤回"),
+        ("page" = Option<u64>, Query, description = "当前页码，默认1"),
+        ("pageSize" = Option<u64>, Query, description = "每页数量，默认20，最大100"),
+    ),
+    responses(
+        (status_code = 200, description = "获取成功"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn get_notice_list(
+    req: &mut Request,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<PageResponse<NoticeResponse>>>, AppError> {
+    let page = req.query::<u64>("page").unwrap_or(1).max(1);
+    let page_size = req
+        .query::<u64>("pageSize")
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let mut query_builder = notice::Entity::find().filter(notice::Column::DeletedTime.is_null());
+
+    if let Some(keyword) = req.query::<String>("keyword").filter(|k| !k.is_empty()) {
+        query_builder = query_builder.filter(notice::Column::Title.contains(&keyword));
+    }
+    if let Some(status) = req.query::<i16>("status") {
+        query_builder = query_builder.filter(notice::Column::Status.eq(status));
+    }
+
+    let total = query_builder
+        .clone()
+        .count(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let notices = query_builder
+        .order_by_desc(notice::Column::IsPinned)
+        .order_by_desc(notice::Column::CreatedTime)
+        .offset((page - 1) * page_size)
+        .limit(page_size)
+        .all(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let items = build_notice_responses(db.as_ref(), notices).await?;
+    Ok(Json(ApiResponse::success(PageResponse::new(
+        items, total, page, page_size,
+    ))))
+}
+
+/// 获取通知公告详情
+#[endpoint(
+    tags("通知公告"),
+    responses(
+        (status_code = 200, description = "获取成功"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 404, description = "公告不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn get_notice(
+    id: PathParam<String>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<NoticeResponse>>, AppError> {
+    let notice_id = parse_notice_id(&id.into_inner())?;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let existing = find_notice(db.as_ref(), notice_id).await?;
+    let response = build_notice_responses(db.as_ref(), vec![existing])
+        .await?
+        .remove(0);
+    Ok(Json(ApiResponse::success(response)))
+}
+
+/// 创建通知公告（草稿）
+#[endpoint(
+    tags("通知公告"),
+    responses(
+        (status_code = 200, description = "创建成功"),
+        (status_code = 400, description = "参数错误"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn create_notice(
+    req: JsonBody<CreateNoticeRequest>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<NoticeResponse>>, AppError> {
+    let data = req.into_inner();
+    if data.title.trim().is_empty() {
+        return Err(AppError::BadRequest("公告标题不能为空".to_string()));
+    }
+    let start_time = parse_time(data.start_time.as_deref())?;
+    let end_time = parse_time(data.end_time.as_deref())?;
+    validate_window(start_time, end_time)?;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let user_id = depot
+        .get::<String>("user_id")
+        .ok()
+        .and_then(|s| Uuid::parse_str(s.as_str()).ok());
+
+    let role_ids = validate_role_ids(db.as_ref(), &data.role_ids).await?;
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let now = Utc::now().naive_utc();
+    let created = notice::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        title: Set(data.title),
+        content: Set(data.content),
+        status: Set(NOTICE_STATUS_DRAFT),
+        is_pinned: Set(data.is_pinned),
+        start_time: Set(start_time),
+        end_time: Set(end_time),
+        published_time: Set(None),
+        published_id: Set(None),
+        created_time: Set(now),
+        created_id: Set(user_id),
+        updated_time: Set(now),
+        updated_id: Set(user_id),
+        deleted_time: Set(None),
+        deleted_id: Set(None),
+    }
+    .insert(&txn)
+    .await
+    .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    replace_notice_roles(&txn, created.id, &role_ids, user_id).await?;
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let response = build_notice_responses(db.as_ref(), vec![created])
+        .await?
+        .remove(0);
+    Ok(Json(ApiResponse::success_with_message(
+        response,
+        i18n::t(depot, "创建成功"),
+    )))
+}
+
+/// 更新通知公告
+#[endpoint(
+    tags("通知公告"),
+    responses(
+        (status_code = 200, description = "更新成功"),
+        (status_code = 400, description = "参数错误"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 404, description = "公告不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn update_notice(
+    id: PathParam<String>,
+    req: JsonBody<UpdateNoticeRequest>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<NoticeResponse>>, AppError> {
+    let notice_id = parse_notice_id(&id.into_inner())?;
+    let data = req.into_inner();
+    if data.title.as_deref().is_some_and(|t| t.trim().is_empty()) {
+        return Err(AppError::BadRequest("公告标题不能为空".to_string()));
+    }
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let user_id = depot
+        .get::<String>("user_id")
+        .ok()
+        .and_then(|s| Uuid::parse_str(s.as_str()).ok());
+
+    let existing = find_notice(db.as_ref(), notice_id).await?;
+    if existing.status == NOTICE_STATUS_PUBLISHED {
+        return Err(AppError::BadRequest("已发布的公告请先撤回再修改".to_string()));
+    }
+
+    let start_time = match data.start_time.as_deref() {
+        Some(value) => parse_time(Some(value))?,
+        None => existing.start_time,
+    };
+    let end_time = match data.end_time.as_deref() {
+        Some(value) => parse_time(Some(value))?,
+        None => existing.end_time,
+    };
+    validate_window(start_time, end_time)?;
+
+    let role_ids = match &data.role_ids {
+        Some(ids) => Some(validate_role_ids(db.as_ref(), ids).await?),
+        None => None,
+    };
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let mut active_model: notice::ActiveModel = existing.into();
+    if let Some(title) = data.title {
+        active_model.title = Set(title);
+    }
+    if let Some(content) = data.content {
+        active_model.content = Set(content);
+    }
+    if let Some(is_pinned) = data.is_pinned {
+        active_model.is_pinned = Set(is_pinned);
+    }
+    active_model.start_time = Set(start_time);
+    active_model.end_time = Set(end_time);
+    active_model.updated_time = Set(Utc::now().naive_utc());
+    active_model.updated_id = Set(user_id);
+
+    let updated = active_model
+        .update(&txn)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    if let Some(role_ids) = role_ids {
+        replace_notice_roles(&txn, notice_id, &role_ids, user_id).await?;
+    }
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let response = build_notice_responses(db.as_ref(), vec![updated])
+        .await?
+        .remove(0);
+    Ok(Json(ApiResponse::success_with_message(
+        response,
+        i18n::t(depot, "更新成功"),
+    )))
+}
+
+/// 删除通知公告（软删除）
+#[endpoint(
+    tags("通知公告"),
+    responses(
+        (status_code = 200, description = "删除成功"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 404, description = "公告不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn delete_notice(
+    id: PathParam<String>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    let notice_id = parse_notice_id(&id.into_inner())?;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let user_id = depot
+        .get::<String>("user_id")
+        .ok()
+        .and_then(|s| Uuid::parse_str(s.as_str()).ok());
+
+    let existing = find_notice(db.as_ref(), notice_id).await?;
+    let mut active_model: notice::ActiveModel = existing.into();
+    active_model.deleted_time = Set(Some(Utc::now().naive_utc()));
+    active_model.deleted_id = Set(user_id);
+    active_model
+        .update(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    Ok(Json(ApiResponse::success_with_message(
+        (),
+        i18n::t(depot, "删除成功"),
+    )))
+}
+
+/// 发布通知公告（草稿或已撤回的公告）
+#[endpoint(
+    tags("通知公告"),
+    responses(
+        (status_code = 200, description = "发布成功"),
+        (status_code = 400, description = "公告已发布"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 404, description = "公告不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn publish_notice(
+    id: PathParam<String>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<NoticeResponse>>, AppError> {
+    let notice_id = parse_notice_id(&id.into_inner())?;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let user_id = depot
+        .get::<String>("user_id")
+        .ok()
+        .and_then(|s| Uuid::parse_str(s.as_str()).ok());
+
+    let existing = find_notice(db.as_ref(), notice_id).await?;
+    if existing.status == NOTICE_STATUS_PUBLISHED {
+        return Err(AppError::BadRequest("公告已发布".to_string()));
+    }
+
+    let now = Utc::now().naive_utc();
+    let mut active_model: notice::ActiveModel = existing.into();
+    active_model.status = Set(NOTICE_STATUS_PUBLISHED);
+    active_model.published_time = Set(Some(now));
+    active_model.published_id = Set(user_id);
+    active_model.updated_time = Set(now);
+    active_model.updated_id = Set(user_id);
+    let updated = active_model
+        .update(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    notify_new_notice(db.as_ref(), &updated).await;
+
+    let response = build_notice_responses(db.as_ref(), vec![updated])
+        .await?
+        .remove(0);
+    Ok(Json(ApiResponse::success_with_message(
+        response,
+        i18n::t(depot, "发布成功"),
+    )))
+}
+
+/// 撤回通知公告
+#[endpoint(
+    tags("通知公告"),
+    responses(
+        (status_code = 200, description = "撤回成功"),
+        (status_code = 400, description = "公告未发布"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 404, description = "公告不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn withdraw_notice(
+    id: PathParam<String>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<NoticeResponse>>, AppError> {
+    let notice_id = parse_notice_id(&id.into_inner())?;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let user_id = depot
+        .get::<String>("user_id")
+        .ok()
+        .and_then(|s| Uuid::parse_str(s.as_str()).ok());
+
+    let existing = find_notice(db.as_ref(), notice_id).await?;
+    if existing.status != NOTICE_STATUS_PUBLISHED {
+        return Err(AppError::BadRequest("只能撤回已发布的公告".to_string()));
+    }
+
+    let mut active_model: notice::ActiveModel = existing.into();
+    active_model.status = Set(NOTICE_STATUS_WITHDRAWN);
+    active_model.updated_time = Set(Utc::now().naive_utc());
+    active_model.updated_id = Set(user_id);
+    let updated = active_model
+        .update(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let response = build_notice_responses(db.as_ref(), vec![updated])
+        .await?
+        .remove(0);
+    Ok(Json(ApiResponse::success_with_message(
+        response,
+        i18n::t(depot, "撤回成功"),
+    )))
+}
+
+// ========== 当前用户 ==========
+
+/// 获取当前用户可见的通知公告
+///
+/// 只返回已发布、处于有效期内且面向所有用户或面向当前用户任一角色的公告，
+/// 置顶公告排在前面。
+#[endpoint(
+    tags("通知公告"),
+    parameters(
+        ("unreadOnly" = Option<bool>, Query, description = "是否只返回未读公告"),
+        ("page" = Option<u64>, Query, description = "当前页码，默认1"),
+        ("pageSize" = Option<u64>, Query, description = "每页数量，默认20，最大100"),
+    ),
+    responses(
+        (status_code = 200, description = "获取成功"),
+        (status_code = 401, description = "未授权"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn get_my_notices(
+    req: &mut Request,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<PageResponse<MyNoticeItem>>>, AppError> {
+    let page = req.query::<u64>("page").unwrap_or(1).max(1);
+    let page_size = req
+        .query::<u64>("pageSize")
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    let unread_only = req.query::<bool>("unreadOnly").unwrap_or(false);
+
+    let user_id = current_user_id(depot)?;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let mut condition = visible_condition(db.as_ref(), user_id).await?;
+    if unread_only {
+        condition = condition.add(unread_condition(user_id));
+    }
+    let query_builder = notice::Entity::find().filter(condition);
+
+    let total = query_builder
+        .clone()
+        .count(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let notices = query_builder
+        .order_by_desc(notice::Column::IsPinned)
+        .order_by_desc(notice::Column::PublishedTime)
+        .offset((page - 1) * page_size)
+        .limit(page_size)
+        .all(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let read_times: HashMap<Uuid, NaiveDateTime> = notice_read::Entity::find()
+        .filter(notice_read::Column::UserId.eq(user_id))
+        .filter(notice_read::Column::NoticeId.is_in(notices.iter().map(|n| n.id).collect::<Vec<_>>()))
+        .all(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?
+        .into_iter()
+        .map(|r| (r.notice_id, r.read_time))
+        .collect();
+
+    let items = notices
+        .into_iter()
+        .map(|n| {
+            let read_time = read_times.get(&n.id);
+            MyNoticeItem {
+                id: n.id.to_string(),
+                title: n.title,
+                content: n.content,
+                is_pinned: n.is_pinned,
+                published_time: format_time(n.published_time),
+                end_time: format_time(n.end_time),
+                is_read: read_time.is_some(),
+                read_time: format_time(read_time.copied()),
+            }
+        })
+        .collect();
+
+    Ok(Json(ApiResponse::success(PageResponse::new(
+        items, total, page, page_size,
+    ))))
+}
+
+/// 获取当前用户的未读公告数量
+#[endpoint(
+    tags("通知公告"),
+    responses(
+        (status_code = 200, description = "获取成功"),
+        (status_code = 401, description = "未授权"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn get_unread_count(
+    depot: &Depot,
+) -> Result<Json<ApiResponse<NoticeCountResponse>>, AppError> {
+    let user_id = current_user_id(depot)?;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let condition = visible_condition(db.as_ref(), user_id)
+        .await?
+        .add(unread_condition(user_id));
+    let count = notice::Entity::find()
+        .filter(condition)
+        .count(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    Ok(Json(ApiResponse::success(NoticeCountResponse { count })))
+}
+
+/// 将公告标记为已读（重复标记不报错）
+#[endpoint(
+    tags("通知公告"),
+    responses(
+        (status_code = 200, description = "操作成功"),
+        (status_code = 401, description = "未授权"),
+        (status_code = 404, description = "公告不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn mark_read(
+    id: PathParam<String>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    let notice_id = parse_notice_id(&id.into_inner())?;
+    let user_id = current_user_id(depot)?;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    // 只能标记自己可见的公告
+    let condition = visible_condition(db.as_ref(), user_id)
+        .await?
+        .add(notice::Column::Id.eq(notice_id));
+    let visible = notice::Entity::find()
+        .filter(condition)
+        .count(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?
+        > 0;
+    if !visible {
+        return Err(AppError::NotFound("公告不存在".to_string()));
+    }
+
+    insert_reads(db.as_ref(), user_id, vec![notice_id]).await?;
+
+    Ok(Json(ApiResponse::success_with_message(
+        (),
+        i18n::t(depot, "操作成功"),
+    )))
+}
+
+/// 将当前用户所有可见的未读公告标记为已读，返回本次标记的数量
+#[endpoint(
+    tags("通知公告"),
+    responses(
+        (status_code = 200, description = "操作成功"),
+        (status_code = 401, description = "未授权"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn mark_all_read(
+    depot: &Depot,
+) -> Result<Json<ApiResponse<NoticeCountResponse>>, AppError> {
+    let user_id = current_user_id(depot)?;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let condition = visible_condition(db.as_ref(), user_id)
+        .await?
+        .add(unread_condition(user_id));
+    let notice_ids: Vec<Uuid> = notice::Entity::find()
+        .select_only()
+        .column(notice::Column::Id)
+        .filter(condition)
+        .into_tuple()
+        .all(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let count = notice_ids.len() as u64;
+    insert_reads(db.as_ref(), user_id, notice_ids).await?;
+
+    Ok(Json(ApiResponse::success_with_message(
+        NoticeCountResponse { count },
+        i18n::t(depot, "操作成功"),
+    )))
+}
+
+// ========== 辅助函数 ==========
+
+fn parse_notice_id(id: &str) -> Result<Uuid, AppError> {
+    Uuid::parse_str(id).map_err(|_| AppError::BadRequest("无效的公告ID".to_string()))
+}
+
+fn current_user_id(depot: &Depot) -> Result<Uuid, AppError> {
+    let user_id_str = depot
+        .get::<String>("user_id")
+        .map_err(|_| AppError::Unauthorized)?;
+    Uuid::parse_str(user_id_str.as_str()).map_err(|_| AppError::Unauthorized)
+}
+
+async fn find_notice(db: &DatabaseConnection, notice_id: Uuid) -> Result<notice::Model, AppError> {
+    notice::Entity::find_by_id(notice_id)
+        .filter(notice::Column::DeletedTime.is_null())
+        .one(db)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?
+        .ok_or(AppError::NotFound("公告不存在".to_string()))
+}
+
+/// 解析时间，空字符串视为未设置
+fn parse_time(value: Option<&str>) -> Result<Option<NaiveDateTime>, AppError> {
+    match value.map(str::trim).filter(|v| !v.is_empty()) {
+        Some(v) => NaiveDateTime::parse_from_str(v, TIME_FORMAT)
+            .map(Some)
+            .map_err(|_| AppError::BadRequest(format!("无效的时间格式: {}", v))),
+        None => Ok(None),
+    }
+}
+
+fn format_time(time: Option<NaiveDateTime>) -> Option<String> {
+    time.map(|t| t.format(TIME_FORMAT).to_string())
+}
+
+fn validate_window(
+    start_time: Option<NaiveDateTime>,
+    end_time: Option<NaiveDateTime>,
+) -> Result<(), AppError> {
+    if let (Some(start), Some(end)) = (start_time, end_time) {
+        if end <= start {
+            return Err(AppError::BadRequest("结束时间必须晚于开始时间".to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// 校验目标角色存在且未删除，返回去重后的角色ID
+async fn validate_role_ids(db: &DatabaseConnection, ids: &[String]) -> Result<Vec<Uuid>, AppError> {
+    let mut role_ids = Vec::with_capacity(ids.len());
+    for id in ids {
+        let role_id = Uuid::parse_str(id)
+            .map_err(|_| AppError::BadRequest(format!("无效的角色ID: {}", id)))?;
+        if !role_ids.contains(&role_id) {
+            role_ids.push(role_id);
+        }
+    }
+    if role_ids.is_empty() {
+        return Ok(role_ids);
+    }
+
+    let found: Vec<Uuid> = role::Entity::find()
+        .select_only()
+        .column(role::Column::Id)
+        .filter(role::Column::Id.is_in(role_ids.clone()))
+        .filter(role::Column::DeletedTime.is_null())
+        .into_tuple()
+        .all(db)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    if let Some(missing) = role_ids.iter().find(|id| !found.contains(id)) {
+        return Err(AppError::BadRequest(format!("角色不存在: {}", missing)));
+    }
+    Ok(role_ids)
+}
+
+async fn replace_notice_roles<C: ConnectionTrait>(
+    db: &C,
+    notice_id: Uuid,
+    role_ids: &[Uuid],
+    user_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    notice_role::Entity::delete_many()
+        .filter(notice_role::Column::NoticeId.eq(notice_id))
+        .exec(db)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    if role_ids.is_empty() {
+        return Ok(());
+    }
+
+    let now = Utc::now().naive_utc();
+    let models = role_ids.iter().map(|role_id| notice_role::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        notice_id: Set(notice_id),
+        role_id: Set(*role_id),
+        created_time: Set(now),
+        created_id: Set(user_id),
+    });
+    notice_role::Entity::insert_many(models)
+        .exec(db)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+    Ok(())
+}
+
+/// 当前用户可见公告的查询条件
+async fn visible_condition(db: &DatabaseConnection, user_id: Uuid) -> Result<Condition, AppError> {
+    let role_ids: Vec<Uuid> = user_role::Entity::find()
+        .select_only()
+        .column(user_role::Column::RoleId)
+        .filter(user_role::Column::UserId.eq(user_id))
+        .into_tuple()
+        .all(db)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let now = Utc::now().naive_utc();
+    let targeted = Query::select()
+        .column(notice_role::Column::NoticeId)
+        .from(notice_role::Entity)
+        .to_owned();
+    let matched = Query::select()
+        .column(notice_role::Column::NoticeId)
+        .from(notice_role::Entity)
+        .and_where(notice_role::Column::RoleId.is_in(role_ids))
+        .to_owned();
+
+    Ok(Condition::all()
+        .add(notice::Column::Status.eq(NOTICE_STATUS_PUBLISHED))
+        .add(notice::Column::DeletedTime.is_null())
+        .add(
+            Condition::any()
+                .add(notice::Column::StartTime.is_null())
+                .add(notice::Column::StartTime.lte(now)),
+        )
+        .add(
+            Condition::any()
+                .add(notice::Column::EndTime.is_null())
+                .add(notice::Column::EndTime.gt(now)),
+        )
+        .add(
+            Condition::any()
+                .add(notice::Column::Id.not_in_subquery(targeted))
+                .add(notice::Column::Id.in_subquery(matched)),
+        ))
+}
+
+/// 当前用户未读公告的查询条件
+fn unread_condition(user_id: Uuid) -> Condition {
+    let read = Query::select()
+        .column(notice_read::Column::NoticeId)
+        .from(notice_read::Entity)
+        .and_where(notice_read::Column::UserId.eq(user_id))
+        .to_owned();
+    Condition::all().add(notice::Column::Id.not_in_subquery(read))
+}
+
+/// 向公告的目标用户推送新公告事件，尚未生效的公告不推送
+async fn notify_new_notice(db: &DatabaseConnection, n: &notice::Model) {
+    if n.start_time.is_some_and(|start| start > Utc::now().naive_utc()) {
+        return;
+    }
+
+    let event = PushEvent::NewNotice {
+        id: n.id.to_string(),
+        title: n.title.clone(),
+    };
+
+    let role_ids: Vec<Uuid> = match notice_role::Entity::find()
+        .select_only()
+        .column(notice_role::Column::RoleId)
+        .filter(notice_role::Column::NoticeId.eq(n.id))
+        .into_tuple()
+        .all(db)
+        .await
+    {
+        Ok(role_ids) => role_ids,
+        Err(e) => {
+            tracing::warn!("查询公告目标角色失败: {}", e);
+            return;
+        }
+    };
+    if role_ids.is_empty() {
+        push::broadcast(event);
+        return;
+    }
+
+    match user_role::Entity::find()
+        .select_only()
+        .column(user_role::Column::UserId)
+        .filter(user_role::Column::RoleId.is_in(role_ids))
+        .distinct()
+        .into_tuple::<Uuid>()
+        .all(db)
+        .await
+    {
+        Ok(user_ids) => {
+            push::send_to_users(&user_ids, event);
+        }
+        Err(e) => tracing::warn!("查询公告目标用户失败: {}", e),
+    }
+}
+
+/// 写入已读记录，已存在的记录保持原阅读时间
+async fn insert_reads(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    notice_ids: Vec<Uuid>,
+) -> Result<(), AppError> {
+    if notice_ids.is_empty() {
+        return Ok(());
+    }
+
+    let now = Utc::now().naive_utc();
+    let models = notice_ids.into_iter().map(|notice_id| notice_read::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        notice_id: Set(notice_id),
+        user_id: Set(user_id),
+        read_time: Set(now),
+    });
+    notice_read::Entity::insert_many(models)
+        .on_conflict(
+            OnConflict::columns([notice_read::Column::NoticeId, notice_read::Column::UserId])
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+    Ok(())
+}
+
+async fn build_notice_responses(
+    db: &DatabaseConnection,
+    notices: Vec<notice::Model>,
+) -> Result<Vec<NoticeResponse>, AppError> {
+    let ids: Vec<Uuid> = notices.iter().map(|n| n.id).collect();
+
+    let mut roles_by_notice: HashMap<Uuid, Vec<String>> = HashMap::new();
+    for r in notice_role::Entity::find()
+        .filter(notice_role::Column::NoticeId.is_in(ids.clone()))
+        .all(db)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?
+    {
+        roles_by_notice
+            .entry(r.notice_id)
+            .or_default()
+            .push(r.role_id.to_string());
+    }
+
+    let read_counts: HashMap<Uuid, i64> = notice_read::Entity::find()
+        .select_only()
+        .column(notice_read::Column::NoticeId)
+        .column_as(notice_read::Column::Id.count(), "read_count")
+        .filter(notice_read::Column::NoticeId.is_in(ids))
+        .group_by(notice_read::Column::NoticeId)
+        .into_tuple::<(Uuid, i64)>()
+        .all(db)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?
+        .into_iter()
+        .collect();
+
+    Ok(notices
+        .into_iter()
+        .map(|n| NoticeResponse {
+            id: n.id.to_string(),
+            role_ids: roles_by_notice.remove(&n.id).unwrap_or_default(),
+            read_count: read_counts.get(&n.id).copied().unwrap_or(0) as u64,
+            title: n.title,
+            content: n.content,
+            status: n.status,
+            is_pinned: n.is_pinned,
+            start_time: format_time(n.start_time),
+            end_time: format_time(n.end_time),
+            published_time: format_time(n.published_time),
+            created_time: n.created_time.format(TIME_FORMAT).to_string(),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::middleware::auth_middleware;
+    use crate::common::testing;
+    use salvo::test::{ResponseExt, TestClient};
+    use serde_json::Value;
+
+    async fn get_json(app: &Service, token: &str, url: &str) -> Value {
+        let mut res = TestClient::get(format!("http://127.0.0.1/{}", url))
+            .bearer_auth(token)
+            .send(app)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK), "{}", url);
+        res.take_json::<Value>().await.unwrap()["data"].take()
+    }
+
+    async fn post(app: &Service, token: &str, url: &str) -> Response {
+        TestClient::post(format!("http://127.0.0.1/{}", url))
+            .bearer_auth(token)
+            .send(app)
+            .await
+    }
+
+    fn titles(page: &Value) -> Vec<&str> {
+        page["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|n| n["title"].as_str().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn my_notices_follow_roles_window_and_read_state() {
+        let Some(db) = testing::TestDb::migrated().await else {
+            return;
+        };
+        let (editor_id, viewer_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let (editor_role, other_role) = (Uuid::new_v4(), Uuid::new_v4());
+        let all = Uuid::new_v4();
+        db.execute_unprepared(&format!(
+            "INSERT INTO roles (id, code, name) VALUES ('{editor_role}', 'editor', '编辑'), ('{other_role}', 'auditor', '审计');
+             INSERT INTO users (id, username, password, real_name)
+             VALUES ('{editor_id}', 'editor', '-', '编辑'), ('{viewer_id}', 'viewer', '-', '访客');
+             INSERT INTO user_roles (user_id, role_id) VALUES ('{editor_id}', '{editor_role}');
+             INSERT INTO notices (id, title, content, status, published_time)
+             VALUES ('{all}', '全员', '-', 1, now() - interval '2 hours');
+             INSERT INTO notices (title, content, status, is_pinned, published_time)
+             VALUES ('编辑置顶', '-', 1, TRUE, now() - interval '3 hours');
+             INSERT INTO notice_roles (notice_id, role_id)
+             SELECT id, '{editor_role}' FROM notices WHERE title = '编辑置顶';
+             INSERT INTO notices (title, content, status, published_time)
+             VALUES ('审计', '-', 1, now()), ('草稿', '-', 0, NULL), ('已撤回', '-', 2, now());
+             INSERT INTO notice_roles (notice_id, role_id)
+             SELECT id, '{other_role}' FROM notices WHERE title = '审计';
+             INSERT INTO notices (title, content, status, published_time, start_time, end_time)
+             VALUES ('已过期', '-', 1, now(), NULL, now() - interval '1 hour'),
+                    ('未生效', '-', 1, now(), now() + interval '1 hour', NULL);"
+        ))
+        .await
+        .unwrap();
+
+        let app = testing::service(
+            Router::with_path("notice")
+                .hoop(auth_middleware)
+                .push(Router::with_path("my").get(get_my_notices))
+                .push(Router::with_path("unreadCount").get(get_unread_count))
+                .push(Router::with_path("readAll").post(mark_all_read))
+                .push(Router::with_path("{id}/read").post(mark_read)),
+            Some(db.arc()),
+        );
+        let editor = testing::access_token(editor_id, editor_role, "editor");
+        let viewer = testing::access_token(viewer_id, Uuid::new_v4(), "viewer");
+
+        // 置顶在前；其它角色的、草稿、已撤回、过期和未生效的公告不可见
+        let page = get_json(&app, &editor, "notice/my").await;
+        assert_eq!(titles(&page), ["编辑置顶", "全员"]);
+        assert_eq!(get_json(&app, &editor, "notice/unreadCount").await["count"], 2);
+        assert_eq!(get_json(&app, &viewer, "notice/unreadCount").await["count"], 1);
+
+        let res = post(&app, &editor, &format!("notice/{}/read", all)).await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        assert_eq!(get_json(&app, &editor, "notice/unreadCount").await["count"], 1);
+        let page = get_json(&app, &editor, "notice/my?unreadOnly=true").await;
+        assert_eq!(titles(&page), ["编辑置顶"]);
+        // 已读状态按用户区分
+        assert_eq!(get_json(&app, &viewer, "notice/unreadCount").await["count"], 1);
+
+        let hidden = notice::Entity::find()
+            .filter(notice::Column::Title.eq("审计"))
+            .one(&*db)
+            .await
+            .unwrap()
+            .unwrap();
+        let res = post(&app, &editor, &format!("notice/{}/read", hidden.id)).await;
+        assert_eq!(res.status_code, Some(StatusCode::NOT_FOUND));
+
+        let mut res = post(&app, &editor, "notice/readAll").await;
+        assert_eq!(res.take_json::<Value>().await.unwrap()["data"]["count"], 1);
+        assert_eq!(get_json(&app, &editor, "notice/unreadCount").await["count"], 0);
+    }
+
+    #[tokio::test]
+    async fn management_requires_admin() {
+        let token = testing::access_token(Uuid::new_v4(), Uuid::new_v4(), "editor");
+        let app = testing::service(super::super::routes::routes(), None);
+        let res = TestClient::get("http://127.0.0.1/notice/list")
+            .bearer_auth(&token)
+            .send(&app)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::FORBIDDEN));
+    }
+}
@@ -0,0 +1,7 @@
+// notice 模块 - 通知公告与已读状态
+
+mod dto;
+mod handler;
+mod routes;
+
+pub use routes::routes;
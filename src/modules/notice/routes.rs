@@ -0,0 +1,28 @@
+use salvo::prelude::*;
+use crate::common::middleware::{admin_only, auth_middleware};
+use super::handler;
+
+pub fn routes() -> Router {
+    Router::with_path("notice")
+        .hoop(auth_middleware)
+        // 当前用户
+        .push(Router::with_path("my").get(handler::get_my_notices))
+        .push(Router::with_path("unreadCount").get(handler::get_unread_count))
+        .push(Router::with_path("readAll").post(handler::mark_all_read))
+        .push(Router::with_path("<id>/read").post(handler::mark_read))
+        // 管理，仅管理员可用
+        .push(
+            Router::new()
+                .hoop(admin_only)
+                .push(Router::with_path("list").get(handler::get_notice_list))
+                .push(Router::new().post(handler::create_notice))
+                .push(
+                    Router::with_path("<id>")
+                        .get(handler::get_notice)
+                        .put(handler::update_notice)
+                        .delete(handler::delete_notice)
+                        .push(Router::with_path("publish").post(handler::publish_notice))
+                        .push(Router::with_path("withdraw").post(handler::withdraw_notice))
+                )
+        )
+}
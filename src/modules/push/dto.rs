@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use salvo::oapi::ToSchema;
+
+/// 推送结果
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PushResult {
+    /// 成功投递的连接数
+    pub delivered: usize,
+}
+
+/// 连接凭证
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PushTicket {
+    /// 一次性凭证，作为 ticket 查询参数建立 WebSocket / SSE 连接
+    pub ticket: String,
+    /// 有效期（秒）
+    pub expires_in: u64,
+}
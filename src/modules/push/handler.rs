@@ -0,0 +1,309 @@
+use futures_util::{stream, SinkExt, StreamExt};
+use salvo::http::header::{HeaderValue, CONTENT_ENCODING};
+use salvo::oapi::extract::{PathParam, QueryParam};
+use salvo::prelude::*;
+use salvo::sse::{SseEvent, SseKeepAlive};
+use salvo::websocket::{Message, WebSocket, WebSocketUpgrade};
+use std::sync::Arc;
+use std::time::Duration;
+use sea_orm::sea_query::Expr;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use tokio_stream::wrappers::ReceiverStream;
+use uuid::Uuid;
+
+use super::dto::{PushResult, PushTicket};
+use crate::common::jwt::JwtService;
+use crate::common::middleware::{extract_token_from_header, is_token_revoked, tokens_revoked_now};
+use crate::common::push::{self, PushEvent};
+use crate::common::{i18n, ApiResponse, AppError};
+use crate::models::user;
+
+/// WebSocket 心跳间隔
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 申请连接凭证
+///
+/// 浏览器的 WebSocket 和 EventSource 无法设置请求头，先用访问令牌申请凭证，
+/// 再通过 `ticket` 查询参数建立连接。凭证只能使用一次，有效期 30 秒。
+#[endpoint(
+    tags("实时推送"),
+    responses(
+        (status_code = 200, description = "申请成功"),
+        (status_code = 401, description = "未授权")
+    )
+)]
+pub async fn issue_ticket(depot: &Depot) -> Result<Json<ApiResponse<PushTicket>>, AppError> {
+    let user_id = depot
+        .get::<String>("user_id")
+        .ok()
+        .and_then(|id| Uuid::parse_str(id).ok())
+        .ok_or(AppError::Unauthorized)?;
+
+    let (ticket, ttl) = push::issue_ticket(user_id);
+    Ok(Json(ApiResponse::success(PushTicket {
+        ticket,
+        expires_in: ttl.as_secs(),
+    })))
+}
+
+/// 建立 WebSocket 推送连接
+///
+/// 通过 Authorization 请求头传递访问令牌，或通过 `ticket` 查询参数传递连接凭证。
+/// 服务端推送的每条消息都是 JSON：{"type": "...", "data": {...}}。
+#[endpoint(
+    tags("实时推送"),
+    parameters(
+        ("ticket" = Option<String>, Query, description = "连接凭证，无法设置请求头时使用"),
+    ),
+    responses(
+        (status_code = 101, description = "协议切换成功"),
+        (status_code = 400, description = "不是 WebSocket 握手请求"),
+        (status_code = 401, description = "未授权")
+    )
+)]
+pub async fn connect_ws(
+    req: &mut Request,
+    depot: &Depot,
+    res: &mut Response,
+) -> Result<(), AppError> {
+    let user_id = authenticate(req, depot).await?;
+
+    WebSocketUpgrade::new()
+        .upgrade(req, res, move |ws| handle_socket(ws, user_id))
+        .await
+        .map_err(|e| AppError::BadRequest(e.brief))
+}
+
+/// 建立 SSE 推送连接（WebSocket 不可用时的降级方案）
+///
+/// 事件名为事件类型，data 与 WebSocket 消息格式相同。
+#[endpoint(
+    tags("实时推送"),
+    parameters(
+        ("ticket" = Option<String>, Query, description = "连接凭证，EventSource 无法设置请求头时使用"),
+    ),
+    responses(
+        (status_code = 200, description = "text/event-stream 事件流"),
+        (status_code = 401, description = "未授权")
+    )
+)]
+pub async fn connect_sse(
+    req: &mut Request,
+    depot: &Depot,
+    res: &mut Response,
+) -> Result<(), AppError> {
+    let user_id = authenticate(req, depot).await?;
+
+    let (connection_id, receiver) = push::register(user_id);
+    // 客户端断开后事件流被丢弃，guard 随之注销连接
+    let guard = ConnectionGuard {
+        user_id,
+        connection_id,
+    };
+
+    let events = stream::once(async move { PushEvent::Connected { connection_id } })
+        .chain(ReceiverStream::new(receiver))
+        .map(move |event| {
+            let _ = &guard;
+            SseEvent::default().name(event.name()).json(&event)
+        });
+
+    // 显式声明不编码，避免压缩中间件缓冲事件流
+    res.headers_mut()
+        .insert(CONTENT_ENCODING, HeaderValue::from_static("identity"));
+    SseKeepAlive::new(events).stream(res);
+    Ok(())
+}
+
+/// 强制用户下线
+///
+/// 吊销该用户已签发的全部令牌，向其在线连接推送 sessionKilled 事件后关闭连接。仅管理员可用。
+#[endpoint(
+    tags("实时推送"),
+    parameters(
+        ("reason" = Option<String>, Query, description = "下线原因，展示给用户"),
+    ),
+    responses(
+        (status_code = 200, description = "推送成功"),
+        (status_code = 400, description = "参数错误"),
+        (status_code = 401, description = "未授权"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 404, description = "用户不存在")
+    )
+)]
+pub async fn kick_user(
+    id: PathParam<String>,
+    reason: QueryParam<String, false>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<PushResult>>, AppError> {
+    let user_id = Uuid::parse_str(&id.into_inner())
+        .map_err(|_| AppError::BadRequest("无效的用户ID".to_string()))?;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    // 令牌的签发时间早于吊销时间即视为失效，刷新令牌同样无法再换取新令牌
+    let result = user::Entity::update_many()
+        .col_expr(
+            user::Column::TokensRevokedTime,
+            Expr::value(tokens_revoked_now()),
+        )
+        .filter(user::Column::Id.eq(user_id))
+        .filter(user::Column::DeletedTime.is_null())
+        .exec(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+    if result.rows_affected == 0 {
+        return Err(AppError::NotFound("用户不存在".to_string()));
+    }
+
+    let delivered = push::send_to_user(
+        user_id,
+        PushEvent::SessionKilled {
+            reason: reason.into_inner(),
+        },
+    );
+    push::disconnect_user(user_id);
+
+    Ok(Json(ApiResponse::success_with_message(
+        PushResult { delivered },
+        i18n::t(depot, "操作成功"),
+    )))
+}
+
+// ========== 辅助函数 ==========
+
+/// 校验身份并返回用户ID，先取 Authorization 请求头中的访问令牌，再取 ticket 查询参数中的连接凭证
+async fn authenticate(req: &Request, depot: &Depot) -> Result<Uuid, AppError> {
+    let Some(token) = extract_token_from_header(req) else {
+        return req
+            .query::<String>("ticket")
+            .and_then(|ticket| push::redeem_ticket(&ticket))
+            .ok_or(AppError::Unauthorized);
+    };
+
+    let jwt_service = depot
+        .get::<Arc<JwtService>>("jwt_service")
+        .map_err(|_| AppError::InternalServerError("JWT 服务不可用".to_string()))?;
+
+    let claims = jwt_service
+        .validate_token(&token)
+        .map_err(|_| AppError::Unauthorized)?;
+    if let Ok(db) = depot.get::<Arc<DatabaseConnection>>("db") {
+        if is_token_revoked(db, &claims).await {
+            return Err(AppError::Unauthorized);
+        }
+    }
+    Uuid::parse_str(&claims.sub).map_err(|_| AppError::Unauthorized)
+}
+
+async fn handle_socket(ws: WebSocket, user_id: Uuid) {
+    let (connection_id, mut receiver) = push::register(user_id);
+    let (mut sink, mut incoming) = ws.split();
+    let mut heartbeat = tokio::time::interval(HEARTBEAT_INTERVAL);
+
+    let mut pending = Some(PushEvent::Connected { connection_id });
+    loop {
+        let event = match pending.take() {
+            Some(event) => event,
+            None => tokio::select! {
+                event = receiver.recv() => match event {
+                    Some(event) => event,
+                    // 连接被服务端关闭（如停机）
+                    None => {
+                        let _ = sink.send(Message::close()).await;
+                        break;
+                    }
+                },
+                message = incoming.next() => match message {
+                    // 客户端消息仅用于保活，ping 由底层自动回复
+                    Some(Ok(message)) if !message.is_close() => continue,
+                    _ => break,
+                },
+                _ = heartbeat.tick() => {
+                    if sink.send(Message::ping(Vec::new())).await.is_err() {
+                        break;
+                    }
+                    continue;
+                }
+            },
+        };
+
+        let text = match serde_json::to_string(&event) {
+            Ok(text) => text,
+            Err(e) => {
+                tracing::warn!("推送事件序列化失败: {}", e);
+                continue;
+            }
+        };
+        if sink.send(Message::text(text)).await.is_err() {
+            break;
+        }
+        if matches!(event, PushEvent::SessionKilled { .. }) {
+            let _ = sink.send(Message::close()).await;
+            break;
+        }
+    }
+
+    push::unregister(user_id, connection_id);
+}
+
+struct ConnectionGuard {
+    user_id: Uuid,
+    connection_id: u64,
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        push::unregister(self.user_id, self.connection_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::jwt::Claims;
+    use crate::common::middleware::auth_middleware;
+    use crate::common::testing;
+    use salvo::test::TestClient;
+    use sea_orm::ConnectionTrait;
+
+    async fn kick(db: &testing::TestDb, user_id: Uuid) -> Response {
+        let router = Router::with_path("kick/{id}")
+            .hoop(auth_middleware)
+            .post(kick_user);
+        TestClient::post(format!("http://127.0.0.1/kick/{}", user_id))
+            .bearer_auth(testing::admin_token())
+            .send(&testing::service(router, Some(db.arc())))
+            .await
+    }
+
+    #[tokio::test]
+    async fn kick_revokes_tokens_issued_before() {
+        let Some(db) = testing::TestDb::migrated().await else {
+            return;
+        };
+        let user_id = Uuid::new_v4();
+        db.execute_unprepared(&format!(
+            "INSERT INTO users (id, username, password, real_name) VALUES ('{user_id}', 'alice', '-', 'Alice')"
+        ))
+        .await
+        .unwrap();
+
+        let mut earlier = Claims::new_access_token(user_id, Uuid::new_v4(), "editor".to_string(), 1);
+        earlier.iat -= 5;
+        assert!(!is_token_revoked(&db, &earlier).await);
+
+        let res = kick(&db, user_id).await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+
+        // 吊销前签发的令牌失效，吊销后（包括同一秒内）签发的令牌仍然有效
+        assert!(is_token_revoked(&db, &earlier).await);
+        let fresh = Claims::new_access_token(user_id, Uuid::new_v4(), "editor".to_string(), 1);
+        assert!(!is_token_revoked(&db, &fresh).await);
+
+        let res = kick(&db, Uuid::new_v4()).await;
+        assert_eq!(res.status_code, Some(StatusCode::NOT_FOUND));
+    }
+}
@@ -0,0 +1,7 @@
+// push 模块 - 实时推送通道（WebSocket，SSE 作为降级方案）
+
+mod dto;
+mod handler;
+mod routes;
+
+pub use routes::routes;
@@ -0,0 +1,20 @@
+use salvo::prelude::*;
+use crate::common::middleware::{admin_only, auth_middleware};
+use super::handler;
+
+pub fn routes() -> Router {
+    Router::with_path("push")
+        // 浏览器的 WebSocket 和 EventSource 无法设置请求头，这两个接口自行校验令牌或连接凭证
+        .push(Router::with_path("ws").get(handler::connect_ws))
+        .push(Router::with_path("sse").get(handler::connect_sse))
+        .push(
+            Router::new()
+                .hoop(auth_middleware)
+                .push(Router::with_path("ticket").post(handler::issue_ticket))
+                .push(
+                    Router::new()
+                        .hoop(admin_only)
+                        .push(Router::with_path("kick/<id>").post(handler::kick_user))
+                )
+        )
+}
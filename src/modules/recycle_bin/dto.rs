@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+use salvo::oapi::ToSchema;
+
+/// 回收站实体类型
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RecycleEntity {
+    /// 用户
+    User,
+    /// 角色
+    Role,
+    /// 菜单
+    Menu,
+}
+
+impl RecycleEntity {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "user" => Some(RecycleEntity::User),
+            "role" => Some(RecycleEntity::Role),
+            "menu" => Some(RecycleEntity::Menu),
+            _ => None,
+        }
+    }
+}
+
+/// 回收站记录
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RecycleBinItem {
+    pub id: String,
+    /// 实体类型
+    pub entity_type: RecycleEntity,
+    /// 显示名称（用户名 / 角色名称 / 菜单名称）
+    pub name: String,
+    /// 辅助标识（真实姓名 / 角色代码 / 菜单路径）
+    pub code: Option<String>,
+    /// 删除时间
+    pub deleted_time: String,
+    /// 删除人ID
+    pub deleted_id: Option<String>,
+    /// 删除人用户名
+    pub deleted_by: Option<String>,
+}
+
+/// 清理过期记录结果
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PurgeResult {
+    /// 保留天数
+    pub retention_days: i64,
+    /// 清理的用户数
+    pub users: u64,
+    /// 清理的角色数
+    pub roles: u64,
+    /// 清理的菜单数
+    pub menus: u64,
+}
@@ -0,0 +1,635 @@
+use chrono::{Duration, NaiveDateTime, Utc};
+use salvo::oapi::extract::PathParam;
+use salvo::prelude::*;
+use sea_orm::{
+    sea_query::Expr, ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
+};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::dto::{PurgeResult, RecycleBinItem, RecycleEntity};
+use crate::common::constants::{
+    DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE, MAX_RECYCLE_BIN_RETENTION_DAYS, RECYCLE_BIN_RETENTION_DAYS,
+};
+use crate::common::{i18n, ApiResponse, AppError, PageResponse};
+use crate::models::{menu, role, role_menu, user};
+use crate::modules::system::service as system_params;
+
+/// 获取回收站列表
+#[endpoint(
+    tags("回收站"),
+    parameters(
+        ("entity" = String, Path, description = "实体类型：user、role、menu"),
+        ("page" = Option<u64>, Query, description = "当前页码，默认1"),
+        ("pageSize" = Option<u64>, Query, description = "每页数量，默认20，最大100"),
+    ),
+    responses(
+        (status_code = 200, description = "获取成功"),
+        (status_code = 400, description = "参数错误"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn list_deleted(
+    req: &mut Request,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<PageResponse<RecycleBinItem>>>, AppError> {
+    let entity = parse_entity(req.param::<String>("entity"))?;
+    let page = req.query::<u64>("page").unwrap_or(1).max(1);
+    let page_size = req
+        .query::<u64>("pageSize")
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    let offset = (page - 1) * page_size;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let (total, mut items) = match entity {
+        RecycleEntity::User => {
+            let query = user::Entity::find().filter(user::Column::DeletedTime.is_not_null());
+            let total = query.clone()
+                .count(db.as_ref())
+                .await
+                .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+            let records = query
+                .order_by_desc(user::Column::DeletedTime)
+                .offset(offset)
+                .limit(page_size)
+                .all(db.as_ref())
+                .await
+                .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+            let items: Vec<RecycleBinItem> = records
+                .into_iter()
+                .map(|u| {
+                    new_item(entity, u.id, u.username, Some(u.real_name), u.deleted_time, u.deleted_id)
+                })
+                .collect();
+            (total, items)
+        }
+        RecycleEntity::Role => {
+            let query = role::Entity::find().filter(role::Column::DeletedTime.is_not_null());
+            let total = query.clone()
+                .count(db.as_ref())
+                .await
+                .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+            let records = query
+                .order_by_desc(role::Column::DeletedTime)
+                .offset(offset)
+                .limit(page_size)
+                .all(db.as_ref())
+                .await
+                .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+            let items: Vec<RecycleBinItem> = records
+                .into_iter()
+                .map(|r| {
+                    new_item(entity, r.id, r.name, Some(r.code), r.deleted_time, r.deleted_id)
+                })
+                .collect();
+            (total, items)
+        }
+        RecycleEntity::Menu => {
+            let query = menu::Entity::find().filter(menu::Column::DeletedTime.is_not_null());
+            let total = query.clone()
+                .count(db.as_ref())
+                .await
+                .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+            let records = query
+                .order_by_desc(menu::Column::DeletedTime)
+                .offset(offset)
+                .limit(page_size)
+                .all(db.as_ref())
+                .await
+                .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+            let items: Vec<RecycleBinItem> = records
+                .into_iter()
+                .map(|m| new_item(entity, m.id, m.name, m.path, m.deleted_time, m.deleted_id))
+                .collect();
+            (total, items)
+        }
+    };
+
+    // 补充删除人用户名
+    let deleter_ids: Vec<Uuid> = items
+        .iter()
+        .filter_map(|item| item.deleted_id.as_deref())
+        .filter_map(|id| Uuid::parse_str(id).ok())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    if !deleter_ids.is_empty() {
+        let deleters: HashMap<String, String> = user::Entity::find()
+            .filter(user::Column::Id.is_in(deleter_ids))
+            .all(db.as_ref())
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?
+            .into_iter()
+            .map(|u| (u.id.to_string(), u.username))
+            .collect();
+        for item in &mut items {
+            item.deleted_by = item
+                .deleted_id
+                .as_ref()
+                .and_then(|id| deleters.get(id).cloned());
+        }
+    }
+
+    Ok(Json(ApiResponse::success(PageResponse::new(
+        items, total, page, page_size,
+    ))))
+}
+
+/// 从回收站恢复记录
+///
+/// 恢复前会重新校验唯一性，菜单还需要父菜单仍然有效，恢复菜单时一并恢复其角色关联。
+#[endpoint(
+    tags("回收站"),
+    parameters(
+        ("entity" = String, Path, description = "实体类型：user、role、menu"),
+    ),
+    responses(
+        (status_code = 200, description = "恢复成功"),
+        (status_code = 400, description = "无法恢复"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 404, description = "记录不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn restore_record(
+    req: &mut Request,
+    id: PathParam<String>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    let entity = parse_entity(req.param::<String>("entity"))?;
+    let record_id = Uuid::parse_str(&id.into_inner())
+        .map_err(|_| AppError::BadRequest("无效的记录ID".to_string()))?;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let user_id = depot
+        .get::<String>("user_id")
+        .ok()
+        .and_then(|s| Uuid::parse_str(s.as_str()).ok());
+    let now = Utc::now().naive_utc();
+
+    match entity {
+        RecycleEntity::User => {
+            let record = user::Entity::find_by_id(record_id)
+                .filter(user::Column::DeletedTime.is_not_null())
+                .one(db.as_ref())
+                .await
+                .map_err(|e| AppError::InternalServerError(e.to_string()))?
+                .ok_or(AppError::NotFound("回收站中不存在该记录".to_string()))?;
+
+            let conflict = user::Entity::find()
+                .filter(user::Column::Username.eq(record.username.as_str()))
+                .filter(user::Column::DeletedTime.is_null())
+                .count(db.as_ref())
+                .await
+                .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+            if conflict > 0 {
+                return Err(AppError::BadRequest(format!(
+                    "用户名 {} 已被使用，无法恢复",
+                    record.username
+                )));
+            }
+
+            let mut active_model: user::ActiveModel = record.into();
+            active_model.deleted_time = Set(None);
+            active_model.deleted_id = Set(None);
+            active_model.updated_time = Set(now);
+            active_model.updated_id = Set(user_id);
+            active_model
+                .update(db.as_ref())
+                .await
+                .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+        }
+        RecycleEntity::Role => {
+            let record = role::Entity::find_by_id(record_id)
+                .filter(role::Column::DeletedTime.is_not_null())
+                .one(db.as_ref())
+                .await
+                .map_err(|e| AppError::InternalServerError(e.to_string()))?
+                .ok_or(AppError::NotFound("回收站中不存在该记录".to_string()))?;
+
+            let conflict = role::Entity::find()
+                .filter(role::Column::Code.eq(record.code.as_str()))
+                .filter(role::Column::DeletedTime.is_null())
+                .count(db.as_ref())
+                .await
+                .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+            if conflict > 0 {
+                return Err(AppError::BadRequest(format!(
+                    "角色代码 {} 已被使用，无法恢复",
+                    record.code
+                )));
+            }
+
+            let mut active_model: role::ActiveModel = record.into();
+            active_model.deleted_time = Set(None);
+            active_model.deleted_id = Set(None);
+            active_model.updated_time = Set(now);
+            active_model.updated_id = Set(user_id);
+            active_model
+                .update(db.as_ref())
+                .await
+                .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+        }
+        RecycleEntity::Menu => {
+            let record = menu::Entity::find_by_id(record_id)
+                .filter(menu::Column::DeletedTime.is_not_null())
+                .one(db.as_ref())
+                .await
+                .map_err(|e| AppError::InternalServerError(e.to_string()))?
+                .ok_or(AppError::NotFound("回收站中不存在该记录".to_string()))?;
+
+            if let Some(parent_id) = record.parent_id {
+                let parent_alive = menu::Entity::find_by_id(parent_id)
+                    .filter(menu::Column::DeletedTime.is_null())
+                    .one(db.as_ref())
+                    .await
+                    .map_err(|e| AppError::InternalServerError(e.to_string()))?
+                    .is_some();
+                if !parent_alive {
+                    return Err(AppError::BadRequest(
+                        "父菜单已被删除，请先恢复父菜单".to_string(),
+                    ));
+                }
+            }
+
+            let txn = db
+                .begin()
+                .await
+                .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+            let mut active_model: menu::ActiveModel = record.into();
+            active_model.deleted_time = Set(None);
+            active_model.deleted_id = Set(None);
+            active_model.updated_time = Set(now);
+            active_model.updated_id = Set(user_id);
+            active_model
+                .update(&txn)
+                .await
+                .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+            // 角色关联随菜单软删除，一并恢复
+            role_menu::Entity::update_many()
+                .col_expr(role_menu::Column::DeletedTime, Expr::value(None::<NaiveDateTime>))
+                .col_expr(role_menu::Column::DeletedId, Expr::value(None::<Uuid>))
+                .filter(role_menu::Column::MenuId.eq(record_id))
+                .filter(role_menu::Column::DeletedTime.is_not_null())
+                .exec(&txn)
+                .await
+                .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+            txn.commit()
+                .await
+                .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+        }
+    }
+
+    Ok(Json(ApiResponse::success_with_message(
+        (),
+        i18n::t(depot, "恢复成功"),
+    )))
+}
+
+/// 彻底删除回收站中的记录
+#[endpoint(
+    tags("回收站"),
+    parameters(
+        ("entity" = String, Path, description = "实体类型：user、role、menu"),
+    ),
+    responses(
+        (status_code = 200, description = "删除成功"),
+        (status_code = 400, description = "无法删除"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 404, description = "记录不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn purge_record(
+    req: &mut Request,
+    id: PathParam<String>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    let entity = parse_entity(req.param::<String>("entity"))?;
+    let record_id = Uuid::parse_str(&id.into_inner())
+        .map_err(|_| AppError::BadRequest("无效的记录ID".to_string()))?;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let result = match entity {
+        RecycleEntity::User => {
+            user::Entity::delete_many()
+                .filter(user::Column::Id.eq(record_id))
+                .filter(user::Column::DeletedTime.is_not_null())
+                .exec(db.as_ref())
+                .await
+                .map_err(|e| AppError::InternalServerError(e.to_string()))?
+        }
+        RecycleEntity::Role => {
+            role::Entity::delete_many()
+                .filter(role::Column::Id.eq(record_id))
+                .filter(role::Column::DeletedTime.is_not_null())
+                .exec(db.as_ref())
+                .await
+                .map_err(|e| AppError::InternalServerError(e.to_string()))?
+        }
+        RecycleEntity::Menu => {
+            // 数据库外键会级联删除子菜单，存在未删除的后代时拒绝
+            let menus = menu::Entity::find()
+                .all(db.as_ref())
+                .await
+                .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+            let candidates: HashSet<Uuid> = menus
+                .iter()
+                .filter(|m| m.deleted_time.is_some())
+                .map(|m| m.id)
+                .collect();
+            if candidates.contains(&record_id)
+                && !subtree_within(&menus, record_id, &candidates)
+            {
+                return Err(AppError::BadRequest(
+                    "该菜单存在未删除的子菜单，无法彻底删除".to_string(),
+                ));
+            }
+            menu::Entity::delete_many()
+                .filter(menu::Column::Id.eq(record_id))
+                .filter(menu::Column::DeletedTime.is_not_null())
+                .exec(db.as_ref())
+                .await
+                .map_err(|e| AppError::InternalServerError(e.to_string()))?
+        }
+    };
+
+    if result.rows_affected == 0 {
+        return Err(AppError::NotFound("回收站中不存在该记录".to_string()));
+    }
+
+    Ok(Json(ApiResponse::success_with_message(
+        (),
+        i18n::t(depot, "删除成功"),
+    )))
+}
+
+/// 清理超过保留期的回收站记录
+#[endpoint(
+    tags("回收站"),
+    parameters(
+        ("retentionDays" = Option<i64>, Query, description = "保留天数（0-3650），默认取系统参数 sys.recycle_bin.retention_days"),
+    ),
+    responses(
+        (status_code = 200, description = "清理成功"),
+        (status_code = 400, description = "保留天数超出范围"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn purge_expired(
+    req: &mut Request,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<PurgeResult>>, AppError> {
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let retention_days = match req.query::<i64>("retentionDays") {
+        Some(days) if (0..=MAX_RECYCLE_BIN_RETENTION_DAYS).contains(&days) => days,
+        Some(_) => {
+            return Err(AppError::BadRequest(format!(
+                "保留天数必须在0-{}之间",
+                MAX_RECYCLE_BIN_RETENTION_DAYS
+            )))
+        }
+        None => retention_days(db.as_ref()).await,
+    };
+
+    let result = purge_expired_records(db.as_ref(), retention_days).await?;
+    Ok(Json(ApiResponse::success(result)))
+}
+
+/// 彻底删除删除时间早于保留期的用户、角色和菜单
+pub async fn purge_expired_records(
+    db: &DatabaseConnection,
+    retention_days: i64,
+) -> Result<PurgeResult, AppError> {
+    let cutoff = Duration::try_days(retention_days)
+        .and_then(|retention| Utc::now().naive_utc().checked_sub_signed(retention))
+        .ok_or_else(|| AppError::BadRequest("保留天数超出范围".to_string()))?;
+
+    let users = user::Entity::delete_many()
+        .filter(user::Column::DeletedTime.lt(cutoff))
+        .exec(db)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?
+        .rows_affected;
+
+    let roles = role::Entity::delete_many()
+        .filter(role::Column::DeletedTime.lt(cutoff))
+        .exec(db)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?
+        .rows_affected;
+
+    // 只清理整棵子树都已过期的菜单，避免外键级联误删
+    let menus = menu::Entity::find()
+        .all(db)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+    let candidates: HashSet<Uuid> = menus
+        .iter()
+        .filter(|m| m.deleted_time.is_some_and(|t| t < cutoff))
+        .map(|m| m.id)
+        .collect();
+    let purgeable: Vec<Uuid> = candidates
+        .iter()
+        .copied()
+        .filter(|id| subtree_within(&menus, *id, &candidates))
+        .collect();
+
+    let menus = if purgeable.is_empty() {
+        0
+    } else {
+        menu::Entity::delete_many()
+            .filter(menu::Column::Id.is_in(purgeable))
+            .exec(db)
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?
+            .rows_affected
+    };
+
+    tracing::info!(
+        "🗑️  回收站清理完成：用户 {}，角色 {}，菜单 {}",
+        users,
+        roles,
+        menus
+    );
+
+    Ok(PurgeResult {
+        retention_days,
+        users,
+        roles,
+        menus,
+    })
+}
+
+// ========== 辅助函数 ==========
+
+/// 回收站保留天数，优先读取系统参数，限制在允许范围内
+pub async fn retention_days(db: &DatabaseConnection) -> i64 {
+    system_params::get::<i64>(db, "sys.recycle_bin.retention_days")
+        .await
+        .unwrap_or(RECYCLE_BIN_RETENTION_DAYS)
+        .clamp(0, MAX_RECYCLE_BIN_RETENTION_DAYS)
+}
+
+fn parse_entity(entity: Option<String>) -> Result<RecycleEntity, AppError> {
+    entity
+        .as_deref()
+        .and_then(RecycleEntity::from_str)
+        .ok_or(AppError::BadRequest("无效的实体类型".to_string()))
+}
+
+fn new_item(
+    entity: RecycleEntity,
+    id: Uuid,
+    name: String,
+    code: Option<String>,
+    deleted_time: Option<chrono::NaiveDateTime>,
+    deleted_id: Option<Uuid>,
+) -> RecycleBinItem {
+    RecycleBinItem {
+        id: id.to_string(),
+        entity_type: entity,
+        name,
+        code,
+        deleted_time: deleted_time
+            .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default(),
+        deleted_id: deleted_id.map(|id| id.to_string()),
+        deleted_by: None,
+    }
+}
+
+/// 判断菜单及其全部后代是否都在给定集合中
+fn subtree_within(menus: &[menu::Model], root_id: Uuid, allowed: &HashSet<Uuid>) -> bool {
+    let mut stack = vec![root_id];
+    let mut visited = HashSet::new();
+    while let Some(id) = stack.pop() {
+        if !visited.insert(id) {
+            continue;
+        }
+        if !allowed.contains(&id) {
+            return false;
+        }
+        stack.extend(menus.iter().filter(|m| m.parent_id == Some(id)).map(|m| m.id));
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::middleware::{admin_only, auth_middleware};
+    use crate::common::testing::{self, TestDb};
+    use salvo::test::TestClient;
+    use sea_orm::ConnectionTrait;
+
+    async fn restore(db: &TestDb, entity: &str, id: Uuid, token: &str) -> Response {
+        let router = Router::with_path("recycleBin/{entity}/{id}/restore")
+            .hoop(auth_middleware)
+            .hoop(admin_only)
+            .post(restore_record);
+        TestClient::post(format!("http://127.0.0.1/recycleBin/{}/{}/restore", entity, id))
+            .bearer_auth(token)
+            .send(&testing::service(router, Some(db.arc())))
+            .await
+    }
+
+    #[tokio::test]
+    async fn restore_menu_requires_live_parent_and_restores_role_links() {
+        let Some(db) = TestDb::migrated().await else {
+            return;
+        };
+        let (role_id, parent, child) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        db.execute_unprepared(&format!(
+            "INSERT INTO roles (id, code, name) VALUES ('{role_id}', 'editor', '编辑');
+             INSERT INTO menus (id, parent_id, name, menu_type, deleted_time) VALUES
+                 ('{parent}', NULL, '父', 'catalog', now()),
+                 ('{child}', '{parent}', '子', 'menu', now());
+             INSERT INTO role_menus (role_id, menu_id, deleted_time) VALUES
+                 ('{role_id}', '{parent}', now()), ('{role_id}', '{child}', now());"
+        ))
+        .await
+        .unwrap();
+        let admin = testing::admin_token();
+
+        let editor = testing::access_token(Uuid::new_v4(), role_id, "editor");
+        let res = restore(&db, "menu", parent, &editor).await;
+        assert_eq!(res.status_code, Some(StatusCode::FORBIDDEN));
+
+        let res = restore(&db, "menu", child, &admin).await;
+        assert_eq!(res.status_code, Some(StatusCode::BAD_REQUEST));
+
+        for id in [parent, child] {
+            let res = restore(&db, "menu", id, &admin).await;
+            assert_eq!(res.status_code, Some(StatusCode::OK));
+        }
+        let live_links = role_menu::Entity::find()
+            .filter(role_menu::Column::RoleId.eq(role_id))
+            .filter(role_menu::Column::DeletedTime.is_null())
+            .count(&*db)
+            .await
+            .unwrap();
+        assert_eq!(live_links, 2);
+
+        let res = restore(&db, "menu", parent, &admin).await;
+        assert_eq!(res.status_code, Some(StatusCode::NOT_FOUND));
+    }
+
+    #[tokio::test]
+    async fn purge_keeps_menus_with_unexpired_descendants() {
+        let Some(db) = TestDb::migrated().await else {
+            return;
+        };
+        let ids: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+        let user_id = Uuid::new_v4();
+        db.execute_unprepared(&format!(
+            "INSERT INTO menus (id, parent_id, name, menu_type, deleted_time) VALUES
+                 ('{a}', NULL, '过期父', 'catalog', now() - interval '40 days'),
+                 ('{b}', '{a}', '过期子', 'menu', now() - interval '40 days'),
+                 ('{c}', NULL, '过期父2', 'catalog', now() - interval '40 days'),
+                 ('{d}', '{c}', '新删除子', 'menu', now() - interval '1 day');
+             INSERT INTO users (id, username, password, real_name, deleted_time)
+                 VALUES ('{user_id}', 'alice', '-', 'Alice', now() - interval '40 days');",
+            a = ids[0],
+            b = ids[1],
+            c = ids[2],
+            d = ids[3],
+        ))
+        .await
+        .unwrap();
+
+        let result = purge_expired_records(&db, 30).await.unwrap();
+        assert_eq!((result.users, result.roles, result.menus), (1, 0, 2));
+
+        let remaining: Vec<Uuid> = menu::Entity::find()
+            .filter(menu::Column::Id.is_in(ids.clone()))
+            .all(&*db)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| m.id)
+            .collect();
+        assert_eq!(remaining.len(), 2);
+        assert!(remaining.contains(&ids[2]) && remaining.contains(&ids[3]));
+    }
+}
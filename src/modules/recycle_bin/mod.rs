@@ -0,0 +1,8 @@
+// recycle_bin 模块 - 回收站（已软删除的用户、角色、菜单）
+
+mod dto;
+mod handler;
+mod routes;
+
+pub use handler::{purge_expired_records, retention_days};
+pub use routes::routes;
@@ -0,0 +1,16 @@
+use salvo::prelude::*;
+use crate::common::middleware::{admin_only, auth_middleware};
+use super::handler;
+
+pub fn routes() -> Router {
+    Router::with_path("recycleBin")
+        .hoop(auth_middleware)
+        .hoop(admin_only)
+        .push(Router::with_path("purge").post(handler::purge_expired))
+        .push(
+            Router::with_path("<entity>")
+                .get(handler::list_deleted)
+                .push(Router::with_path("<id>").delete(handler::purge_record))
+                .push(Router::with_path("<id>/restore").post(handler::restore_record))
+        )
+}
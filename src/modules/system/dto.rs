@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+use salvo::oapi::ToSchema;
+
+/// 参数值类型
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ParamValueType {
+    /// 字符串
+    String,
+    /// 数字
+    Number,
+    /// 布尔
+    Boolean,
+    /// JSON
+    Json,
+}
+
+impl ParamValueType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ParamValueType::String => "string",
+            ParamValueType::Number => "number",
+            ParamValueType::Boolean => "boolean",
+            ParamValueType::Json => "json",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "string" => Some(ParamValueType::String),
+            "number" => Some(ParamValueType::Number),
+            "boolean" => Some(ParamValueType::Boolean),
+            "json" => Some(ParamValueType::Json),
+            _ => None,
+        }
+    }
+}
+
+/// 创建系统参数请求
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[salvo(schema(example = json!({
+    "paramKey": "sys.user.init_password",
+    "paramValue": "123456",
+    "valueType": "string",
+    "paramGroup": "user",
+    "description": "新用户初始密码",
+    "isPublic": false
+})))]
+pub struct CreateSystemParamRequest {
+    /// 参数键
+    pub param_key: String,
+    /// 参数值
+    pub param_value: String,
+    /// 值类型：string、number、boolean、json
+    pub value_type: String,
+    /// 参数分组
+    #[serde(default = "default_group")]
+    pub param_group: String,
+    /// 描述
+    pub description: Option<String>,
+    /// 是否公开给前端
+    #[serde(default)]
+    pub is_public: bool,
+}
+
+/// 更新系统参数请求
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSystemParamRequest {
+    /// 参数键（内置参数不可修改）
+    pub param_key: Option<String>,
+    /// 参数值
+    pub param_value: Option<String>,
+    /// 值类型（内置参数不可修改）
+    pub value_type: Option<String>,
+    /// 参数分组
+    pub param_group: Option<String>,
+    /// 描述
+    pub description: Option<String>,
+    /// 是否公开给前端
+    pub is_public: Option<bool>,
+}
+
+/// 系统参数响应
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemParamResponse {
+    pub id: String,
+    pub param_key: String,
+    pub param_value: String,
+    pub value_type: String,
+    pub param_group: String,
+    pub description: Option<String>,
+    pub is_builtin: bool,
+    pub is_public: bool,
+    pub updated_time: String,
+}
+
+fn default_group() -> String {
+    "default".to_string()
+}
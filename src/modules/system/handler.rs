@@ -0,0 +1,454 @@
+use chrono::Utc;
+use regex::Regex;
+use salvo::oapi::extract::{JsonBody, PathParam};
+use salvo::prelude::*;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, Set,
+};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::OnceLock;
+use uuid::Uuid;
+
+use super::dto::{CreateSystemParamRequest, SystemParamResponse, UpdateSystemParamRequest};
+use super::service;
+use crate::common::constants::{DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE};
+use crate::common::{i18n, ApiResponse, AppError, PageResponse};
+use crate::models::system_param;
+
+/// 获取系统参数列表（分页）
+#[endpoint(
+    tags("系统参数"),
+    parameters(
+        ("group" = Option<String>, Query, description = "参数分组"),
+        ("keyword" = Option<String>, Query, description = "参数键或描述（模糊搜索）"),
+        ("page" = Option<u64>, Query, description = "当前页码，默认1"),
+        ("pageSize" = Option<u64>, Query, description = "每页数量，默认20，最大100"),
+    ),
+    responses(
+        (status_code = 200, description = "获取成功"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn get_param_list(
+    req: &mut Request,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<PageResponse<SystemParamResponse>>>, AppError> {
+    let page = req.query::<u64>("page").unwrap_or(1).max(1);
+    let page_size = req
+        .query::<u64>("pageSize")
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let mut query_builder =
+        system_param::Entity::find().filter(system_param::Column::DeletedTime.is_null());
+
+    if let Some(group) = req.query::<String>("group").filter(|g| !g.is_empty()) {
+        query_builder = query_builder.filter(system_param::Column::ParamGroup.eq(group));
+    }
+    if let Some(keyword) = req.query::<String>("keyword").filter(|k| !k.is_empty()) {
+        query_builder = query_builder.filter(
+            Condition::any()
+                .add(system_param::Column::ParamKey.contains(&keyword))
+                .add(system_param::Column::Description.contains(&keyword)),
+        );
+    }
+
+    let total = query_builder
+        .clone()
+        .count(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let params = query_builder
+        .order_by_asc(system_param::Column::ParamGroup)
+        .order_by_asc(system_param::Column::ParamKey)
+        .offset((page - 1) * page_size)
+        .limit(page_size)
+        .all(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let items = params.iter().map(model_to_response).collect();
+    Ok(Json(ApiResponse::success(PageResponse::new(
+        items, total, page, page_size,
+    ))))
+}
+
+/// 获取公开的系统参数（无需登录）
+///
+/// 返回 参数键 -> 按类型转换后的值，供前端启动时读取站点配置。
+#[endpoint(
+    tags("系统参数"),
+    responses(
+        (status_code = 200, description = "获取成功"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn get_public_params(
+    depot: &Depot,
+) -> Result<Json<ApiResponse<BTreeMap<String, serde_json::Value>>>, AppError> {
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let params = service::all_params(db.as_ref()).await?;
+    let public = params
+        .values()
+        .filter(|p| p.is_public)
+        .map(|p| (p.param_key.clone(), service::typed_value(p)))
+        .collect();
+
+    Ok(Json(ApiResponse::success(public)))
+}
+
+/// 获取单个系统参数
+#[endpoint(
+    tags("系统参数"),
+    responses(
+        (status_code = 200, description = "获取成功"),
+        (status_code = 404, description = "参数不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn get_param(
+    id: PathParam<String>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<SystemParamResponse>>, AppError> {
+    let param_id = Uuid::parse_str(&id.into_inner())
+        .map_err(|_| AppError::BadRequest("无效的参数ID".to_string()))?;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let param = find_param(db.as_ref(), param_id).await?;
+    Ok(Json(ApiResponse::success(model_to_response(&param))))
+}
+
+/// 创建系统参数
+#[endpoint(
+    tags("系统参数"),
+    responses(
+        (status_code = 200, description = "创建成功"),
+        (status_code = 400, description = "参数错误"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn create_param(
+    req: JsonBody<CreateSystemParamRequest>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<SystemParamResponse>>, AppError> {
+    let data = req.into_inner();
+
+    validate_key(&data.param_key)?;
+    service::validate_value(&data.value_type, &data.param_value)?;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let user_id = depot
+        .get::<String>("user_id")
+        .ok()
+        .and_then(|s| Uuid::parse_str(s.as_str()).ok());
+
+    ensure_key_available(db.as_ref(), &data.param_key, None).await?;
+
+    let now = Utc::now().naive_utc();
+    let param = system_param::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        param_key: Set(data.param_key),
+        param_value: Set(data.param_value),
+        value_type: Set(data.value_type),
+        param_group: Set(data.param_group),
+        description: Set(data.description),
+        is_builtin: Set(false),
+        is_public: Set(data.is_public),
+        created_time: Set(now),
+        created_id: Set(user_id),
+        updated_time: Set(now),
+        updated_id: Set(user_id),
+        deleted_time: Set(None),
+        deleted_id: Set(None),
+    }
+    .insert(db.as_ref())
+    .await
+    .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    service::invalidate_cache(db.as_ref()).await;
+
+    Ok(Json(ApiResponse::success_with_message(
+        model_to_response(&param),
+        i18n::t(depot, "创建成功"),
+    )))
+}
+
+/// 更新系统参数
+#[endpoint(
+    tags("系统参数"),
+    responses(
+        (status_code = 200, description = "更新成功"),
+        (status_code = 400, description = "参数错误"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 404, description = "参数不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn update_param(
+    id: PathParam<String>,
+    req: JsonBody<UpdateSystemParamRequest>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<SystemParamResponse>>, AppError> {
+    let param_id = Uuid::parse_str(&id.into_inner())
+        .map_err(|_| AppError::BadRequest("无效的参数ID".to_string()))?;
+
+    let data = req.into_inner();
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let user_id = depot
+        .get::<String>("user_id")
+        .ok()
+        .and_then(|s| Uuid::parse_str(s.as_str()).ok());
+
+    let existing = find_param(db.as_ref(), param_id).await?;
+
+    if existing.is_builtin {
+        let key_changed = data.param_key.as_ref().is_some_and(|k| *k != existing.param_key);
+        let type_changed = data.value_type.as_ref().is_some_and(|t| *t != existing.value_type);
+        if key_changed || type_changed {
+            return Err(AppError::BadRequest("内置参数不能修改键和类型".to_string()));
+        }
+    }
+
+    if let Some(key) = &data.param_key {
+        validate_key(key)?;
+        ensure_key_available(db.as_ref(), key, Some(param_id)).await?;
+    }
+
+    let value_type = data.value_type.clone().unwrap_or(existing.value_type.clone());
+    let param_value = data.param_value.clone().unwrap_or(existing.param_value.clone());
+    service::validate_value(&value_type, &param_value)?;
+
+    let mut active_model: system_param::ActiveModel = existing.into();
+    if let Some(key) = data.param_key {
+        active_model.param_key = Set(key);
+    }
+    active_model.param_value = Set(param_value);
+    active_model.value_type = Set(value_type);
+    if let Some(group) = data.param_group {
+        active_model.param_group = Set(group);
+    }
+    if data.description.is_some() {
+        active_model.description = Set(data.description);
+    }
+    if let Some(is_public) = data.is_public {
+        active_model.is_public = Set(is_public);
+    }
+    active_model.updated_time = Set(Utc::now().naive_utc());
+    active_model.updated_id = Set(user_id);
+
+    let updated = active_model
+        .update(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    service::invalidate_cache(db.as_ref()).await;
+
+    Ok(Json(ApiResponse::success_with_message(
+        model_to_response(&updated),
+        i18n::t(depot, "更新成功"),
+    )))
+}
+
+/// 删除系统参数（软删除）
+#[endpoint(
+    tags("系统参数"),
+    responses(
+        (status_code = 200, description = "删除成功"),
+        (status_code = 400, description = "内置参数不可删除"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 404, description = "参数不存在"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn delete_param(
+    id: PathParam<String>,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    let param_id = Uuid::parse_str(&id.into_inner())
+        .map_err(|_| AppError::BadRequest("无效的参数ID".to_string()))?;
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let user_id = depot
+        .get::<String>("user_id")
+        .ok()
+        .and_then(|s| Uuid::parse_str(s.as_str()).ok());
+
+    let existing = find_param(db.as_ref(), param_id).await?;
+    if existing.is_builtin {
+        return Err(AppError::BadRequest("内置参数不可删除".to_string()));
+    }
+
+    let mut active_model: system_param::ActiveModel = existing.into();
+    active_model.deleted_time = Set(Some(Utc::now().naive_utc()));
+    active_model.deleted_id = Set(user_id);
+    active_model
+        .update(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    service::invalidate_cache(db.as_ref()).await;
+
+    Ok(Json(ApiResponse::success_with_message(
+        (),
+        i18n::t(depot, "删除成功"),
+    )))
+}
+
+// ========== 辅助函数 ==========
+
+async fn find_param(
+    db: &DatabaseConnection,
+    param_id: Uuid,
+) -> Result<system_param::Model, AppError> {
+    system_param::Entity::find_by_id(param_id)
+        .filter(system_param::Column::DeletedTime.is_null())
+        .one(db)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?
+        .ok_or(AppError::NotFound("参数不存在".to_string()))
+}
+
+/// 校验参数键格式：字母开头，允许字母、数字、点、下划线和横线
+fn validate_key(key: &str) -> Result<(), AppError> {
+    static KEY_PATTERN: OnceLock<Regex> = OnceLock::new();
+    let pattern =
+        KEY_PATTERN.get_or_init(|| Regex::new(r"^[A-Za-z][A-Za-z0-9_.\-]{0,99}$").unwrap());
+    if pattern.is_match(key) {
+        Ok(())
+    } else {
+        Err(AppError::BadRequest("参数键格式无效".to_string()))
+    }
+}
+
+/// 参数键在所有记录（含已删除）中唯一
+async fn ensure_key_available(
+    db: &DatabaseConnection,
+    key: &str,
+    exclude_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    let mut query = system_param::Entity::find().filter(system_param::Column::ParamKey.eq(key));
+    if let Some(id) = exclude_id {
+        query = query.filter(system_param::Column::Id.ne(id));
+    }
+    let exists = query
+        .count(db)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?
+        > 0;
+    if exists {
+        return Err(AppError::BadRequest(format!("参数键 {} 已存在", key)));
+    }
+    Ok(())
+}
+
+fn model_to_response(p: &system_param::Model) -> SystemParamResponse {
+    SystemParamResponse {
+        id: p.id.to_string(),
+        param_key: p.param_key.clone(),
+        param_value: p.param_value.clone(),
+        value_type: p.value_type.clone(),
+        param_group: p.param_group.clone(),
+        description: p.description.clone(),
+        is_builtin: p.is_builtin,
+        is_public: p.is_public,
+        updated_time: p.updated_time.format("%Y-%m-%d %H:%M:%S").to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::middleware::{admin_only, auth_middleware};
+    use crate::common::testing;
+    use salvo::test::{ResponseExt, TestClient};
+    use serde_json::{json, Value};
+
+    #[tokio::test]
+    async fn writes_require_admin_and_refresh_the_cache() {
+        let Some(db) = testing::TestDb::migrated().await else {
+            return;
+        };
+        let app = testing::service(super::super::routes::routes(), Some(db.arc()));
+        let body = json!({
+            "paramKey": "site.title",
+            "paramValue": "Maple",
+            "valueType": "string",
+            "isPublic": true,
+        });
+
+        let editor = testing::access_token(Uuid::new_v4(), Uuid::new_v4(), "editor");
+        let res = TestClient::post("http://127.0.0.1/system/param")
+            .bearer_auth(&editor)
+            .json(&body)
+            .send(&app)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::FORBIDDEN));
+
+        let admin = testing::admin_token();
+        let mut res = TestClient::post("http://127.0.0.1/system/param")
+            .bearer_auth(&admin)
+            .json(&body)
+            .send(&app)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        let id = res.take_json::<Value>().await.unwrap()["data"]["id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+
+        // 公开接口只返回 is_public 的参数，值按类型转换
+        let mut res = TestClient::get("http://127.0.0.1/system/param/public")
+            .send(&app)
+            .await;
+        let public = res.take_json::<Value>().await.unwrap();
+        assert_eq!(public["data"]["site.title"], "Maple");
+        assert!(public["data"].get("sys.recycle_bin.retention_days").is_none());
+        assert_eq!(
+            service::get::<i64>(&db, "sys.recycle_bin.retention_days").await,
+            Some(30)
+        );
+        assert_eq!(service::get::<bool>(&db, "site.title").await, None);
+
+        let update = testing::service(
+            Router::with_path("system/param/{id}")
+                .hoop(auth_middleware)
+                .hoop(admin_only)
+                .put(update_param),
+            Some(db.arc()),
+        );
+        let res = TestClient::put(format!("http://127.0.0.1/system/param/{}", id))
+            .bearer_auth(&admin)
+            .json(&json!({ "paramValue": "Maple Admin" }))
+            .send(&update)
+            .await;
+        assert_eq!(res.status_code, Some(StatusCode::OK));
+        assert_eq!(
+            service::get::<String>(&db, "site.title").await.as_deref(),
+            Some("Maple Admin")
+        );
+    }
+}
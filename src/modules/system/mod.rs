@@ -1 +1,8 @@
-// system 模块 - 待实现
+// system 模块 - 系统参数
+
+mod dto;
+mod handler;
+mod routes;
+pub mod service;
+
+pub use routes::routes;
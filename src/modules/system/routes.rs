@@ -0,0 +1,25 @@
+use salvo::prelude::*;
+use crate::common::middleware::{admin_only, auth_middleware};
+use super::handler;
+
+pub fn routes() -> Router {
+    Router::with_path("system/param")
+        .push(Router::with_path("public").get(handler::get_public_params))
+        .push(
+            Router::new()
+                .hoop(auth_middleware)
+                .push(Router::with_path("list").get(handler::get_param_list))
+                .push(Router::with_path("<id>").get(handler::get_param))
+                // 参数会影响系统行为（如回收站自动清理），修改仅管理员可用
+                .push(
+                    Router::new()
+                        .hoop(admin_only)
+                        .push(Router::new().post(handler::create_param))
+                        .push(
+                            Router::with_path("<id>")
+                                .put(handler::update_param)
+                                .delete(handler::delete_param)
+                        )
+                )
+        )
+}
@@ -0,0 +1,188 @@
+// 系统参数服务 - 带内存缓存的参数读取
+// 参数在首次读取时整体加载，写操作后失效，下次读取时重新加载。
+// 写操作通过 PostgreSQL NOTIFY 通知其他实例失效缓存，另设有效期兜底，
+// 监听连接断开期间丢失的通知最多影响一个有效期。
+
+use sea_orm::sqlx::postgres::PgListener;
+use sea_orm::{ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use super::dto::ParamValueType;
+use crate::common::{shutdown, AppError};
+use crate::models::system_param;
+
+/// 缓存有效期
+const CACHE_TTL: Duration = Duration::from_secs(60);
+/// 参数变更通知频道
+const CHANGE_CHANNEL: &str = "system_params_changed";
+
+type ParamMap = Arc<HashMap<String, system_param::Model>>;
+
+struct CachedParams {
+    params: ParamMap,
+    loaded_at: Instant,
+}
+
+// 全局参数缓存：参数键 -> 参数
+static PARAM_CACHE: RwLock<Option<CachedParams>> = RwLock::new(None);
+/// 缓存代数，每次失效加一；加载期间发生失效时不写入缓存，避免旧快照覆盖
+static CACHE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// 获取全部有效参数，缓存为空或过期时从数据库加载
+pub async fn all_params(db: &DatabaseConnection) -> Result<ParamMap, AppError> {
+    let cached = PARAM_CACHE.read().ok().and_then(|guard| {
+        guard
+            .as_ref()
+            .filter(|c| c.loaded_at.elapsed() < CACHE_TTL)
+            .map(|c| c.params.clone())
+    });
+    if let Some(params) = cached {
+        return Ok(params);
+    }
+
+    let generation = CACHE_GENERATION.load(Ordering::Acquire);
+    let params: HashMap<String, system_param::Model> = system_param::Entity::find()
+        .filter(system_param::Column::DeletedTime.is_null())
+        .all(db)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?
+        .into_iter()
+        .map(|p| (p.param_key.clone(), p))
+        .collect();
+    let params = Arc::new(params);
+
+    if let Ok(mut guard) = PARAM_CACHE.write() {
+        // 在写锁内比较代数，失效与写入不会交错
+        if CACHE_GENERATION.load(Ordering::Acquire) == generation {
+            *guard = Some(CachedParams {
+                params: params.clone(),
+                loaded_at: Instant::now(),
+            });
+        }
+    }
+    Ok(params)
+}
+
+/// 参数写入后调用：使本实例缓存失效，并通知其他实例
+pub async fn invalidate_cache(db: &DatabaseConnection) {
+    invalidate_local();
+    let sql = format!("NOTIFY {}", CHANGE_CHANNEL);
+    if let Err(e) = db.execute_unprepared(&sql).await {
+        tracing::warn!("发送系统参数变更通知失败，其他实例将在缓存过期后刷新: {}", e);
+    }
+}
+
+/// 监听其他实例的参数变更通知，数据库连接建立后启动
+pub fn spawn_change_listener(db: Arc<DatabaseConnection>) {
+    tokio::spawn(async move {
+        while !shutdown::is_shutting_down() {
+            if let Err(e) = listen_for_changes(&db).await {
+                // 停机时连接池关闭导致的中断无需告警
+                if shutdown::is_shutting_down() {
+                    break;
+                }
+                tracing::warn!("系统参数变更监听中断，5 秒后重试: {}", e);
+            }
+            // 监听中断期间可能错过通知
+            invalidate_local();
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        }
+    });
+}
+
+/// 按类型读取参数，值按 value_type 转换后反序列化为 T
+///
+/// 支持 String、数字、bool 以及任意可从 JSON 反序列化的类型，
+/// 参数不存在或类型不匹配时返回 None。
+pub async fn get<T: DeserializeOwned>(db: &DatabaseConnection, key: &str) -> Option<T> {
+    let params = match all_params(db).await {
+        Ok(params) => params,
+        Err(e) => {
+            tracing::warn!("读取系统参数 {} 失败: {}", key, e);
+            return None;
+        }
+    };
+
+    let param = params.get(key)?;
+    match serde_json::from_value(typed_value(param)) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            tracing::warn!("系统参数 {} 类型转换失败: {}", key, e);
+            None
+        }
+    }
+}
+
+/// 校验参数值能否按声明的类型解析
+pub fn validate_value(value_type: &str, value: &str) -> Result<(), AppError> {
+    let value_type = ParamValueType::from_str(value_type)
+        .ok_or(AppError::BadRequest("无效的参数类型".to_string()))?;
+
+    let valid = match value_type {
+        ParamValueType::String => true,
+        ParamValueType::Number => value.trim().parse::<f64>().is_ok(),
+        ParamValueType::Boolean => value.trim().parse::<bool>().is_ok(),
+        ParamValueType::Json => serde_json::from_str::<serde_json::Value>(value).is_ok(),
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::BadRequest(format!(
+            "参数值与类型 {} 不匹配",
+            value_type.as_str()
+        )))
+    }
+}
+
+/// 将参数值转换为对应类型的 JSON 值
+pub fn typed_value(param: &system_param::Model) -> serde_json::Value {
+    let value = param.param_value.trim();
+    match ParamValueType::from_str(&param.value_type) {
+        Some(ParamValueType::Number) => value
+            .parse::<i64>()
+            .map(serde_json::Value::from)
+            .or_else(|_| value.parse::<f64>().map(serde_json::Value::from))
+            .unwrap_or(serde_json::Value::Null),
+        Some(ParamValueType::Boolean) => value
+            .parse::<bool>()
+            .map(serde_json::Value::Bool)
+            .unwrap_or(serde_json::Value::Null),
+        Some(ParamValueType::Json) => {
+            serde_json::from_str(value).unwrap_or(serde_json::Value::Null)
+        }
+        _ => serde_json::Value::String(param.param_value.clone()),
+    }
+}
+
+// ========== 辅助函数 ==========
+
+fn invalidate_local() {
+    if let Ok(mut guard) = PARAM_CACHE.write() {
+        CACHE_GENERATION.fetch_add(1, Ordering::AcqRel);
+        *guard = None;
+    }
+}
+
+async fn listen_for_changes(db: &DatabaseConnection) -> Result<(), sea_orm::sqlx::Error> {
+    let mut listener = PgListener::connect_with(db.get_postgres_connection_pool()).await?;
+    listener.listen(CHANGE_CHANNEL).await?;
+    // 开始监听前的变更不会收到通知
+    invalidate_local();
+    loop {
+        match listener.try_recv().await? {
+            Some(_) => invalidate_local(),
+            // 连接断开，下次调用时自动重连
+            None => {
+                if shutdown::is_shutting_down() {
+                    return Ok(());
+                }
+                invalidate_local();
+            }
+        }
+    }
+}
@@ -9,7 +9,7 @@ pub struct UserListQuery {
     pub username: Option<String>,
     /// 昵称（模糊搜索）
     pub real_name: Option<String>,
-    /// 性别
+    /// 性别：0-未知，1-男，2-女（字典 sys_user_gender）
     pub gender: Option<i16>,
     /// 邮箱（模糊搜索）
     pub email: Option<String>,
@@ -38,6 +38,32 @@ pub struct UserListItem {
     pub email: Option<String>,
     pub phone: Option<String>,
     pub avatar: Option<String>,
+    pub gender: i16,
     pub status: i16,
     pub created_time: String,
 }
+
+/// 用户导入结果
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UserImportResult {
+    /// 是否仅校验
+    pub dry_run: bool,
+    /// 数据行数（不含表头和空行）
+    pub total: usize,
+    /// 实际导入的用户数，存在错误或仅校验时为 0
+    pub imported: usize,
+    /// 逐行错误，为空表示全部校验通过
+    pub errors: Vec<UserImportError>,
+}
+
+/// 导入错误
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UserImportError {
+    /// 文件中的行号（表头为第 1 行）
+    pub row: usize,
+    /// 出错的列
+    pub column: Option<String>,
+    pub message: String,
+}
@@ -1,11 +1,41 @@
+use chrono::Utc;
 use salvo::prelude::*;
 use salvo::oapi::extract::QueryParam;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use sea_orm::{DatabaseConnection, EntityTrait, QueryFilter, ColumnTrait, QueryOrder, PaginatorTrait, QuerySelect};
+use sea_orm::{
+    ActiveModelTrait, DatabaseConnection, EntityTrait, QueryFilter, ColumnTrait, QueryOrder,
+    PaginatorTrait, QuerySelect, Select, Set, TransactionTrait,
+};
+use uuid::Uuid;
 
-use crate::common::{ApiResponse, AppError, response::PageResponse, constants::MAX_PAGE_SIZE};
-use crate::models::user;
-use super::dto::{UserListQuery, UserListItem};
+use crate::common::{
+    i18n, validation, ApiResponse, AppError, response::PageResponse,
+    constants::{MAX_PAGE_SIZE, PASSWORD_MAX_LENGTH, PASSWORD_MIN_LENGTH, SUPER_ADMIN_ROLE_ID},
+};
+use crate::common::{crypto, middleware};
+use crate::models::{role, user, user_role};
+use super::dto::{UserListQuery, UserListItem, UserImportError, UserImportResult};
+use super::sheet::{self, SheetFormat};
+
+/// 单次导入的最大行数
+const MAX_IMPORT_ROWS: usize = 1000;
+/// 导入文件的最大字节数
+const MAX_IMPORT_FILE_SIZE: u64 = 5 * 1024 * 1024;
+/// 单次导出的最大行数
+const MAX_EXPORT_ROWS: u64 = 10000;
+
+/// 导入列：(字段名, 表头, 是否必填)，表头可使用字段名或中文名，必填列可带 * 号
+const IMPORT_COLUMNS: &[(&str, &str, bool)] = &[
+    ("username", "用户名", true),
+    ("realName", "真实姓名", true),
+    ("password", "密码", true),
+    ("email", "邮箱", false),
+    ("phone", "手机号", false),
+    ("gender", "性别", false),
+    ("status", "状态", false),
+    ("roleCodes", "角色代码", false),
+];
 
 /// 获取用户列表（分页）
 #[endpoint(
@@ -39,6 +69,365 @@ pub async fn get_user_list(
     let page_size = params.page_size.min(MAX_PAGE_SIZE).max(1);
 
     // 3. 构建查询条件
+    let query_builder = filtered_users(&params);
+
+    // 4. 查询总数（用于分页）
+    let total = query_builder.clone()
+        .count(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    // 5. 分页查询数据，按创建时间倒序
+    let users = query_builder
+        .order_by_desc(user::Column::CreatedTime)
+        .offset((page - 1) * page_size)
+        .limit(page_size)
+        .all(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    // 6. 转换为响应结构（隐藏敏感字段如密码）
+    let items: Vec<UserListItem> = users.into_iter().map(|u| UserListItem {
+        id: u.id.to_string(),
+        username: u.username,
+        real_name: u.real_name,
+        email: u.email,
+        phone: u.phone,
+        avatar: u.avatar,
+        gender: u.gender,
+        status: u.status,
+        created_time: u.created_time.format("%Y-%m-%d %H:%M:%S").to_string(),
+    }).collect();
+
+    // 7. 返回分页响应
+    let page_response = PageResponse::new(items, total, page, page_size);
+    Ok(Json(ApiResponse::success(page_response)))
+}
+
+/// 下载用户导入模板
+#[endpoint(
+    tags("用户管理"),
+    parameters(
+        ("format" = Option<String>, Query, description = "模板格式：xlsx（默认）或 csv"),
+    ),
+    responses(
+        (status_code = 200, description = "模板文件"),
+        (status_code = 400, description = "参数错误"),
+        (status_code = 403, description = "需要管理员权限")
+    )
+)]
+pub async fn download_import_template(
+    req: &mut Request,
+    res: &mut Response,
+) -> Result<(), AppError> {
+    let format = SheetFormat::from_param(req.query::<String>("format").as_deref())?;
+
+    let header = IMPORT_COLUMNS
+        .iter()
+        .map(|(_, label, required)| {
+            if *required { format!("{}*", label) } else { label.to_string() }
+        })
+        .collect();
+    let example = [
+        "zhangsan", "张三", "Zhangsan@123", "zhangsan@example.com", "13800138000", "1", "1", "",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+
+    let body = sheet::write_rows(format, &[header, example])?;
+    write_attachment(res, format, "user_import_template", body)
+}
+
+/// 批量导入用户
+///
+/// 使用 multipart/form-data 上传 CSV 或 XLSX 文件（字段名 `file`，按扩展名识别格式）。
+/// 所有行校验通过后才在同一事务中写入；任一行有误则不导入任何数据，返回逐行错误。
+/// 性别：0-未知，1-男，2-女；状态：1-启用，0-禁用；多个角色代码用逗号分隔。仅管理员可用。
+#[endpoint(
+    tags("用户管理"),
+    parameters(
+        ("dryRun" = Option<bool>, Query, description = "是否仅校验不写入，默认false"),
+    ),
+    responses(
+        (status_code = 200, description = "导入完成，errors 不为空时表示校验未通过"),
+        (status_code = 400, description = "文件格式错误"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn import_users(
+    req: &mut Request,
+    depot: &Depot,
+) -> Result<Json<ApiResponse<UserImportResult>>, AppError> {
+    let dry_run = req.query::<bool>("dryRun").unwrap_or(false);
+
+    let part = req
+        .file("file")
+        .await
+        .ok_or_else(|| AppError::BadRequest("请选择要上传的文件".to_string()))?;
+    if part.size() > MAX_IMPORT_FILE_SIZE {
+        return Err(AppError::BadRequest(format!(
+            "文件大小不能超过 {}MB",
+            MAX_IMPORT_FILE_SIZE / 1024 / 1024
+        )));
+    }
+    let format = SheetFormat::from_file_name(part.name().unwrap_or_default())?;
+    let data = tokio::fs::read(part.path())
+        .await
+        .map_err(|e| AppError::InternalServerError(format!("读取上传文件失败: {}", e)))?;
+
+    let mut rows = sheet::read_rows(format, &data)?.into_iter();
+    let header = rows
+        .next()
+        .ok_or_else(|| AppError::BadRequest("导入文件中没有数据".to_string()))?;
+    let columns = map_columns(&header)?;
+
+    // 行号从 1 开始，表头为第 1 行；跳过空行但保留原始行号
+    let rows: Vec<(usize, Vec<String>)> = rows
+        .enumerate()
+        .map(|(i, row)| (i + 2, row))
+        .filter(|(_, row)| row.iter().any(|cell| !cell.is_empty()))
+        .collect();
+    if rows.is_empty() {
+        return Err(AppError::BadRequest("导入文件中没有数据".to_string()));
+    }
+    if rows.len() > MAX_IMPORT_ROWS {
+        return Err(AppError::BadRequest(format!(
+            "单次最多导入 {} 行",
+            MAX_IMPORT_ROWS
+        )));
+    }
+
+    let db = depot
+        .get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let user_id = depot
+        .get::<String>("user_id")
+        .ok()
+        .and_then(|s| Uuid::parse_str(s.as_str()).ok());
+
+    // 用户名唯一约束包含已删除的用户
+    let usernames: Vec<String> = rows
+        .iter()
+        .map(|(_, row)| cell(row, &columns, "username").to_string())
+        .filter(|u| !u.is_empty())
+        .collect();
+    let taken: HashSet<String> = user::Entity::find()
+        .select_only()
+        .column(user::Column::Username)
+        .filter(user::Column::Username.is_in(usernames))
+        .into_tuple::<String>()
+        .all(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?
+        .into_iter()
+        .collect();
+
+    let role_ids: HashMap<String, Uuid> = role::Entity::find()
+        .filter(role::Column::DeletedTime.is_null())
+        .all(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?
+        .into_iter()
+        .map(|r| (r.code, r.id))
+        .collect();
+
+    // 只有超级管理员可以分配超级管理员角色
+    let forbidden_roles: HashSet<Uuid> = if middleware::is_admin(depot) {
+        HashSet::new()
+    } else {
+        Uuid::parse_str(SUPER_ADMIN_ROLE_ID).into_iter().collect()
+    };
+
+    let mut validator = RowValidator {
+        columns: &columns,
+        taken: &taken,
+        role_ids: &role_ids,
+        forbidden_roles: &forbidden_roles,
+        seen: HashSet::new(),
+        errors: Vec::new(),
+    };
+    let users: Vec<ImportedUser> = rows
+        .iter()
+        .filter_map(|(row_number, row)| validator.validate(*row_number, row))
+        .collect();
+
+    let total = rows.len();
+    let errors: Vec<UserImportError> = validator
+        .errors
+        .into_iter()
+        .map(|e| UserImportError {
+            message: i18n::t(depot, &e.message),
+            ..e
+        })
+        .collect();
+
+    if !errors.is_empty() || dry_run {
+        let message = if errors.is_empty() {
+            "校验通过"
+        } else {
+            "数据校验未通过，未导入任何数据"
+        };
+        return Ok(Json(ApiResponse::success_with_message(
+            UserImportResult { dry_run, total, imported: 0, errors },
+            i18n::t(depot, message),
+        )));
+    }
+
+    let passwords = hash_passwords(users.iter().map(|u| u.password.clone()).collect()).await?;
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    let now = Utc::now().naive_utc();
+    for (data, password) in users.iter().zip(passwords) {
+        let id = Uuid::new_v4();
+        user::ActiveModel {
+            id: Set(id),
+            username: Set(data.username.clone()),
+            password: Set(password),
+            real_name: Set(data.real_name.clone()),
+            email: Set(data.email.clone()),
+            phone: Set(data.phone.clone()),
+            avatar: Set(None),
+            avatar_thumb: Set(None),
+            gender: Set(data.gender),
+            status: Set(data.status),
+            created_time: Set(now),
+            created_id: Set(user_id),
+            updated_time: Set(now),
+            updated_id: Set(user_id),
+            deleted_time: Set(None),
+            deleted_id: Set(None),
+            tokens_revoked_time: Set(None),
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+        for role_id in &data.role_ids {
+            user_role::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                user_id: Set(id),
+                role_id: Set(*role_id),
+                created_time: Set(now),
+                created_id: Set(user_id),
+            }
+            .insert(&txn)
+            .await
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+        }
+    }
+
+    txn.commit()
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    Ok(Json(ApiResponse::success_with_message(
+        UserImportResult { dry_run, total, imported: users.len(), errors },
+        i18n::t(depot, "导入成功"),
+    )))
+}
+
+/// 导出用户
+///
+/// 筛选条件与用户列表相同，忽略分页参数。仅管理员可用。
+#[endpoint(
+    tags("用户管理"),
+    parameters(
+        ("format" = Option<String>, Query, description = "导出格式：xlsx（默认）或 csv"),
+        ("username" = Option<String>, Query, description = "用户名（模糊搜索）"),
+        ("realName" = Option<String>, Query, description = "昵称（模糊搜索）"),
+        ("gender" = Option<i16>, Query, description = "性别"),
+        ("email" = Option<String>, Query, description = "邮箱（模糊搜索）"),
+        ("phone" = Option<String>, Query, description = "手机号（模糊搜索）"),
+        ("status" = Option<i16>, Query, description = "用户状态：1-启用，0-禁用"),
+    ),
+    responses(
+        (status_code = 200, description = "导出文件"),
+        (status_code = 400, description = "参数错误或数据过多"),
+        (status_code = 403, description = "需要管理员权限"),
+        (status_code = 500, description = "服务器错误")
+    )
+)]
+pub async fn export_users(
+    req: &mut Request,
+    depot: &Depot,
+    res: &mut Response,
+) -> Result<(), AppError> {
+    let format = SheetFormat::from_param(req.query::<String>("format").as_deref())?;
+    let params = req
+        .parse_queries::<UserListQuery>()
+        .map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+    let db = depot.get::<Arc<DatabaseConnection>>("db")
+        .map_err(|_| AppError::InternalServerError("数据库服务不可用".to_string()))?;
+
+    let query_builder = filtered_users(&params);
+    let total = query_builder.clone()
+        .count(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+    if total > MAX_EXPORT_ROWS {
+        return Err(AppError::BadRequest(format!(
+            "导出数据过多，请缩小筛选范围（最多 {} 条）",
+            MAX_EXPORT_ROWS
+        )));
+    }
+
+    let users = query_builder
+        .order_by_desc(user::Column::CreatedTime)
+        .all(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+
+    // 用户ID -> 角色代码列表
+    let mut role_codes: HashMap<Uuid, Vec<String>> = HashMap::new();
+    let links = user_role::Entity::find()
+        .filter(user_role::Column::UserId.is_in(users.iter().map(|u| u.id)))
+        .find_also_related(role::Entity)
+        .all(db.as_ref())
+        .await
+        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+    for (link, r) in links {
+        if let Some(r) = r.filter(|r| r.deleted_time.is_none()) {
+            role_codes.entry(link.user_id).or_default().push(r.code);
+        }
+    }
+
+    let mut rows = vec![
+        ["用户名", "真实姓名", "邮箱", "手机号", "性别", "状态", "角色代码", "创建时间"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>(),
+    ];
+    for u in users {
+        let mut codes = role_codes.remove(&u.id).unwrap_or_default();
+        codes.sort();
+        rows.push(vec![
+            u.username,
+            u.real_name,
+            u.email.unwrap_or_default(),
+            u.phone.unwrap_or_default(),
+            u.gender.to_string(),
+            u.status.to_string(),
+            codes.join(","),
+            u.created_time.format("%Y-%m-%d %H:%M:%S").to_string(),
+        ]);
+    }
+
+    let body = sheet::write_rows(format, &rows)?;
+    write_attachment(res, format, "users", body)
+}
+
+// ========== 辅助函数 ==========
+
+/// 按列表筛选条件构建查询，列表和导出共用
+fn filtered_users(params: &UserListQuery) -> Select<user::Entity> {
     let mut query_builder = user::Entity::find()
         .filter(user::Column::DeletedTime.is_null()); // 排除已删除用户
 
@@ -70,39 +459,218 @@ pub async fn get_user_list(
         }
     }
 
+    // 性别精确匹配
+    if let Some(gender) = params.gender {
+        query_builder = query_builder.filter(user::Column::Gender.eq(gender));
+    }
+
     // 状态精确匹配
     if let Some(status) = params.status {
         query_builder = query_builder.filter(user::Column::Status.eq(status));
     }
 
-    // 4. 查询总数（用于分页）
-    let total = query_builder.clone()
-        .count(db.as_ref())
-        .await
-        .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+    query_builder
+}
 
-    // 5. 分页查询数据，按创建时间倒序
-    let users = query_builder
-        .order_by_desc(user::Column::CreatedTime)
-        .offset((page - 1) * page_size)
-        .limit(page_size)
-        .all(db.as_ref())
-        .await
+fn write_attachment(
+    res: &mut Response,
+    format: SheetFormat,
+    name: &str,
+    body: Vec<u8>,
+) -> Result<(), AppError> {
+    res.add_header(
+        "Content-Disposition",
+        format!("attachment; filename=\"{}.{}\"", name, format.extension()),
+        true,
+    )
+    .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+    res.add_header("Content-Type", format.content_type(), true)
         .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+    res.write_body(body)
+        .map_err(|e| AppError::InternalServerError(e.to_string()))
+}
 
-    // 6. 转换为响应结构（隐藏敏感字段如密码）
-    let items: Vec<UserListItem> = users.into_iter().map(|u| UserListItem {
-        id: u.id.to_string(),
-        username: u.username,
-        real_name: u.real_name,
-        email: u.email,
-        phone: u.phone,
-        avatar: u.avatar,
-        status: u.status,
-        created_time: u.created_time.format("%Y-%m-%d %H:%M:%S").to_string(),
-    }).collect();
+/// 根据表头确定各字段所在列
+fn map_columns(header: &[String]) -> Result<HashMap<&'static str, usize>, AppError> {
+    let mut columns = HashMap::new();
+    for (index, title) in header.iter().enumerate() {
+        let title = title.trim().trim_end_matches('*').trim();
+        if let Some((key, _, _)) = IMPORT_COLUMNS
+            .iter()
+            .find(|(key, label, _)| title.eq_ignore_ascii_case(key) || title == *label)
+        {
+            columns.entry(*key).or_insert(index);
+        }
+    }
 
-    // 7. 返回分页响应
-    let page_response = PageResponse::new(items, total, page, page_size);
-    Ok(Json(ApiResponse::success(page_response)))
-}
\ No newline at end of file
+    for (key, label, required) in IMPORT_COLUMNS {
+        if *required && !columns.contains_key(key) {
+            return Err(AppError::BadRequest(format!("缺少必填列: {}", label)));
+        }
+    }
+    Ok(columns)
+}
+
+fn cell<'a>(row: &'a [String], columns: &HashMap<&'static str, usize>, key: &str) -> &'a str {
+    columns
+        .get(key)
+        .and_then(|&index| row.get(index))
+        .map(String::as_str)
+        .unwrap_or_default()
+}
+
+/// 校验通过的导入行
+struct ImportedUser {
+    username: String,
+    real_name: String,
+    password: String,
+    email: Option<String>,
+    phone: Option<String>,
+    gender: i16,
+    status: i16,
+    role_ids: Vec<Uuid>,
+}
+
+struct RowValidator<'a> {
+    columns: &'a HashMap<&'static str, usize>,
+    /// 数据库中已存在的用户名
+    taken: &'a HashSet<String>,
+    role_ids: &'a HashMap<String, Uuid>,
+    /// 调用者无权分配的角色
+    forbidden_roles: &'a HashSet<Uuid>,
+    /// 文件中已出现的用户名
+    seen: HashSet<String>,
+    errors: Vec<UserImportError>,
+}
+
+impl RowValidator<'_> {
+    /// 校验一行，记录该行的全部错误；有错误时返回 None
+    fn validate(&mut self, row_number: usize, row: &[String]) -> Option<ImportedUser> {
+        let error_count = self.errors.len();
+        let get = |key| cell(row, self.columns, key);
+
+        let username = get("username").to_string();
+        if username.is_empty() {
+            self.error(row_number, "username", "用户名不能为空".to_string());
+        } else if !validation::is_valid_username(&username) {
+            self.error(row_number, "username", "用户名格式不正确".to_string());
+        } else if self.taken.contains(&username) {
+            self.error(row_number, "username", format!("用户名 {} 已存在", username));
+        } else if !self.seen.insert(username.clone()) {
+            self.error(row_number, "username", format!("用户名 {} 在文件中重复", username));
+        }
+
+        let real_name = get("realName").to_string();
+        if real_name.is_empty() || real_name.chars().count() > 100 {
+            self.error(row_number, "realName", "真实姓名长度必须在1-100之间".to_string());
+        }
+
+        let password = get("password").to_string();
+        let length = password.chars().count();
+        if !(PASSWORD_MIN_LENGTH..=PASSWORD_MAX_LENGTH).contains(&length) {
+            self.error(
+                row_number,
+                "password",
+                format!("密码长度必须在{}-{}之间", PASSWORD_MIN_LENGTH, PASSWORD_MAX_LENGTH),
+            );
+        }
+
+        let email = Some(get("email").to_string()).filter(|e| !e.is_empty());
+        if email.as_deref().is_some_and(|e| !validation::is_valid_email(e)) {
+            self.error(row_number, "email", "邮箱格式不正确".to_string());
+        }
+
+        let phone = Some(get("phone").to_string()).filter(|p| !p.is_empty());
+        if phone.as_deref().is_some_and(|p| !validation::is_valid_phone(p)) {
+            self.error(row_number, "phone", "手机号格式不正确".to_string());
+        }
+
+        let gender = match get("gender") {
+            "" => 0,
+            value => match value.parse::<i16>() {
+                Ok(g) if (0..=2).contains(&g) => g,
+                _ => {
+                    self.error(row_number, "gender", "性别必须为 0、1 或 2".to_string());
+                    0
+                }
+            },
+        };
+
+        let status = match get("status") {
+            "" => 1,
+            value => match value.parse::<i16>() {
+                Ok(s) if s == 0 || s == 1 => s,
+                _ => {
+                    self.error(row_number, "status", "状态必须为 0 或 1".to_string());
+                    1
+                }
+            },
+        };
+
+        let mut role_ids = Vec::new();
+        for code in get("roleCodes")
+            .split([',', '，', ';'])
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+        {
+            match self.role_ids.get(code) {
+                Some(id) if self.forbidden_roles.contains(id) => {
+                    self.error(row_number, "roleCodes", format!("无权分配角色 {}", code))
+                }
+                Some(id) if !role_ids.contains(id) => role_ids.push(*id),
+                Some(_) => {}
+                None => self.error(row_number, "roleCodes", format!("角色代码 {} 不存在", code)),
+            }
+        }
+
+        (self.errors.len() == error_count).then_some(ImportedUser {
+            username,
+            real_name,
+            password,
+            email,
+            phone,
+            gender,
+            status,
+            role_ids,
+        })
+    }
+
+    fn error(&mut self, row: usize, column: &str, message: String) {
+        self.errors.push(UserImportError {
+            row,
+            column: Some(column.to_string()),
+            message,
+        });
+    }
+}
+
+/// 批量计算密码哈希，bcrypt 计算较慢，按 CPU 核数并行
+async fn hash_passwords(passwords: Vec<String>) -> Result<Vec<String>, AppError> {
+    tokio::task::spawn_blocking(move || {
+        let workers = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let chunk_size = passwords.len().div_ceil(workers).max(1);
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = passwords
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|p| crypto::hash_password(p))
+                            .collect::<Result<Vec<_>, _>>()
+                    })
+                })
+                .collect();
+            let mut hashes = Vec::with_capacity(passwords.len());
+            for handle in handles {
+                let chunk = handle
+                    .join()
+                    .map_err(|_| AppError::InternalServerError("密码哈希计算失败".to_string()))??;
+                hashes.extend(chunk);
+            }
+            Ok(hashes)
+        })
+    })
+    .await
+    .map_err(|e| AppError::InternalServerError(e.to_string()))?
+}
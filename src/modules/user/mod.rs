@@ -3,5 +3,6 @@
 pub mod dto;
 mod routes;
 mod handler;
+mod sheet;
 
 pub use routes::routes;
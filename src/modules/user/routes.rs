@@ -1,7 +1,17 @@
 use salvo::Router;
+use crate::common::middleware::{admin_only, auth_middleware};
 use crate::modules::user::handler;
 
 pub fn routes() -> Router {
     Router::with_path("user")
+        .hoop(auth_middleware)
         .push(Router::with_path("getUserList").get(handler::get_user_list))
-}
\ No newline at end of file
+        // 导入可分配角色、导出包含联系方式，仅管理员可用
+        .push(
+            Router::new()
+                .hoop(admin_only)
+                .push(Router::with_path("importTemplate").get(handler::download_import_template))
+                .push(Router::with_path("import").post(handler::import_users))
+                .push(Router::with_path("export").get(handler::export_users))
+        )
+}
@@ -0,0 +1,217 @@
+// 表格读写：导入导出共用的 CSV / XLSX 处理
+
+use calamine::{open_workbook_from_rs, Reader, Xlsx};
+use rust_xlsxwriter::{Format, Workbook};
+use std::borrow::Cow;
+use std::io::Cursor;
+
+use crate::common::AppError;
+
+/// UTF-8 BOM，Excel 打开 CSV 时依赖它识别编码
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+/// 以这些字符开头的单元格会被 Excel 等表格软件当作公式
+const FORMULA_PREFIXES: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+/// 表格格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SheetFormat {
+    Csv,
+    Xlsx,
+}
+
+impl SheetFormat {
+    /// 解析 format 查询参数，默认 xlsx
+    pub fn from_param(format: Option<&str>) -> Result<Self, AppError> {
+        match format.unwrap_or("xlsx") {
+            "csv" => Ok(SheetFormat::Csv),
+            "xlsx" => Ok(SheetFormat::Xlsx),
+            _ => Err(AppError::BadRequest("不支持的文件格式".to_string())),
+        }
+    }
+
+    /// 按上传文件的扩展名判断格式
+    pub fn from_file_name(file_name: &str) -> Result<Self, AppError> {
+        let extension = file_name
+            .rsplit_once('.')
+            .map(|(_, ext)| ext.to_ascii_lowercase());
+        Self::from_param(Some(extension.as_deref().unwrap_or_default()))
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            SheetFormat::Csv => "csv",
+            SheetFormat::Xlsx => "xlsx",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            SheetFormat::Csv => "text/csv; charset=utf-8",
+            SheetFormat::Xlsx => {
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+            }
+        }
+    }
+}
+
+/// 读取全部行（含表头），单元格统一转为去除首尾空白的字符串，并还原导出时的公式转义
+pub fn read_rows(format: SheetFormat, data: &[u8]) -> Result<Vec<Vec<String>>, AppError> {
+    match format {
+        SheetFormat::Csv => {
+            let data = data.strip_prefix(UTF8_BOM).unwrap_or(data);
+            csv::ReaderBuilder::new()
+                .has_headers(false)
+                .flexible(true)
+                .from_reader(data)
+                .records()
+                .map(|record| {
+                    record
+                        .map(|r| {
+                            r.iter()
+                                .map(|cell| unescape_formula(cell.trim()).to_string())
+                                .collect()
+                        })
+                        .map_err(|e| AppError::BadRequest(format!("CSV 解析失败: {}", e)))
+                })
+                .collect()
+        }
+        SheetFormat::Xlsx => {
+            let mut workbook: Xlsx<_> = open_workbook_from_rs(Cursor::new(data))
+                .map_err(|e| AppError::BadRequest(format!("Excel 解析失败: {}", e)))?;
+            let range = workbook
+                .worksheet_range_at(0)
+                .ok_or_else(|| AppError::BadRequest("Excel 文件中没有工作表".to_string()))?
+                .map_err(|e| AppError::BadRequest(format!("Excel 解析失败: {}", e)))?;
+            Ok(range
+                .rows()
+                .map(|row| {
+                    row.iter()
+                        .map(|cell| unescape_formula(cell.to_string().trim()).to_string())
+                        .collect()
+                })
+                .collect())
+        }
+    }
+}
+
+/// 写出表格，第一行为表头；以公式字符开头的单元格加单引号转义，避免打开文件时执行公式
+pub fn write_rows(format: SheetFormat, rows: &[Vec<String>]) -> Result<Vec<u8>, AppError> {
+    match format {
+        SheetFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(UTF8_BOM.to_vec());
+            for row in rows {
+                writer
+                    .write_record(row.iter().map(|value| escape_formula(value).into_owned()))
+                    .map_err(|e| AppError::InternalServerError(e.to_string()))?;
+            }
+            writer
+                .into_inner()
+                .map_err(|e| AppError::InternalServerError(e.to_string()))
+        }
+        SheetFormat::Xlsx => {
+            let mut workbook = Workbook::new();
+            let worksheet = workbook.add_worksheet();
+            let header_format = Format::new().set_bold();
+            for (r, row) in rows.iter().enumerate() {
+                for (c, value) in row.iter().enumerate() {
+                    let value = escape_formula(value);
+                    let value = value.as_ref();
+                    let result = if r == 0 {
+                        worksheet.write_string_with_format(r as u32, c as u16, value, &header_format)
+                    } else {
+                        worksheet.write_string(r as u32, c as u16, value)
+                    };
+                    result.map_err(|e| AppError::InternalServerError(e.to_string()))?;
+                }
+            }
+            workbook
+                .save_to_buffer()
+                .map_err(|e| AppError::InternalServerError(e.to_string()))
+        }
+    }
+}
+
+// ========== 辅助函数 ==========
+
+fn escape_formula(value: &str) -> Cow<'_, str> {
+    if value.starts_with(FORMULA_PREFIXES) {
+        Cow::Owned(format!("'{}", value))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+/// 去掉 escape_formula 添加的单引号，导出的文件可以直接再导入
+fn unescape_formula(value: &str) -> &str {
+    match value.strip_prefix('\'') {
+        Some(rest) if rest.starts_with(FORMULA_PREFIXES) => rest,
+        _ => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows() -> Vec<Vec<String>> {
+        [
+            ["用户名", "邮箱", "备注"],
+            ["alice", "alice@example.com", "=HYPERLINK(\"http://evil\")"],
+            ["bob", "@bob", "-1+2"],
+            ["carol", "'quoted", "plain"],
+        ]
+        .iter()
+        .map(|row| row.iter().map(|s| s.to_string()).collect())
+        .collect()
+    }
+
+    #[test]
+    fn escape_formula_prefixes_dangerous_cells() {
+        assert_eq!(escape_formula("=SUM(A1:A2)"), "'=SUM(A1:A2)");
+        assert_eq!(escape_formula("+1"), "'+1");
+        assert_eq!(escape_formula("@cmd"), "'@cmd");
+        assert_eq!(escape_formula("\tx"), "'\tx");
+        assert!(matches!(escape_formula("alice"), Cow::Borrowed("alice")));
+        assert_eq!(escape_formula(""), "");
+    }
+
+    #[test]
+    fn unescape_formula_only_strips_added_quote() {
+        assert_eq!(unescape_formula("'=SUM(A1:A2)"), "=SUM(A1:A2)");
+        assert_eq!(unescape_formula("'quoted"), "'quoted");
+        assert_eq!(unescape_formula("'"), "'");
+        assert_eq!(unescape_formula("=raw"), "=raw");
+        for value in ["=1", "-2", "@a", "'b", "c"] {
+            assert_eq!(unescape_formula(&escape_formula(value)), value);
+        }
+    }
+
+    #[test]
+    fn csv_round_trip_keeps_values() {
+        let data = write_rows(SheetFormat::Csv, &rows()).unwrap();
+        assert!(data.starts_with(UTF8_BOM));
+        let text = String::from_utf8_lossy(&data);
+        assert!(text.contains("'=HYPERLINK"));
+        assert_eq!(read_rows(SheetFormat::Csv, &data).unwrap(), rows());
+    }
+
+    #[test]
+    fn xlsx_round_trip_keeps_values() {
+        let data = write_rows(SheetFormat::Xlsx, &rows()).unwrap();
+        assert_eq!(read_rows(SheetFormat::Xlsx, &data).unwrap(), rows());
+    }
+
+    #[test]
+    fn format_is_detected_from_file_name() {
+        assert_eq!(
+            SheetFormat::from_file_name("users.CSV").unwrap(),
+            SheetFormat::Csv
+        );
+        assert_eq!(
+            SheetFormat::from_file_name("users.xlsx").unwrap(),
+            SheetFormat::Xlsx
+        );
+        assert!(SheetFormat::from_file_name("users").is_err());
+        assert!(SheetFormat::from_param(Some("xls")).is_err());
+    }
+}
@@ -1,12 +1,23 @@
 use salvo::prelude::*;
 use salvo::oapi::OpenApi;
+use crate::common::constants::API_PREFIX;
 use crate::modules;
 
 pub fn create_router() -> Router {
-    Router::with_path("api/v1")
+    Router::with_path(API_PREFIX)
         .push(modules::health::routes())
         .push(modules::auth::routes())
+        .push(modules::user::routes())
         .push(modules::menu::routes())
+        .push(modules::recycle_bin::routes())
+        .push(modules::system::routes())
+        .push(modules::dict::routes())
+        .push(modules::notice::routes())
+        .push(modules::push::routes())
+        .push(modules::job::routes())
+        .push(modules::file::routes())
+        .push(modules::monitor::routes())
+        .push(modules::codegen::routes())
 }
 
 pub fn create_openapi() -> OpenApi {